@@ -15,6 +15,20 @@ pub enum SwapError {
     InsufficientFunds,
     Overflow,
     InvalidDecimals,
+    AuctionNotEnded,
+    AuctionEnded,
+    BidTooLow,
+    RevealNotOpen,
+    InvalidCommitment,
+    OfferExpired,
+    OrdersNotCrossing,
+    LimitPriceExceeded,
+    NothingUnlocked,
+    InvalidOracle,
+    StaleOracle,
+    TriggerNotMet,
+    OrderNotStarted,
+    AccountNotMigrated,
 }
 
 impl From<SwapError> for ProgramError {
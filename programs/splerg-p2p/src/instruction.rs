@@ -1,6 +1,15 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankInstruction;
 
+use crate::state::TriggerCondition;
+
+/// Amounts for one order of a batch instruction
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderAmounts {
+    pub maker_amount: u64,
+    pub taker_amount: u64,
+}
+
 #[derive(BorshSerialize, ShankInstruction, BorshDeserialize, Debug)]
 pub enum SwapInstruction {
     #[account(0, signer, name = "payer", desc = "Payer account")]
@@ -33,6 +42,10 @@ pub enum SwapInstruction {
     #[account(5, name = "token_program", desc = "Token program")]
     Harvest,
 
+    /// Passing the position mint PDA, the maker's ATA for it, the Token 2022 program and the
+    /// associated token program as trailing accounts tokenizes the order: the maker gets
+    /// the single token of the position mint, and whoever holds it can reprice, cancel
+    /// and receive the proceeds in the maker's place
     #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
     #[account(
         1,
@@ -62,6 +75,9 @@ pub enum SwapInstruction {
         taker_amount: u64,
     },
 
+    /// A tokenized order is repriced by the position holder, who passes their position
+    /// token account as a trailing account. A registered order takes its maker registry
+    /// PDA as the last account
     #[account(0, signer, name = "maker", desc = "Maker account")]
     #[account(1, writable, name = "order", desc = "Order PDA account")]
     #[account(
@@ -88,6 +104,16 @@ pub enum SwapInstruction {
     #[account(2, name = "new_taker", desc = "New taker pubkey")]
     ChangeTaker { new_taker: [u8; 32] },
 
+    /// The trailing accounts are only passed when the order uses them: the funding
+    /// account of a recurring order, the group of a one-cancels-other member, then the
+    /// follow-up order PDA and system program of a chained order, then the vesting PDA and
+    /// system program of a vesting order, then the price account of a pegged order, then
+    /// the trigger price account of a stop order, then the order's taker mint ATA holding a
+    /// reservation bond, then the position token account of a tokenized order, whose owner
+    /// is paid in place of the maker, then the maker registry PDA of a registered order.
+    /// A chained order takes the follow-up order's escrow
+    /// as `maker_receiving_account`, a vesting order takes the vesting PDA's maker mint ATA
+    /// as `taker_receiving_account`. Pegged orders are filled with `CompletePeggedSwap`
     #[account(0, signer, name = "taker", desc = "Taker account")]
     #[account(1, writable, name = "order", desc = "Order PDA account")]
     #[account(
@@ -118,8 +144,59 @@ pub enum SwapInstruction {
     #[account(7, name = "taker_mint", desc = "Taker mint")]
     #[account(8, name = "token_program", desc = "Token program")]
     #[account(9, name = "token_authority", desc = "Token Authority PDA")]
+    #[account(
+        13,
+        optional,
+        writable,
+        name = "funding_account",
+        desc = "Recurring order funding account"
+    )]
+    #[account(
+        14,
+        optional,
+        writable,
+        name = "order_group",
+        desc = "One-cancels-other group of the order"
+    )]
+    #[account(
+        15,
+        optional,
+        writable,
+        name = "follow_up_order",
+        desc = "Order PDA a chained order re-lists into"
+    )]
+    #[account(16, optional, name = "system_program", desc = "System program")]
+    #[account(
+        17,
+        optional,
+        writable,
+        name = "vesting",
+        desc = "Vesting PDA created for the taker"
+    )]
+    #[account(
+        18,
+        optional,
+        name = "oracle",
+        desc = "Price account of a pegged order"
+    )]
+    #[account(
+        19,
+        optional,
+        name = "trigger_oracle",
+        desc = "Price account gating a conditional order"
+    )]
+    #[account(
+        20,
+        optional,
+        writable,
+        name = "order_taker_ata",
+        desc = "Order PDA's taker mint ATA holding a reservation bond"
+    )]
     CompleteSwap,
 
+    /// A tokenized order is closed by the position holder, who passes their position
+    /// token account as a trailing account. A registered order takes its maker registry
+    /// PDA as the last account
     #[account(
         0,
         signer,
@@ -128,4 +205,1522 @@ pub enum SwapInstruction {
     )]
     #[account(1, writable, name = "order", desc = "Order PDA account")]
     CloseOrder,
+
+    #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
+    #[account(
+        1,
+        writable,
+        name = "order",
+        desc = "Order PDA account (to be created)"
+    )]
+    #[account(2, writable, name = "maker_ata", desc = "Maker mint ATA (initialized)")]
+    #[account(
+        3,
+        writable,
+        name = "pda_maker_ata",
+        desc = "PDA maker mint ATA (initialized)"
+    )]
+    #[account(4, name = "id", desc = "order id")]
+    #[account(5, name = "maker_mint", desc = "Maker token mint")]
+    #[account(6, name = "taker_mint", desc = "Taker token mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(
+        9,
+        name = "token_program",
+        desc = "Token Program (optional Token 2022)"
+    )]
+    InitializeAuction {
+        maker_amount: u64,
+        reserve_price: u64,
+        end_ts: i64,
+    },
+
+    #[account(0, signer, name = "bidder", desc = "Bidder (pays bid account rent)")]
+    #[account(1, writable, name = "order", desc = "Auction order PDA account")]
+    #[account(2, writable, name = "bid", desc = "Bid PDA account")]
+    #[account(3, writable, name = "bid_escrow", desc = "Bid PDA taker mint ATA")]
+    #[account(
+        4,
+        writable,
+        name = "bidder_taker_ata",
+        desc = "Bidder's taker mint token account"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "previous_bidder_taker_ata",
+        desc = "Previous bidder's taker mint token account (refund)"
+    )]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(9, name = "token_program", desc = "Token program of the taker mint")]
+    PlaceBid { amount: u64 },
+
+    #[account(0, writable, name = "order", desc = "Auction order PDA account")]
+    #[account(1, writable, name = "bid", desc = "Bid PDA account")]
+    #[account(2, writable, name = "bid_escrow", desc = "Bid PDA taker mint ATA")]
+    #[account(
+        3,
+        writable,
+        name = "bidder",
+        desc = "Winning bidder, receives bid rent"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "maker_taker_ata",
+        desc = "Maker's taker mint token account"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "bidder_maker_ata",
+        desc = "Winning bidder's maker mint token account"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(7, name = "treasury", desc = "Treasury PDA account")]
+    #[account(
+        8,
+        writable,
+        name = "treasury_maker_ata",
+        desc = "Treasury maker mint ATA"
+    )]
+    #[account(
+        9,
+        writable,
+        name = "treasury_taker_ata",
+        desc = "Treasury taker mint ATA"
+    )]
+    #[account(10, name = "maker_mint", desc = "Maker mint")]
+    #[account(11, name = "taker_mint", desc = "Taker mint")]
+    #[account(12, name = "token_program", desc = "Token program")]
+    #[account(13, name = "token_program_2022", desc = "Token 2022 program")]
+    SettleAuction,
+
+    #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
+    #[account(
+        1,
+        writable,
+        name = "order",
+        desc = "Order PDA account (to be created)"
+    )]
+    #[account(2, writable, name = "maker_ata", desc = "Maker mint ATA (initialized)")]
+    #[account(
+        3,
+        writable,
+        name = "pda_maker_ata",
+        desc = "PDA maker mint ATA (initialized)"
+    )]
+    #[account(4, name = "id", desc = "order id")]
+    #[account(5, name = "maker_mint", desc = "Maker token mint")]
+    #[account(6, name = "taker_mint", desc = "Taker token mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(
+        9,
+        name = "token_program",
+        desc = "Token Program (optional Token 2022)"
+    )]
+    InitializeSealedAuction {
+        maker_amount: u64,
+        reserve_price: u64,
+        commit_end_ts: i64,
+        reveal_end_ts: i64,
+        penalty_bps: u16,
+    },
+
+    #[account(0, signer, name = "bidder", desc = "Bidder (pays sealed bid rent)")]
+    #[account(1, name = "order", desc = "Sealed auction order PDA account")]
+    #[account(2, writable, name = "sealed_bid", desc = "Sealed bid PDA account")]
+    #[account(
+        3,
+        writable,
+        name = "bid_escrow",
+        desc = "Sealed bid PDA taker mint ATA"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "bidder_taker_ata",
+        desc = "Bidder's taker mint token account"
+    )]
+    #[account(5, name = "taker_mint", desc = "Taker mint")]
+    #[account(6, name = "system_program", desc = "System program")]
+    #[account(7, name = "rent", desc = "Rent sysvar")]
+    #[account(8, name = "token_program", desc = "Token program of the taker mint")]
+    CommitBid { commitment: [u8; 32], deposit: u64 },
+
+    #[account(0, signer, name = "bidder", desc = "Bidder")]
+    #[account(1, writable, name = "order", desc = "Sealed auction order PDA account")]
+    #[account(2, writable, name = "sealed_bid", desc = "Sealed bid PDA account")]
+    RevealBid { amount: u64, salt: [u8; 32] },
+
+    #[account(0, writable, name = "order", desc = "Sealed auction order PDA account")]
+    #[account(
+        1,
+        writable,
+        name = "sealed_bid",
+        desc = "Winning sealed bid PDA account"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "bid_escrow",
+        desc = "Sealed bid PDA taker mint ATA"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "bidder",
+        desc = "Winning bidder, receives bid rent"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "maker_taker_ata",
+        desc = "Maker's taker mint token account"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "bidder_taker_ata",
+        desc = "Winning bidder's taker mint token account (deposit change)"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "bidder_maker_ata",
+        desc = "Winning bidder's maker mint token account"
+    )]
+    #[account(
+        7,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(8, name = "treasury", desc = "Treasury PDA account")]
+    #[account(
+        9,
+        writable,
+        name = "treasury_maker_ata",
+        desc = "Treasury maker mint ATA"
+    )]
+    #[account(
+        10,
+        writable,
+        name = "treasury_taker_ata",
+        desc = "Treasury taker mint ATA"
+    )]
+    #[account(11, name = "maker_mint", desc = "Maker mint")]
+    #[account(12, name = "taker_mint", desc = "Taker mint")]
+    #[account(13, name = "token_program", desc = "Token program")]
+    #[account(14, name = "token_program_2022", desc = "Token 2022 program")]
+    SettleSealedAuction,
+
+    #[account(
+        0,
+        writable,
+        name = "bidder",
+        desc = "Bidder, receives sealed bid rent"
+    )]
+    #[account(
+        1,
+        name = "order",
+        desc = "Sealed auction order PDA account (may be closed)"
+    )]
+    #[account(2, writable, name = "sealed_bid", desc = "Sealed bid PDA account")]
+    #[account(
+        3,
+        writable,
+        name = "bid_escrow",
+        desc = "Sealed bid PDA taker mint ATA"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "bidder_taker_ata",
+        desc = "Bidder's taker mint token account"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "maker_taker_ata",
+        desc = "Maker's taker mint token account (unrevealed penalty)"
+    )]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "token_program", desc = "Token program of the taker mint")]
+    RefundSealedBid,
+
+    #[account(0, signer, name = "taker", desc = "Taker (pays counter offer rent)")]
+    #[account(1, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "counter_offer",
+        desc = "Counter offer PDA account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "offer_escrow",
+        desc = "Counter offer PDA taker mint ATA"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "taker_ata",
+        desc = "Taker's taker mint token account"
+    )]
+    #[account(5, name = "taker_mint", desc = "Taker mint")]
+    #[account(6, name = "system_program", desc = "System program")]
+    #[account(7, name = "rent", desc = "Rent sysvar")]
+    #[account(8, name = "token_program", desc = "Token program of the taker mint")]
+    ProposeCounterOffer {
+        maker_amount: u64,
+        taker_amount: u64,
+        expires_ts: i64,
+    },
+
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "counter_offer",
+        desc = "Counter offer PDA account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "offer_escrow",
+        desc = "Counter offer PDA taker mint ATA"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "taker",
+        desc = "Proposing taker, receives offer rent"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "maker_taker_ata",
+        desc = "Maker's taker mint token account"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "maker_maker_ata",
+        desc = "Maker's maker mint token account (unsold remainder)"
+    )]
+    #[account(
+        7,
+        writable,
+        name = "taker_maker_ata",
+        desc = "Taker's maker mint token account"
+    )]
+    #[account(
+        8,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(9, name = "treasury", desc = "Treasury PDA account")]
+    #[account(
+        10,
+        writable,
+        name = "treasury_maker_ata",
+        desc = "Treasury maker mint ATA"
+    )]
+    #[account(
+        11,
+        writable,
+        name = "treasury_taker_ata",
+        desc = "Treasury taker mint ATA"
+    )]
+    #[account(12, name = "maker_mint", desc = "Maker mint")]
+    #[account(13, name = "taker_mint", desc = "Taker mint")]
+    #[account(14, name = "token_program", desc = "Token program")]
+    #[account(15, name = "token_program_2022", desc = "Token 2022 program")]
+    AcceptCounterOffer,
+
+    #[account(0, signer, writable, name = "taker", desc = "Proposing taker")]
+    #[account(1, name = "order", desc = "Order PDA account (may be closed)")]
+    #[account(
+        2,
+        writable,
+        name = "counter_offer",
+        desc = "Counter offer PDA account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "offer_escrow",
+        desc = "Counter offer PDA taker mint ATA"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "taker_ata",
+        desc = "Taker's taker mint token account"
+    )]
+    #[account(5, name = "taker_mint", desc = "Taker mint")]
+    #[account(6, name = "token_program", desc = "Token program of the taker mint")]
+    WithdrawCounterOffer,
+
+    #[account(0, signer, name = "authority", desc = "Treasury authority")]
+    #[account(1, writable, name = "treasury", desc = "Treasury PDA account")]
+    UpdateCrankerShare { cranker_share_bps: u16 },
+
+    /// A registered order takes its maker registry PDA as a trailing account, order A's
+    /// before order B's
+    #[account(0, signer, name = "cranker", desc = "Anyone settling the match")]
+    #[account(
+        1,
+        writable,
+        name = "order_a",
+        desc = "Order PDA selling mint X for mint Y"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "order_b",
+        desc = "Order PDA selling mint Y for mint X"
+    )]
+    #[account(3, writable, name = "order_a_escrow", desc = "Order A mint X ATA")]
+    #[account(4, writable, name = "order_b_escrow", desc = "Order B mint Y ATA")]
+    #[account(
+        5,
+        writable,
+        name = "maker_a_ata",
+        desc = "Order A maker's mint Y token account"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "maker_b_ata",
+        desc = "Order B maker's mint X token account"
+    )]
+    #[account(
+        7,
+        writable,
+        name = "cranker_ata",
+        desc = "Cranker's mint Y token account"
+    )]
+    #[account(8, name = "treasury", desc = "Treasury PDA account")]
+    #[account(9, writable, name = "treasury_x_ata", desc = "Treasury mint X ATA")]
+    #[account(10, writable, name = "treasury_y_ata", desc = "Treasury mint Y ATA")]
+    #[account(11, name = "mint_x", desc = "Mint X")]
+    #[account(12, name = "mint_y", desc = "Mint Y")]
+    #[account(13, name = "token_program", desc = "Token program")]
+    #[account(14, name = "token_program_2022", desc = "Token 2022 program")]
+    MatchOrders,
+
+    /// Remaining accounts hold 6 accounts per order of the ring, in cycle order:
+    /// order, order escrow, order maker mint, previous order maker's ATA for that mint,
+    /// treasury ATA for that mint, submitter's ATA for that mint
+    #[account(
+        0,
+        signer,
+        name = "submitter",
+        desc = "Ring submitter, receives the surplus"
+    )]
+    #[account(1, name = "treasury", desc = "Treasury PDA account")]
+    #[account(2, name = "token_program", desc = "Token program")]
+    #[account(3, name = "token_program_2022", desc = "Token 2022 program")]
+    SettleRing,
+
+    /// Fills same-pair orders in the given order until `max_taker_amount` is spent,
+    /// failing unless at least `min_maker_amount` is received after fees.
+    /// Remaining accounts hold 3 accounts per order: order, order escrow, maker's taker mint ATA
+    #[account(0, signer, name = "taker", desc = "Taker account")]
+    #[account(
+        1,
+        writable,
+        name = "taker_ata",
+        desc = "Taker's taker mint token account"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "taker_maker_ata",
+        desc = "Taker's maker mint token account"
+    )]
+    #[account(3, name = "treasury", desc = "Treasury PDA account")]
+    #[account(
+        4,
+        writable,
+        name = "treasury_maker_ata",
+        desc = "Treasury maker mint ATA"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "treasury_taker_ata",
+        desc = "Treasury taker mint ATA"
+    )]
+    #[account(6, name = "maker_mint", desc = "Maker mint")]
+    #[account(7, name = "taker_mint", desc = "Taker mint")]
+    #[account(8, name = "token_program", desc = "Token program")]
+    #[account(9, name = "token_program_2022", desc = "Token 2022 program")]
+    SweepOrders {
+        max_taker_amount: u64,
+        min_maker_amount: u64,
+    },
+
+    /// Creates one order per level, the `i`th order id is `SwapOrder::batch_id(base_id, i)`.
+    /// Remaining accounts hold 2 accounts per level: order PDA, order maker mint ATA
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "maker",
+        desc = "Maker (order creator, pays rent)"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "maker_ata",
+        desc = "Maker's maker mint token account"
+    )]
+    #[account(2, name = "maker_mint", desc = "Maker mint")]
+    #[account(3, name = "taker_mint", desc = "Taker mint")]
+    #[account(4, name = "system_program", desc = "System program")]
+    #[account(5, name = "token_program", desc = "Token program of the maker mint")]
+    BatchInitializeOrders {
+        base_id: [u8; 32],
+        levels: Vec<OrderAmounts>,
+    },
+
+    /// Remaining accounts hold 2 accounts per level: order PDA, order maker mint ATA
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(
+        1,
+        writable,
+        name = "maker_ata",
+        desc = "Maker's maker mint token account"
+    )]
+    #[account(2, name = "maker_mint", desc = "Maker mint")]
+    #[account(3, name = "token_program", desc = "Token program of the maker mint")]
+    BatchChangeOrderAmounts { levels: Vec<OrderAmounts> },
+
+    /// Refunds the escrow and closes each order.
+    /// Remaining accounts hold 2 accounts per order: order PDA, order maker mint ATA
+    #[account(0, signer, writable, name = "maker", desc = "Maker account")]
+    #[account(
+        1,
+        writable,
+        name = "maker_ata",
+        desc = "Maker's maker mint token account"
+    )]
+    #[account(2, name = "maker_mint", desc = "Maker mint")]
+    #[account(3, name = "token_program", desc = "Token program of the maker mint")]
+    BatchCancelOrders,
+
+    #[account(0, signer, writable, name = "maker", desc = "Maker (pays rent)")]
+    #[account(1, writable, name = "maker_account", desc = "Maker registry PDA")]
+    #[account(2, name = "system_program", desc = "System program")]
+    #[account(3, name = "rent", desc = "Rent sysvar")]
+    InitializeMakerAccount,
+
+    /// Same accounts as `InitializeOrder` with the order id taken from the registry nonce,
+    /// `MakerAccount::order_id(maker, nonce)`
+    #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
+    #[account(
+        1,
+        writable,
+        name = "order",
+        desc = "Order PDA account (must be uninitialized)"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's token account (source of tokens)"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(
+        4,
+        name = "order_id",
+        desc = "Order id derived from the registry nonce"
+    )]
+    #[account(5, name = "maker_mint", desc = "Maker mint")]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(9, name = "token_program", desc = "Token program")]
+    #[account(10, writable, name = "maker_account", desc = "Maker registry PDA")]
+    InitializeRegisteredOrder {
+        maker_amount: u64,
+        taker_amount: u64,
+    },
+
+    /// Refunds and closes registered orders, which must be passed in nonce order.
+    /// Remaining accounts hold 4 accounts per order: order PDA, order maker mint ATA,
+    /// maker's maker mint ATA, maker mint
+    #[account(0, signer, writable, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "maker_account", desc = "Maker registry PDA")]
+    #[account(2, name = "token_program", desc = "Token program")]
+    #[account(3, name = "token_program_2022", desc = "Token 2022 program")]
+    CancelAllOrders,
+
+    /// Same accounts as `InitializeOrder`. After each `CompleteSwap` the order re-escrows
+    /// `maker_amount` from the funding account, which must delegate to the order PDA,
+    /// until `max_refills` or `refill_cap` runs out
+    #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
+    #[account(
+        1,
+        writable,
+        name = "order",
+        desc = "Order PDA account (must be uninitialized)"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's token account (source of tokens)"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(4, name = "order_id", desc = "Order id")]
+    #[account(5, name = "maker_mint", desc = "Maker mint")]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(9, name = "token_program", desc = "Token program")]
+    #[account(
+        10,
+        name = "funding_account",
+        desc = "Maker's maker mint token account refills are drawn from"
+    )]
+    InitializeRecurringOrder {
+        maker_amount: u64,
+        taker_amount: u64,
+        max_refills: u64,
+        refill_cap: u64,
+    },
+    /// Same accounts as `InitializeOrder`. The full `maker_amount` is escrowed but
+    /// `CompleteSwap` only fills `slice` maker tokens per `interval` seconds elapsed
+    #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
+    #[account(
+        1,
+        writable,
+        name = "order",
+        desc = "Order PDA account (must be uninitialized)"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's token account (source of tokens)"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(4, name = "order_id", desc = "Order id")]
+    #[account(5, name = "maker_mint", desc = "Maker mint")]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(9, name = "token_program", desc = "Token program")]
+    InitializeTwapOrder {
+        maker_amount: u64,
+        taker_amount: u64,
+        interval: i64,
+        slice: u64,
+    },
+    /// Links open fixed price orders of the maker so that filling one voids the others.
+    /// Remaining accounts are the member order PDAs, at least two
+    #[account(0, signer, writable, name = "maker", desc = "Maker (pays rent)")]
+    #[account(1, writable, name = "order_group", desc = "Order group PDA")]
+    #[account(2, name = "system_program", desc = "System program")]
+    InitializeOrderGroup { id: [u8; 32] },
+
+    /// Refunds and closes a group member voided by another member's fill, callable by anyone
+    #[account(0, name = "order_group", desc = "Order group PDA")]
+    #[account(1, writable, name = "order", desc = "Voided order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(3, writable, name = "maker", desc = "Maker account, receives the rent")]
+    #[account(
+        4,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's maker mint ATA"
+    )]
+    #[account(5, name = "maker_mint", desc = "Maker mint")]
+    #[account(6, name = "token_program", desc = "Token program")]
+    RefundGroupOrder,
+    /// Re-lists the proceeds of the order on fill, asking `taker_amount` of the follow-up
+    /// taker mint for all of them. The follow-up escrow is the ATA of the order PDA derived
+    /// from `SwapOrder::follow_up_id` and must exist before the fill
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "maker",
+        desc = "Maker (prepays follow-up rent)"
+    )]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        name = "follow_up_taker_mint",
+        desc = "Mint the proceeds are offered for"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
+    SetFollowUpOrder { taker_amount: u64, end_ts: i64 },
+    /// Makes the taker's maker tokens vest from the fill: nothing is claimable for `cliff`
+    /// seconds and everything after `duration` seconds. The taker creates the maker mint ATA
+    /// of the order's vesting PDA before filling
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    SetVestingSchedule { cliff: i64, duration: i64 },
+
+    /// Sends the vested part of a vesting schedule to the taker, closing it once fully claimed
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "beneficiary",
+        desc = "Taker of the vesting order"
+    )]
+    #[account(1, writable, name = "vesting", desc = "Vesting PDA")]
+    #[account(
+        2,
+        writable,
+        name = "vesting_token_account",
+        desc = "Vesting PDA's ATA"
+    )]
+    #[account(3, writable, name = "beneficiary_token_account", desc = "Taker's ATA")]
+    #[account(4, name = "mint", desc = "Vested mint")]
+    #[account(5, name = "token_program", desc = "Token program")]
+    ClaimVested,
+    /// Lets the order's taker deposit ahead of settlement until `deposit_end_ts`.
+    /// The maker's side is the order escrow, which `CloseOrder` withdraws while no
+    /// taker deposit is waiting
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    OpenDepositWindow { deposit_end_ts: i64 },
+
+    /// Deposits the order's `taker_amount` into the order PDA's taker mint ATA. The taker
+    /// passes the amounts they agreed to, the maker can reprice until the deposit lands
+    #[account(0, signer, name = "taker", desc = "Taker named on the order")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "taker_escrow_token_account",
+        desc = "Order PDA's taker mint ATA"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "taker_token_account",
+        desc = "Taker's taker mint ATA"
+    )]
+    #[account(4, name = "taker_mint", desc = "Taker mint")]
+    #[account(5, name = "token_program", desc = "Token program")]
+    DepositTakerTokens {
+        expected_maker_amount: u64,
+        expected_taker_amount: u64,
+    },
+
+    /// Swaps the maker escrow and the taker deposit, callable by anyone once both are in
+    #[account(0, signer, name = "settler", desc = "Maker, taker or keeper")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "taker_escrow_token_account",
+        desc = "Order PDA's taker mint ATA"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "maker_receiving_account",
+        desc = "Maker's taker mint ATA"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "taker_receiving_account",
+        desc = "Taker's maker mint ATA"
+    )]
+    #[account(6, name = "treasury", desc = "Treasury PDA")]
+    #[account(
+        7,
+        writable,
+        name = "treasury_maker_ata",
+        desc = "Treasury maker mint ATA"
+    )]
+    #[account(
+        8,
+        writable,
+        name = "treasury_taker_ata",
+        desc = "Treasury taker mint ATA"
+    )]
+    #[account(9, name = "maker_mint", desc = "Maker mint")]
+    #[account(10, name = "taker_mint", desc = "Taker mint")]
+    #[account(11, name = "token_program", desc = "Token program")]
+    #[account(12, name = "token_program_2022", desc = "Token 2022 program")]
+    SettleDeposits,
+
+    /// Returns an unsettled taker deposit after the deposit window
+    #[account(0, signer, name = "taker", desc = "Taker named on the order")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "taker_escrow_token_account",
+        desc = "Order PDA's taker mint ATA"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "taker_token_account",
+        desc = "Taker's taker mint ATA"
+    )]
+    #[account(4, name = "taker_mint", desc = "Taker mint")]
+    #[account(5, name = "token_program", desc = "Token program")]
+    WithdrawTakerDeposit,
+    /// Same accounts as `InitializeOrder`, followed by the arbitration accounts. The escrow
+    /// is released to the counterparty one `milestones` tranche at a time on the arbiter's
+    /// approval. Without milestones `maker_amount` is a single tranche
+    #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
+    #[account(
+        1,
+        writable,
+        name = "order",
+        desc = "Order PDA account (must be uninitialized)"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's token account (source of tokens)"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(4, name = "order_id", desc = "Order id")]
+    #[account(5, name = "maker_mint", desc = "Maker mint")]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(9, name = "token_program", desc = "Token program")]
+    #[account(
+        10,
+        name = "counterparty",
+        desc = "Taker receiving the released tranches"
+    )]
+    #[account(11, name = "arbiter", desc = "Arbiter approving releases")]
+    #[account(12, writable, name = "arbitration", desc = "Arbitration PDA")]
+    InitializeArbitratedOrder {
+        maker_amount: u64,
+        deadline_ts: i64,
+        milestones: Vec<u64>,
+    },
+
+    /// Releases the next milestone tranche to the counterparty
+    #[account(0, signer, name = "arbiter", desc = "Arbiter account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(2, writable, name = "arbitration", desc = "Arbitration PDA")]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "taker_receiving_account",
+        desc = "Counterparty's maker mint ATA"
+    )]
+    #[account(5, name = "treasury", desc = "Treasury PDA")]
+    #[account(
+        6,
+        writable,
+        name = "treasury_maker_ata",
+        desc = "Treasury maker mint ATA"
+    )]
+    #[account(7, name = "maker_mint", desc = "Maker mint")]
+    #[account(8, name = "token_program", desc = "Token program")]
+    #[account(
+        9,
+        writable,
+        name = "maker",
+        desc = "Maker, receives the arbitration rent after the last tranche"
+    )]
+    ApproveMilestone,
+
+    /// Refunds the unreleased escrow to the maker. The arbiter can rule a refund at any
+    /// time, anyone can trigger it after the arbitration deadline
+    #[account(0, name = "authority", desc = "Arbiter, or anyone after the deadline")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(2, writable, name = "arbitration", desc = "Arbitration PDA")]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's maker mint ATA"
+    )]
+    #[account(5, name = "maker_mint", desc = "Maker mint")]
+    #[account(6, name = "token_program", desc = "Token program")]
+    #[account(
+        7,
+        writable,
+        name = "maker",
+        desc = "Maker, receives the arbitration rent"
+    )]
+    RefundArbitratedOrder,
+
+    /// Pegs the order to a Pyth-style price account: each fill asks the oracle price for
+    /// the maker amount, adjusted by `premium_bps`. Fills fail while the price is older
+    /// than `max_age` seconds or its confidence is wider than `max_conf_bps`
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(2, name = "oracle", desc = "Price account, taker mint per maker mint")]
+    SetOraclePeg {
+        premium_bps: i16,
+        max_age: i64,
+        max_conf_bps: u16,
+    },
+
+    /// Makes the order conditional: fills are rejected until the trigger oracle's price
+    /// is on the `condition` side of `trigger_price`, given in the oracle's own units.
+    /// Prices older than `max_age` seconds or with a confidence wider than `max_conf_bps`
+    /// never satisfy the trigger
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(2, name = "trigger_oracle", desc = "Price account gating fills")]
+    SetOrderTrigger {
+        condition: TriggerCondition,
+        trigger_price: i64,
+        max_age: i64,
+        max_conf_bps: u16,
+    },
+
+    /// Schedules the order to open for fills at `start_ts`, 0 opens it right away. The
+    /// schedule is fixed once a start time has passed
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    SetOrderStart { start_ts: i64 },
+
+    /// Same accounts as `InitializeOrder`, followed by the counterparty. The escrow is
+    /// locked to the SHA-256 `hashlock` until `timelock_ts`, after which the maker can
+    /// reclaim it
+    #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
+    #[account(
+        1,
+        writable,
+        name = "order",
+        desc = "Order PDA account (must be uninitialized)"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's token account (source of tokens)"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(4, name = "order_id", desc = "Order id")]
+    #[account(5, name = "maker_mint", desc = "Maker mint")]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(9, name = "token_program", desc = "Token program")]
+    #[account(10, name = "counterparty", desc = "Taker the escrow is released to")]
+    InitializeHtlcOrder {
+        maker_amount: u64,
+        taker_amount: u64,
+        hashlock: [u8; 32],
+        timelock_ts: i64,
+    },
+
+    /// Releases the escrow to the counterparty before the timelock. Anyone holding the
+    /// preimage can submit it, and it's logged for the other chain's leg
+    #[account(0, signer, name = "claimer", desc = "Account submitting the preimage")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "taker_maker_ata",
+        desc = "Counterparty's maker mint ATA"
+    )]
+    #[account(4, name = "treasury", desc = "Treasury PDA account")]
+    #[account(
+        5,
+        writable,
+        name = "treasury_maker_ata",
+        desc = "Treasury maker mint ATA"
+    )]
+    #[account(6, name = "maker_mint", desc = "Maker mint")]
+    #[account(7, name = "token_program", desc = "Token program")]
+    ClaimHtlc { preimage: [u8; 32] },
+
+    /// Returns the escrow of an unclaimed HTLC to the maker once the timelock has passed
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's maker mint ATA"
+    )]
+    #[account(4, name = "maker_mint", desc = "Maker mint")]
+    #[account(5, name = "token_program", desc = "Token program")]
+    ReclaimHtlc,
+
+    /// Makes the order private: instead of a named taker it stores the hash of a claim
+    /// secret shared off-chain. A zero hash makes the order public again
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    SetClaimCode { claim_code_hash: [u8; 32] },
+
+    /// Commits to the claim secret, bound to the taker's pubkey, ahead of revealing it
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "taker",
+        desc = "Taker account (pays rent)"
+    )]
+    #[account(1, name = "order", desc = "Order PDA account")]
+    #[account(2, writable, name = "claim_commitment", desc = "Claim commitment PDA")]
+    #[account(3, name = "system_program", desc = "System program")]
+    CommitClaimCode { commitment: [u8; 32] },
+
+    /// Reveals the claim secret committed to in an earlier slot, making the committer the
+    /// order's taker so it can be filled with `CompleteSwap`
+    #[account(0, writable, signer, name = "taker", desc = "Taker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(2, writable, name = "claim_commitment", desc = "Claim commitment PDA")]
+    RevealClaimCode { secret: [u8; 32] },
+
+    /// Locks an open order to the taker for `slots` slots against a bond of
+    /// `RESERVATION_BOND_BPS` of the taker amount. Filling within the window returns the
+    /// bond, a lapsed reservation's bond is forfeited to the maker
+    #[account(0, signer, name = "taker", desc = "Taker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(2, writable, name = "taker_ata", desc = "Taker's taker mint ATA")]
+    #[account(
+        3,
+        writable,
+        name = "order_taker_ata",
+        desc = "Order PDA's taker mint ATA holding the bond"
+    )]
+    #[account(4, name = "taker_mint", desc = "Taker mint")]
+    #[account(5, name = "token_program", desc = "Token program")]
+    ReserveOrder { slots: u64 },
+
+    /// Pays the bond of a lapsed reservation to the maker and reopens the order
+    #[account(0, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        1,
+        writable,
+        name = "order_taker_ata",
+        desc = "Order PDA's taker mint ATA holding the bond"
+    )]
+    #[account(2, writable, name = "maker_taker_ata", desc = "Maker's taker mint ATA")]
+    #[account(3, name = "taker_mint", desc = "Taker mint")]
+    #[account(4, name = "token_program", desc = "Token program")]
+    ForfeitReservation,
+
+    /// Same accounts as `InitializeOrder`. Writes a call option over the escrow: whoever
+    /// buys it for `premium` can fill at `strike_amount` with `CompleteSwap` until
+    /// `expiry_ts`
+    #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
+    #[account(
+        1,
+        writable,
+        name = "order",
+        desc = "Order PDA account (must be uninitialized)"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's token account (source of tokens)"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(4, name = "order_id", desc = "Order id")]
+    #[account(5, name = "maker_mint", desc = "Maker mint")]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(9, name = "token_program", desc = "Token program")]
+    InitializeCoveredCall {
+        maker_amount: u64,
+        strike_amount: u64,
+        premium: u64,
+        expiry_ts: i64,
+    },
+
+    /// Buys an unsold covered call, the premium is paid to the maker less the treasury fee.
+    /// The buyer passes the terms they agreed to, the maker can reprice until the call is sold
+    #[account(0, signer, name = "buyer", desc = "Buyer, becomes the option holder")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(2, writable, name = "buyer_taker_ata", desc = "Buyer's taker mint ATA")]
+    #[account(3, writable, name = "maker_taker_ata", desc = "Maker's taker mint ATA")]
+    #[account(4, name = "treasury", desc = "Treasury PDA account")]
+    #[account(
+        5,
+        writable,
+        name = "treasury_taker_ata",
+        desc = "Treasury taker mint ATA"
+    )]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "token_program", desc = "Token program")]
+    BuyOption {
+        maker_amount: u64,
+        strike_amount: u64,
+        premium: u64,
+    },
+
+    /// Hands the option right to a new holder
+    #[account(0, signer, name = "holder", desc = "Current option holder")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(2, name = "new_holder", desc = "New option holder")]
+    TransferOption,
+
+    /// Returns a covered call's escrow to the maker once it has expired unexercised, or
+    /// at any time while it's unsold
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's maker mint ATA"
+    )]
+    #[account(4, name = "maker_mint", desc = "Maker mint")]
+    #[account(5, name = "token_program", desc = "Token program")]
+    ReclaimCoveredCall,
+
+    /// Same accounts as `InitializeOrder`, followed by the payer. Requests `taker_amount`
+    /// from the payer without escrowing anything
+    #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
+    #[account(
+        1,
+        writable,
+        name = "order",
+        desc = "Order PDA account (must be uninitialized)"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's token account (source of tokens)"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(4, name = "order_id", desc = "Order id")]
+    #[account(5, name = "maker_mint", desc = "Maker mint")]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(9, name = "token_program", desc = "Token program")]
+    #[account(10, name = "payer", desc = "Taker the payment is requested from")]
+    InitializeInvoice { taker_amount: u64 },
+
+    /// Pays an invoice to the maker, less the treasury fee
+    #[account(0, signer, name = "payer", desc = "Taker named on the invoice")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(2, writable, name = "payer_taker_ata", desc = "Payer's taker mint ATA")]
+    #[account(3, writable, name = "maker_taker_ata", desc = "Maker's taker mint ATA")]
+    #[account(4, name = "treasury", desc = "Treasury PDA account")]
+    #[account(
+        5,
+        writable,
+        name = "treasury_taker_ata",
+        desc = "Treasury taker mint ATA"
+    )]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "token_program", desc = "Token program")]
+    PayInvoice,
+
+    /// Same accounts as `InitializeOrder`, followed by the recipient. Escrows
+    /// `maker_amount` for the recipient to claim without paying anything
+    #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
+    #[account(
+        1,
+        writable,
+        name = "order",
+        desc = "Order PDA account (must be uninitialized)"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's token account (source of tokens)"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(4, name = "order_id", desc = "Order id")]
+    #[account(5, name = "maker_mint", desc = "Maker mint")]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(9, name = "token_program", desc = "Token program")]
+    #[account(10, name = "recipient", desc = "Taker the escrow is claimable by")]
+    InitializeDrop { maker_amount: u64 },
+
+    /// Claims a drop's escrow, less the treasury fee
+    #[account(0, signer, name = "recipient", desc = "Taker named on the drop")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "recipient_maker_ata",
+        desc = "Recipient's maker mint ATA"
+    )]
+    #[account(4, name = "treasury", desc = "Treasury PDA account")]
+    #[account(
+        5,
+        writable,
+        name = "treasury_maker_ata",
+        desc = "Treasury maker mint ATA"
+    )]
+    #[account(6, name = "maker_mint", desc = "Maker mint")]
+    #[account(7, name = "token_program", desc = "Token program")]
+    ClaimDrop,
+
+    /// Same accounts as `InitializeOrder`. Sells up to `hard_cap` of the escrow at the
+    /// `maker_amount` to `taker_amount` price until `end_ts`, at most `wallet_cap` per
+    /// buyer. Buyers are refunded if less than `soft_cap` sells. Zero caps are unset
+    #[account(0, signer, name = "maker", desc = "Maker (order creator, pays rent)")]
+    #[account(
+        1,
+        writable,
+        name = "order",
+        desc = "Order PDA account (must be uninitialized)"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's token account (source of tokens)"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(4, name = "order_id", desc = "Order id")]
+    #[account(5, name = "maker_mint", desc = "Maker mint")]
+    #[account(6, name = "taker_mint", desc = "Taker mint")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "rent", desc = "Rent sysvar")]
+    #[account(9, name = "token_program", desc = "Token program")]
+    InitializeSale {
+        maker_amount: u64,
+        taker_amount: u64,
+        end_ts: i64,
+        wallet_cap: u64,
+        soft_cap: u64,
+        hard_cap: u64,
+    },
+
+    /// Buys `amount` maker tokens from a sale, paid into the order and recorded in the
+    /// buyer's fill record until the sale ends
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "buyer",
+        desc = "Buyer (pays fill record rent)"
+    )]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(2, writable, name = "fill_record", desc = "Buyer's fill record PDA")]
+    #[account(3, writable, name = "buyer_taker_ata", desc = "Buyer's taker mint ATA")]
+    #[account(
+        4,
+        writable,
+        name = "order_taker_ata",
+        desc = "Order PDA's taker mint ATA collecting payments"
+    )]
+    #[account(5, name = "taker_mint", desc = "Taker mint")]
+    #[account(6, name = "token_program", desc = "Token program")]
+    #[account(7, name = "system_program", desc = "System program")]
+    BuyFromSale { amount: u64 },
+
+    /// Settles a buyer's fill record after the sale: the purchased tokens if the soft cap
+    /// was reached, a refund of the payment otherwise
+    #[account(0, writable, signer, name = "buyer", desc = "Buyer account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(2, writable, name = "fill_record", desc = "Buyer's fill record PDA")]
+    #[account(
+        3,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(4, writable, name = "buyer_maker_ata", desc = "Buyer's maker mint ATA")]
+    #[account(
+        5,
+        writable,
+        name = "order_taker_ata",
+        desc = "Order PDA's taker mint ATA collecting payments"
+    )]
+    #[account(6, writable, name = "buyer_taker_ata", desc = "Buyer's taker mint ATA")]
+    #[account(7, name = "maker_mint", desc = "Maker mint")]
+    #[account(8, name = "taker_mint", desc = "Taker mint")]
+    #[account(9, name = "token_program", desc = "Token program")]
+    #[account(10, name = "token_program_2022", desc = "Token program 2022")]
+    ClaimSale,
+
+    /// Returns the unsold inventory to the maker once the sale has ended, with the
+    /// proceeds less the treasury fee if the soft cap was reached. Callable by anyone
+    #[account(0, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        1,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(2, writable, name = "maker_maker_ata", desc = "Maker's maker mint ATA")]
+    #[account(
+        3,
+        writable,
+        name = "order_taker_ata",
+        desc = "Order PDA's taker mint ATA collecting payments"
+    )]
+    #[account(4, writable, name = "maker_taker_ata", desc = "Maker's taker mint ATA")]
+    #[account(5, name = "treasury", desc = "Treasury PDA account")]
+    #[account(
+        6,
+        writable,
+        name = "treasury_taker_ata",
+        desc = "Treasury taker mint ATA"
+    )]
+    #[account(7, name = "maker_mint", desc = "Maker mint")]
+    #[account(8, name = "taker_mint", desc = "Taker mint")]
+    #[account(9, name = "token_program", desc = "Token program")]
+    #[account(10, name = "token_program_2022", desc = "Token program 2022")]
+    FinalizeSale,
+
+    /// Moves an order created by the original program to the current layout, growing
+    /// the account. Anyone can migrate an order, the payer covers the extra rent
+    #[account(0, writable, signer, name = "payer", desc = "Pays the extra rent")]
+    #[account(1, writable, name = "order", desc = "Legacy order PDA account")]
+    #[account(2, name = "system_program", desc = "System program")]
+    MigrateOrder,
+
+    /// Returns a sealed auction's lot to the maker once reveals are over and no bid met
+    /// the reserve
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's maker mint ATA"
+    )]
+    #[account(4, name = "maker_mint", desc = "Maker mint")]
+    #[account(5, name = "token_program", desc = "Token program")]
+    ReclaimSealedAuction,
+
+    /// Moves a treasury created by the original program to the current layout, growing
+    /// the account. Anyone can migrate it, the payer covers the extra rent
+    #[account(0, writable, signer, name = "payer", desc = "Pays the extra rent")]
+    #[account(1, writable, name = "treasury", desc = "Legacy treasury PDA account")]
+    #[account(2, name = "system_program", desc = "System program")]
+    MigrateTreasury,
+
+    /// `CompleteSwap` with a limit on what the taker pays, which fills of pegged orders
+    /// require since their price is only known at execution. Same accounts as
+    /// `CompleteSwap`
+    #[account(0, signer, name = "taker", desc = "Taker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "maker_receiving_account",
+        desc = "Maker's receiving token account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "taker_sending_account",
+        desc = "Taker's sending token account"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "taker_receiving_account",
+        desc = "Taker's receiving token account"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(6, name = "maker_mint", desc = "Maker mint")]
+    #[account(7, name = "taker_mint", desc = "Taker mint")]
+    #[account(8, name = "token_program", desc = "Token program")]
+    #[account(9, name = "token_authority", desc = "Token Authority PDA")]
+    #[account(
+        13,
+        optional,
+        writable,
+        name = "funding_account",
+        desc = "Recurring order funding account"
+    )]
+    #[account(
+        14,
+        optional,
+        writable,
+        name = "order_group",
+        desc = "One-cancels-other group of the order"
+    )]
+    #[account(
+        15,
+        optional,
+        writable,
+        name = "follow_up_order",
+        desc = "Order PDA a chained order re-lists into"
+    )]
+    #[account(16, optional, name = "system_program", desc = "System program")]
+    #[account(
+        17,
+        optional,
+        writable,
+        name = "vesting",
+        desc = "Vesting PDA created for the taker"
+    )]
+    #[account(
+        18,
+        optional,
+        name = "oracle",
+        desc = "Price account of a pegged order"
+    )]
+    #[account(
+        19,
+        optional,
+        name = "trigger_oracle",
+        desc = "Price account gating a conditional order"
+    )]
+    #[account(
+        20,
+        optional,
+        writable,
+        name = "order_taker_ata",
+        desc = "Order PDA's taker mint ATA holding a reservation bond"
+    )]
+    CompletePeggedSwap { max_taker_amount: u64 },
+
+    /// Returns a drop's escrow to the maker while the recipient hasn't claimed it
+    #[account(0, signer, name = "maker", desc = "Maker account")]
+    #[account(1, writable, name = "order", desc = "Order PDA account")]
+    #[account(
+        2,
+        writable,
+        name = "escrow_token_account",
+        desc = "Program's escrow token account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "maker_token_account",
+        desc = "Maker's maker mint ATA"
+    )]
+    #[account(4, name = "maker_mint", desc = "Maker mint")]
+    #[account(5, name = "token_program", desc = "Token program")]
+    ReclaimDrop,
+
+    /// Closes an unrevealed claim commitment, returning its rent to the committer. Works
+    /// whatever state the order is in, including after it was filled or closed
+    #[account(0, writable, signer, name = "taker", desc = "Committer account")]
+    #[account(1, name = "order", desc = "Order PDA the commitment was made against")]
+    #[account(2, writable, name = "claim_commitment", desc = "Claim commitment PDA")]
+    WithdrawClaimCommitment,
 }
@@ -3,6 +3,7 @@
 pub mod error;
 pub mod instruction;
 pub mod math;
+pub mod oracle;
 pub mod processor;
 pub mod state;
 pub mod utils;
@@ -13,6 +13,16 @@ pub fn calculate_token_fee(amount: u128, fee_basis_points: u16) -> Result<u128,
         .ok_or(SwapError::Overflow)
 }
 
+/// Splits a token amount into the part delivered to the counterparty and the treasury fee
+pub fn split_token_fee(amount: u64, fee_basis_points: u16) -> Result<(u64, u64), SwapError> {
+    let fee: u64 = calculate_token_fee(amount.into(), fee_basis_points)?
+        .try_into()
+        .map_err(|_| SwapError::Overflow)?;
+    let amount_after_fee = amount.checked_sub(fee).ok_or(SwapError::Overflow)?;
+
+    Ok((amount_after_fee, fee))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,4 +85,19 @@ mod tests {
         assert_eq!(calculate_token_fee(10000, 1).unwrap(), 1);
         assert_eq!(calculate_token_fee(10001, 1).unwrap(), 1);
     }
+
+    #[test]
+    fn test_split_token_fee() {
+        // 1% fee leaves 99% for the counterparty
+        assert_eq!(split_token_fee(10000, 100).unwrap(), (9900, 100));
+
+        // Rounded down fee stays with the counterparty
+        assert_eq!(split_token_fee(9999, 1).unwrap(), (9999, 0));
+
+        // 100% fee
+        assert_eq!(split_token_fee(u64::MAX, 10000).unwrap(), (0, u64::MAX));
+
+        // Fees above 100% can't be taken from the amount
+        assert!(split_token_fee(10000, 10001).is_err());
+    }
 }
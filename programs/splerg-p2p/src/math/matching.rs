@@ -0,0 +1,181 @@
+use crate::error::SwapError;
+
+/// Amounts moved when order A (selling X for Y) is matched against order B (selling Y for X)
+#[derive(Debug, PartialEq, Eq)]
+pub struct MatchFill {
+    /// X leaving A's escrow, all of it delivered to B
+    pub base: u64,
+    /// Y owed to A for `base` at A's price, rounded up
+    pub a_owed: u64,
+    /// Y B pays for `base` at B's price, rounded down
+    pub b_paid: u64,
+}
+
+impl MatchFill {
+    /// Price improvement left over after paying A its asking price
+    pub fn surplus(&self) -> u64 {
+        self.b_paid - self.a_owed
+    }
+}
+
+/// Fills as much X as both orders allow, each side at no worse than its own price
+pub fn match_amounts(
+    a_maker_amount: u64,
+    a_taker_amount: u64,
+    b_maker_amount: u64,
+    b_taker_amount: u64,
+) -> Result<MatchFill, SwapError> {
+    if a_maker_amount == 0 || b_taker_amount == 0 {
+        return Err(SwapError::InvalidAmount);
+    }
+
+    let base = a_maker_amount.min(b_taker_amount);
+
+    let a_owed = (base as u128)
+        .checked_mul(a_taker_amount.into())
+        .ok_or(SwapError::Overflow)?
+        .div_ceil(a_maker_amount.into());
+    let b_paid = (base as u128)
+        .checked_mul(b_maker_amount.into())
+        .ok_or(SwapError::Overflow)?
+        .checked_div(b_taker_amount.into())
+        .ok_or(SwapError::Overflow)?;
+
+    if b_paid < a_owed {
+        return Err(SwapError::OrdersNotCrossing);
+    }
+
+    Ok(MatchFill {
+        base,
+        a_owed: a_owed.try_into().map_err(|_| SwapError::Overflow)?,
+        b_paid: b_paid.try_into().map_err(|_| SwapError::Overflow)?,
+    })
+}
+
+/// Fills an order for at most `budget` of its taker mint, returning the maker and taker
+/// amounts filled. Partial fills round in the maker's favor.
+pub fn fill_for_budget(
+    maker_amount: u64,
+    taker_amount: u64,
+    budget: u64,
+) -> Result<(u64, u64), SwapError> {
+    if maker_amount == 0 || taker_amount == 0 {
+        return Err(SwapError::InvalidAmount);
+    }
+    if budget >= taker_amount {
+        return Ok((maker_amount, taker_amount));
+    }
+
+    let maker_filled = (budget as u128)
+        .checked_mul(maker_amount.into())
+        .ok_or(SwapError::Overflow)?
+        / taker_amount as u128;
+    let taker_filled = maker_filled
+        .checked_mul(taker_amount.into())
+        .ok_or(SwapError::Overflow)?
+        .div_ceil(maker_amount.into());
+
+    Ok((
+        maker_filled.try_into().map_err(|_| SwapError::Overflow)?,
+        taker_filled.try_into().map_err(|_| SwapError::Overflow)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_cross() {
+        // A sells 100 X for 200 Y, B sells 200 Y for 100 X
+        let fill = match_amounts(100, 200, 200, 100).unwrap();
+        assert_eq!(
+            fill,
+            MatchFill {
+                base: 100,
+                a_owed: 200,
+                b_paid: 200,
+            }
+        );
+        assert_eq!(fill.surplus(), 0);
+    }
+
+    #[test]
+    fn test_surplus() {
+        // B pays 2.5 Y per X while A only asks 2
+        let fill = match_amounts(100, 200, 250, 100).unwrap();
+        assert_eq!(fill.base, 100);
+        assert_eq!(fill.a_owed, 200);
+        assert_eq!(fill.b_paid, 250);
+        assert_eq!(fill.surplus(), 50);
+    }
+
+    #[test]
+    fn test_partial_fill() {
+        // B only wants 40 of A's 100 X
+        let fill = match_amounts(100, 200, 100, 40).unwrap();
+        assert_eq!(fill.base, 40);
+        assert_eq!(fill.a_owed, 80);
+        assert_eq!(fill.b_paid, 100);
+
+        // A only has 30 of the 100 X B wants
+        let fill = match_amounts(30, 60, 300, 100).unwrap();
+        assert_eq!(fill.base, 30);
+        assert_eq!(fill.a_owed, 60);
+        assert_eq!(fill.b_paid, 90);
+    }
+
+    #[test]
+    fn test_rounding_favors_makers() {
+        // 5 X at A's 0.3 Y per X is 1.5 Y, A gets 2
+        let fill = match_amounts(10, 3, 2, 5).unwrap();
+        assert_eq!(fill.a_owed, 2);
+        assert_eq!(fill.b_paid, 2);
+
+        // 5 X at B's 2/9 Y per X is 1.1 Y, B pays 1
+        let fill = match_amounts(5, 1, 2, 9).unwrap();
+        assert_eq!(fill.a_owed, 1);
+        assert_eq!(fill.b_paid, 1);
+    }
+
+    #[test]
+    fn test_not_crossing() {
+        // A asks 2 Y per X, B only pays 1.5
+        assert_eq!(
+            match_amounts(100, 200, 150, 100),
+            Err(SwapError::OrdersNotCrossing)
+        );
+    }
+
+    #[test]
+    fn test_empty_orders() {
+        assert_eq!(match_amounts(0, 1, 1, 1), Err(SwapError::InvalidAmount));
+        assert_eq!(match_amounts(1, 1, 1, 0), Err(SwapError::InvalidAmount));
+    }
+
+    #[test]
+    fn test_large_amounts() {
+        let fill = match_amounts(u64::MAX, u64::MAX, u64::MAX, u64::MAX).unwrap();
+        assert_eq!(fill.base, u64::MAX);
+        assert_eq!(fill.a_owed, u64::MAX);
+        assert_eq!(fill.b_paid, u64::MAX);
+    }
+
+    #[test]
+    fn test_fill_for_budget() {
+        // budget covers the whole order
+        assert_eq!(fill_for_budget(100, 200, 500).unwrap(), (100, 200));
+        assert_eq!(fill_for_budget(100, 200, 200).unwrap(), (100, 200));
+
+        // half the order
+        assert_eq!(fill_for_budget(100, 200, 100).unwrap(), (50, 100));
+
+        // 3 Y per X, 10 Y buys 3 X for 9 Y
+        assert_eq!(fill_for_budget(100, 300, 10).unwrap(), (3, 9));
+
+        // too small a budget to buy anything
+        assert_eq!(fill_for_budget(100, 300, 2).unwrap(), (0, 0));
+
+        assert_eq!(fill_for_budget(0, 1, 1), Err(SwapError::InvalidAmount));
+    }
+}
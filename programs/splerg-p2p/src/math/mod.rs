@@ -1 +1,6 @@
 pub mod fee;
+pub mod matching;
+pub mod oracle;
+pub mod sale;
+pub mod twap;
+pub mod vesting;
@@ -0,0 +1,97 @@
+use crate::error::SwapError;
+
+fn pow10(exponent: u32) -> Result<u128, SwapError> {
+    10u128.checked_pow(exponent).ok_or(SwapError::Overflow)
+}
+
+/// Taker amount for `maker_amount` at an oracle price of `price * 10^expo` whole taker
+/// tokens per whole maker token, adjusted by the maker's premium (or discount if negative)
+/// in basis points. Rounds up in the maker's favor.
+pub fn pegged_taker_amount(
+    maker_amount: u64,
+    price: i64,
+    expo: i32,
+    maker_decimals: u8,
+    taker_decimals: u8,
+    premium_bps: i16,
+) -> Result<u64, SwapError> {
+    if price <= 0 {
+        return Err(SwapError::InvalidOracle);
+    }
+    let adjustment = 10_000i32 + premium_bps as i32;
+    if adjustment <= 0 {
+        return Err(SwapError::InvalidAmount);
+    }
+
+    // taker = maker * price * 10^(expo + taker_decimals - maker_decimals) * adjustment / 10^4
+    let scale = expo + taker_decimals as i32 - maker_decimals as i32;
+    let mut numerator = (maker_amount as u128)
+        .checked_mul(price as u128)
+        .ok_or(SwapError::Overflow)?
+        .checked_mul(adjustment as u128)
+        .ok_or(SwapError::Overflow)?;
+    let mut denominator = 10_000u128;
+    if scale >= 0 {
+        numerator = numerator
+            .checked_mul(pow10(scale as u32)?)
+            .ok_or(SwapError::Overflow)?;
+    } else {
+        denominator = denominator
+            .checked_mul(pow10(scale.unsigned_abs())?)
+            .ok_or(SwapError::Overflow)?;
+    }
+
+    numerator
+        .div_ceil(denominator)
+        .try_into()
+        .map_err(|_| SwapError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalizes_decimals() {
+        // 2 X (9 decimals) at 1.5 Y per X is 3 Y (6 decimals)
+        assert_eq!(
+            pegged_taker_amount(2_000_000_000, 150_000_000, -8, 9, 6, 0).unwrap(),
+            3_000_000
+        );
+        // and the other way around
+        assert_eq!(
+            pegged_taker_amount(2_000_000, 150_000_000, -8, 6, 9, 0).unwrap(),
+            3_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_applies_premium_and_discount() {
+        assert_eq!(
+            pegged_taker_amount(1_000, 100, -2, 0, 0, 250).unwrap(),
+            1_025
+        );
+        assert_eq!(
+            pegged_taker_amount(1_000, 100, -2, 0, 0, -250).unwrap(),
+            975
+        );
+        assert_eq!(
+            pegged_taker_amount(1_000, 100, -2, 0, 0, -10_000).unwrap_err(),
+            SwapError::InvalidAmount
+        );
+    }
+
+    #[test]
+    fn test_rounds_up_for_maker() {
+        assert_eq!(pegged_taker_amount(1, 1, -1, 0, 0, 0).unwrap(), 1);
+        assert_eq!(pegged_taker_amount(3, 1, 0, 0, 0, 1).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_rejects_non_positive_price() {
+        assert_eq!(
+            pegged_taker_amount(1, 0, 0, 0, 0, 0).unwrap_err(),
+            SwapError::InvalidOracle
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use crate::error::SwapError;
+
+/// Taker tokens owed for `amount` maker tokens of a sale pricing `maker_amount` at
+/// `taker_amount`, rounded up so buying in small pieces is never cheaper
+pub fn sale_cost(amount: u64, maker_amount: u64, taker_amount: u64) -> Result<u64, SwapError> {
+    if maker_amount == 0 {
+        return Err(SwapError::InvalidAmount);
+    }
+
+    let cost = (amount as u128)
+        .checked_mul(taker_amount as u128)
+        .ok_or(SwapError::Overflow)?
+        .div_ceil(maker_amount as u128);
+
+    cost.try_into().map_err(|_| SwapError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cost_at_sale_price() {
+        assert_eq!(sale_cost(1_000, 100_000, 200_000).unwrap(), 2_000);
+        assert_eq!(sale_cost(100_000, 100_000, 200_000).unwrap(), 200_000);
+    }
+
+    #[test]
+    fn test_cost_rounds_up() {
+        assert_eq!(sale_cost(1, 3, 1).unwrap(), 1);
+        assert_eq!(sale_cost(4, 3, 1).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_cost_overflow() {
+        assert_eq!(sale_cost(u64::MAX, 1, 2), Err(SwapError::Overflow));
+        assert_eq!(sale_cost(1, 0, 2), Err(SwapError::InvalidAmount));
+    }
+}
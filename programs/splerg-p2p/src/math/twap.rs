@@ -0,0 +1,47 @@
+use crate::error::SwapError;
+
+/// Maker tokens released by a TWAP schedule at `now`. The first slice is released at
+/// `start_ts` and another one every `interval` seconds after.
+pub fn unlocked_amount(
+    start_ts: i64,
+    interval: i64,
+    slice: u64,
+    now: i64,
+) -> Result<u64, SwapError> {
+    if interval <= 0 {
+        return Err(SwapError::InvalidAmount);
+    }
+    if now < start_ts {
+        return Ok(0);
+    }
+
+    let intervals = (now - start_ts) / interval + 1;
+    Ok(slice.saturating_mul(intervals as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_slice_is_released_at_start() {
+        assert_eq!(unlocked_amount(100, 3_600, 50, 100).unwrap(), 50);
+        assert_eq!(unlocked_amount(100, 3_600, 50, 3_699).unwrap(), 50);
+        assert_eq!(unlocked_amount(100, 3_600, 50, 99).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_slices_accumulate_per_interval() {
+        assert_eq!(unlocked_amount(0, 3_600, 50, 3_600).unwrap(), 100);
+        assert_eq!(unlocked_amount(0, 3_600, 50, 23 * 3_600).unwrap(), 1_200);
+        assert_eq!(unlocked_amount(0, 1, u64::MAX, 10).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_rejects_empty_interval() {
+        assert_eq!(
+            unlocked_amount(0, 0, 50, 10).unwrap_err(),
+            SwapError::InvalidAmount
+        );
+    }
+}
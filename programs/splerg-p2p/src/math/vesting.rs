@@ -0,0 +1,51 @@
+use crate::error::SwapError;
+
+/// Tokens vested at `now` on a linear schedule from `start_ts` to `end_ts`.
+/// Nothing is released before `cliff_ts`, which then releases everything accrued so far.
+pub fn vested_amount(
+    total: u64,
+    start_ts: i64,
+    cliff_ts: i64,
+    end_ts: i64,
+    now: i64,
+) -> Result<u64, SwapError> {
+    if now < cliff_ts || now < start_ts {
+        return Ok(0);
+    }
+    if now >= end_ts {
+        return Ok(total);
+    }
+
+    let elapsed = (now - start_ts) as u128;
+    let duration = (end_ts - start_ts) as u128;
+    let vested = (total as u128)
+        .checked_mul(elapsed)
+        .ok_or(SwapError::Overflow)?
+        / duration;
+
+    vested.try_into().map_err(|_| SwapError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nothing_vests_before_cliff() {
+        assert_eq!(vested_amount(1_000, 0, 250, 1_000, 0).unwrap(), 0);
+        assert_eq!(vested_amount(1_000, 0, 250, 1_000, 249).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_cliff_releases_accrued_amount() {
+        assert_eq!(vested_amount(1_000, 0, 250, 1_000, 250).unwrap(), 250);
+        assert_eq!(vested_amount(1_000, 0, 250, 1_000, 500).unwrap(), 500);
+    }
+
+    #[test]
+    fn test_everything_vests_at_end() {
+        assert_eq!(vested_amount(1_000, 0, 250, 1_000, 1_000).unwrap(), 1_000);
+        assert_eq!(vested_amount(1_000, 0, 0, 1_000, i64::MAX).unwrap(), 1_000);
+        assert_eq!(vested_amount(u64::MAX, 0, 0, 3, 1).unwrap(), u64::MAX / 3);
+    }
+}
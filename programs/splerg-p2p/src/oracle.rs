@@ -0,0 +1,147 @@
+//! Reader for price accounts laid out like Pyth's v2 price account. Only the header
+//! and the aggregate price are read, so a stand-in account just needs `PRICE_ACCOUNT_LEN`
+//! bytes with those fields filled in, owned by `PYTH_PROGRAM_ID`.
+
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey, pubkey::Pubkey,
+};
+
+use crate::error::SwapError;
+
+/// Pyth oracle program, the only owner whose price accounts orders are pegged or
+/// triggered on
+pub const PYTH_PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+pub const MAGIC: u32 = 0xa1b2_c3d4;
+pub const VERSION: u32 = 2;
+/// `atype` of a price account
+pub const ACCOUNT_TYPE_PRICE: u32 = 3;
+/// Aggregate `status` while publishers are trading
+pub const STATUS_TRADING: u32 = 1;
+
+pub const MAGIC_OFFSET: usize = 0;
+pub const VERSION_OFFSET: usize = 4;
+pub const ACCOUNT_TYPE_OFFSET: usize = 8;
+pub const EXPONENT_OFFSET: usize = 20;
+pub const TIMESTAMP_OFFSET: usize = 96;
+pub const AGGREGATE_PRICE_OFFSET: usize = 208;
+pub const AGGREGATE_CONF_OFFSET: usize = 216;
+pub const AGGREGATE_STATUS_OFFSET: usize = 224;
+/// Bytes up to the end of the aggregate price info
+pub const PRICE_ACCOUNT_LEN: usize = 240;
+
+/// Aggregate price read from a price account, `price * 10^expo` taker tokens per maker token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    data[offset..offset + N].try_into().unwrap()
+}
+
+/// Parses the aggregate price out of raw price account data
+pub fn parse_price(data: &[u8]) -> Result<OraclePrice, SwapError> {
+    if data.len() < PRICE_ACCOUNT_LEN
+        || u32::from_le_bytes(read_bytes(data, MAGIC_OFFSET)) != MAGIC
+        || u32::from_le_bytes(read_bytes(data, VERSION_OFFSET)) != VERSION
+        || u32::from_le_bytes(read_bytes(data, ACCOUNT_TYPE_OFFSET)) != ACCOUNT_TYPE_PRICE
+    {
+        return Err(SwapError::InvalidOracle);
+    }
+    if u32::from_le_bytes(read_bytes(data, AGGREGATE_STATUS_OFFSET)) != STATUS_TRADING {
+        return Err(SwapError::InvalidOracle);
+    }
+
+    Ok(OraclePrice {
+        price: i64::from_le_bytes(read_bytes(data, AGGREGATE_PRICE_OFFSET)),
+        conf: u64::from_le_bytes(read_bytes(data, AGGREGATE_CONF_OFFSET)),
+        expo: i32::from_le_bytes(read_bytes(data, EXPONENT_OFFSET)),
+        publish_time: i64::from_le_bytes(read_bytes(data, TIMESTAMP_OFFSET)),
+    })
+}
+
+/// Reads the aggregate price of a price account owned by the Pyth program
+pub fn read_price(oracle_info: &AccountInfo) -> Result<OraclePrice, ProgramError> {
+    if *oracle_info.owner != PYTH_PROGRAM_ID {
+        return Err(SwapError::InvalidOracle.into());
+    }
+    Ok(parse_price(&oracle_info.data.borrow())?)
+}
+
+/// Loads a price that is positive, at most `max_age` seconds old at `now` and whose
+/// confidence interval is within `max_conf_bps` of the price
+pub fn load_price(
+    oracle_info: &AccountInfo,
+    now: i64,
+    max_age: i64,
+    max_conf_bps: u16,
+) -> Result<OraclePrice, ProgramError> {
+    let price = read_price(oracle_info)?;
+
+    if price.price <= 0 {
+        return Err(SwapError::InvalidOracle.into());
+    }
+    if now.saturating_sub(price.publish_time) > max_age {
+        return Err(SwapError::StaleOracle.into());
+    }
+    if (price.conf as u128) * 10_000 > (price.price as u128) * max_conf_bps as u128 {
+        return Err(SwapError::StaleOracle.into());
+    }
+
+    Ok(price)
+}
+
+/// Lays out a price account, for stand-in oracles
+pub fn pack_price(price: &OraclePrice) -> Vec<u8> {
+    let mut data = vec![0; PRICE_ACCOUNT_LEN];
+    data[MAGIC_OFFSET..MAGIC_OFFSET + 4].copy_from_slice(&MAGIC.to_le_bytes());
+    data[VERSION_OFFSET..VERSION_OFFSET + 4].copy_from_slice(&VERSION.to_le_bytes());
+    data[ACCOUNT_TYPE_OFFSET..ACCOUNT_TYPE_OFFSET + 4]
+        .copy_from_slice(&ACCOUNT_TYPE_PRICE.to_le_bytes());
+    data[EXPONENT_OFFSET..EXPONENT_OFFSET + 4].copy_from_slice(&price.expo.to_le_bytes());
+    data[TIMESTAMP_OFFSET..TIMESTAMP_OFFSET + 8].copy_from_slice(&price.publish_time.to_le_bytes());
+    data[AGGREGATE_PRICE_OFFSET..AGGREGATE_PRICE_OFFSET + 8]
+        .copy_from_slice(&price.price.to_le_bytes());
+    data[AGGREGATE_CONF_OFFSET..AGGREGATE_CONF_OFFSET + 8]
+        .copy_from_slice(&price.conf.to_le_bytes());
+    data[AGGREGATE_STATUS_OFFSET..AGGREGATE_STATUS_OFFSET + 4]
+        .copy_from_slice(&STATUS_TRADING.to_le_bytes());
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRICE: OraclePrice = OraclePrice {
+        price: 150_000_000,
+        conf: 50_000,
+        expo: -8,
+        publish_time: 1_000,
+    };
+
+    #[test]
+    fn test_parse_packed_price() {
+        assert_eq!(parse_price(&pack_price(&PRICE)).unwrap(), PRICE);
+    }
+
+    #[test]
+    fn test_rejects_other_accounts() {
+        let mut data = pack_price(&PRICE);
+        data[MAGIC_OFFSET] ^= 1;
+        assert_eq!(parse_price(&data).unwrap_err(), SwapError::InvalidOracle);
+
+        let mut data = pack_price(&PRICE);
+        data[AGGREGATE_STATUS_OFFSET] = 0;
+        assert_eq!(parse_price(&data).unwrap_err(), SwapError::InvalidOracle);
+
+        assert_eq!(
+            parse_price(&pack_price(&PRICE)[..PRICE_ACCOUNT_LEN - 1]).unwrap_err(),
+            SwapError::InvalidOracle
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{Arbitration, OrderKind, OrderStatus},
+    utils::{close_program_account, get_token_balance, transfer_tokens, transfer_tokens_with_fee},
+    validation::{
+        get_arbitration_pda, validate_arbitration_pda, validate_mint, validate_order_kind,
+        validate_order_open, validate_order_pda, validate_signer, validate_token_account,
+        validate_token_program, validate_treasury_pda,
+    },
+};
+
+use super::Processor;
+
+/// Number of accounts shared with `InitializeOrder`
+const INITIALIZE_ORDER_ACCOUNTS: usize = 10;
+
+impl Processor {
+    pub(crate) fn process_initialize_arbitrated_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+        deadline_ts: i64,
+        milestones: Vec<u64>,
+    ) -> ProgramResult {
+        if accounts.len() < INITIALIZE_ORDER_ACCOUNTS + 3 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (order_accounts, arbitration_accounts) = accounts.split_at(INITIALIZE_ORDER_ACCOUNTS);
+        let maker_info = &order_accounts[0];
+        let system_program_info = &order_accounts[7];
+        let [counterparty_info, arbiter_info, arbitration_info, ..] = arbitration_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // without milestones the whole escrow is a single tranche
+        let milestones = if milestones.is_empty() {
+            vec![maker_amount]
+        } else {
+            milestones
+        };
+        if milestones.len() > Arbitration::MAX_MILESTONES
+            || milestones.contains(&0)
+            || milestones
+                .iter()
+                .try_fold(0u64, |total, amount| total.checked_add(*amount))
+                != Some(maker_amount)
+        {
+            return Err(SwapError::InvalidAmount.into());
+        }
+        if deadline_ts <= Clock::get()?.unix_timestamp {
+            return Err(SwapError::OfferExpired.into());
+        }
+        if *counterparty_info.key == Pubkey::default() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // the counterparty pays off-chain, the milestones above already rule out a zero
+        // `maker_amount`
+        let (mut order, order_account_info) =
+            Self::create_order_account(program_id, order_accounts, maker_amount, 0)?;
+        order.kind = OrderKind::Arbitrated;
+        order.taker = *counterparty_info.key;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        let (arbitration_pda, bump) = get_arbitration_pda(program_id, order_account_info.key)?;
+        if arbitration_pda != *arbitration_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let arbitration = Arbitration {
+            order: *order_account_info.key,
+            arbiter: *arbiter_info.key,
+            deadline_ts,
+            released: 0,
+            bump,
+            milestones,
+        };
+        let space = Arbitration::len(arbitration.milestones.len());
+
+        invoke_signed(
+            &system_instruction::create_account(
+                maker_info.key,
+                arbitration_info.key,
+                Rent::get()?.minimum_balance(space),
+                space as u64,
+                program_id,
+            ),
+            &[
+                maker_info.clone(),
+                arbitration_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&arbitration.signer_seeds()],
+        )?;
+
+        arbitration.serialize(&mut *arbitration_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Releases the next milestone tranche to the counterparty
+    pub(crate) fn process_approve_milestone(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let arbiter_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let arbitration_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let taker_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let maker_info = next_account_info(account_info_iter)?;
+
+        validate_signer(arbiter_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Arbitrated)?;
+        validate_order_open(&order)?;
+        let mut arbitration =
+            validate_arbitration_pda(program_id, arbitration_info, order_account_info.key)?;
+        if arbitration.arbiter != *arbiter_info.key {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+        if *maker_info.key != order.maker {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_token_program(maker_mint, token_program.key)?;
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+        validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+        validate_token_account(taker_maker_ata, &order.taker, maker_mint.key)?;
+        validate_token_account(
+            treasury_maker_ata,
+            treasury_account_info.key,
+            maker_mint.key,
+        )?;
+
+        let tranche = *arbitration
+            .milestones
+            .get(arbitration.released as usize)
+            .ok_or(SwapError::InvalidOrderState)?;
+
+        transfer_tokens_with_fee(
+            token_program,
+            order_maker_ata,
+            maker_mint,
+            taker_maker_ata,
+            treasury_maker_ata,
+            order_account_info,
+            tranche,
+            treasury.fee,
+            &[&order.signer_seeds()],
+        )?;
+
+        arbitration.released += 1;
+        order.maker_amount -= tranche;
+        if (arbitration.released as usize) < arbitration.milestones.len() {
+            arbitration.serialize(&mut *arbitration_info.data.borrow_mut())?;
+            order.serialize(&mut *order_account_info.data.borrow_mut())?;
+            return Ok(());
+        }
+
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        // Return the arbitration rent to the maker once the last tranche is out
+        close_program_account(arbitration_info, maker_info)
+    }
+
+    /// Refunds the unreleased escrow to the maker, on the arbiter's ruling or by
+    /// anyone once the arbiter has let the deadline pass
+    pub(crate) fn process_refund_arbitrated_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let arbitration_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let maker_info = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Arbitrated)?;
+        validate_order_open(&order)?;
+        let arbitration =
+            validate_arbitration_pda(program_id, arbitration_info, order_account_info.key)?;
+        if *maker_info.key != order.maker {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let arbiter_ruling = authority_info.is_signer && *authority_info.key == arbitration.arbiter;
+        if !arbiter_ruling && Clock::get()?.unix_timestamp < arbitration.deadline_ts {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_token_program(maker_mint, token_program.key)?;
+        validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+        validate_token_account(maker_ata, &order.maker, maker_mint.key)?;
+
+        transfer_tokens(
+            token_program,
+            order_maker_ata,
+            maker_mint,
+            maker_ata,
+            order_account_info,
+            get_token_balance(order_maker_ata)?,
+            &[&order.signer_seeds()],
+        )?;
+
+        // the order stays around as a record, the maker can close it now
+        order.maker_amount = 0;
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        // Return the arbitration rent to the maker
+        close_program_account(arbitration_info, maker_info)
+    }
+}
@@ -0,0 +1,256 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{Bid, OrderKind, OrderStatus},
+    utils::{
+        close_program_account, get_token_balance, get_token_program, transfer_tokens,
+        transfer_tokens_with_fee,
+    },
+    validation::{
+        get_bid_pda, validate_bid_pda, validate_mint, validate_order_kind, validate_order_open,
+        validate_order_pda, validate_rent_sysvar, validate_signer, validate_system_program,
+        validate_token_account, validate_token_program, validate_treasury_pda,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_initialize_auction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+        reserve_price: u64,
+        end_ts: i64,
+    ) -> ProgramResult {
+        if end_ts <= Clock::get()?.unix_timestamp {
+            return Err(SwapError::AuctionEnded.into());
+        }
+
+        let (mut order, order_account_info) =
+            Self::create_order(program_id, accounts, maker_amount, reserve_price)?;
+
+        order.kind = OrderKind::Auction;
+        order.end_ts = end_ts;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_place_bid(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let bidder_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let bid_account_info = next_account_info(account_info_iter)?;
+        let bid_escrow = next_account_info(account_info_iter)?;
+        let bidder_taker_ata = next_account_info(account_info_iter)?;
+        let previous_bidder_taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(bidder_info)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Auction)?;
+        validate_order_open(&order)?;
+
+        if Clock::get()?.unix_timestamp >= order.end_ts {
+            return Err(SwapError::AuctionEnded.into());
+        }
+
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        validate_token_program(taker_mint, token_program.key)?;
+        validate_system_program(system_program_info.key)?;
+        validate_rent_sysvar(rent_info.key)?;
+        validate_token_account(bidder_taker_ata, bidder_info.key, &order.taker_token_mint)?;
+
+        let (bid_pda, bump) = get_bid_pda(program_id, order_account_info.key)?;
+        if bid_pda != *bid_account_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        validate_token_account(bid_escrow, &bid_pda, &order.taker_token_mint)?;
+
+        let mut bid = if bid_account_info.data_is_empty() {
+            if amount < order.taker_amount {
+                return Err(SwapError::BidTooLow.into());
+            }
+
+            let rent = Rent::from_account_info(rent_info)?;
+            invoke_signed(
+                &system_instruction::create_account(
+                    bidder_info.key,
+                    bid_account_info.key,
+                    rent.minimum_balance(Bid::LEN),
+                    Bid::LEN as u64,
+                    program_id,
+                ),
+                &[
+                    bidder_info.clone(),
+                    bid_account_info.clone(),
+                    system_program_info.clone(),
+                ],
+                &[&[b"bid", order_account_info.key.as_ref(), &[bump]]],
+            )?;
+
+            Bid::new(*order_account_info.key, *bidder_info.key, amount, bump)
+        } else {
+            let bid = validate_bid_pda(program_id, bid_account_info, order_account_info.key)?;
+            if amount <= bid.amount {
+                return Err(SwapError::BidTooLow.into());
+            }
+
+            // refund the bid being replaced
+            validate_token_account(
+                previous_bidder_taker_ata,
+                &bid.bidder,
+                &order.taker_token_mint,
+            )?;
+            transfer_tokens(
+                token_program,
+                bid_escrow,
+                taker_mint,
+                previous_bidder_taker_ata,
+                bid_account_info,
+                bid.amount,
+                &[&bid.signer_seeds()],
+            )?;
+
+            bid
+        };
+
+        transfer_tokens(
+            token_program,
+            bidder_taker_ata,
+            taker_mint,
+            bid_escrow,
+            bidder_info,
+            amount,
+            &[],
+        )?;
+
+        bid.bidder = *bidder_info.key;
+        bid.amount = amount;
+        bid.serialize(&mut *bid_account_info.data.borrow_mut())?;
+
+        order.taker = *bidder_info.key;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_settle_auction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let order_account_info = next_account_info(account_info_iter)?;
+        let bid_account_info = next_account_info(account_info_iter)?;
+        let bid_escrow = next_account_info(account_info_iter)?;
+        let bidder_info = next_account_info(account_info_iter)?;
+        let maker_taker_ata = next_account_info(account_info_iter)?;
+        let bidder_maker_ata = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_taker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let token_program_2022 = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Auction)?;
+        validate_order_open(&order)?;
+
+        if Clock::get()?.unix_timestamp < order.end_ts {
+            return Err(SwapError::AuctionNotEnded.into());
+        }
+
+        let bid = validate_bid_pda(program_id, bid_account_info, order_account_info.key)?;
+        if bid.bidder != *bidder_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+
+        validate_token_account(maker_taker_ata, &order.maker, &order.taker_token_mint)?;
+        validate_token_account(bidder_maker_ata, &bid.bidder, &order.maker_token_mint)?;
+        validate_token_account(
+            order_maker_ata,
+            order_account_info.key,
+            &order.maker_token_mint,
+        )?;
+        validate_token_account(bid_escrow, bid_account_info.key, &order.taker_token_mint)?;
+        validate_token_account(
+            treasury_maker_ata,
+            treasury_account_info.key,
+            &order.maker_token_mint,
+        )?;
+        validate_token_account(
+            treasury_taker_ata,
+            treasury_account_info.key,
+            &order.taker_token_mint,
+        )?;
+
+        let maker_token_program = get_token_program(maker_mint, token_program, token_program_2022)?;
+        let taker_token_program = get_token_program(taker_mint, token_program, token_program_2022)?;
+
+        if get_token_balance(order_maker_ata)? < order.maker_amount {
+            return Err(SwapError::InsufficientFunds.into());
+        }
+
+        // winning bid -> maker, maker escrow -> winning bidder
+        transfer_tokens_with_fee(
+            taker_token_program,
+            bid_escrow,
+            taker_mint,
+            maker_taker_ata,
+            treasury_taker_ata,
+            bid_account_info,
+            bid.amount,
+            treasury.fee,
+            &[&bid.signer_seeds()],
+        )?;
+        transfer_tokens_with_fee(
+            maker_token_program,
+            order_maker_ata,
+            maker_mint,
+            bidder_maker_ata,
+            treasury_maker_ata,
+            order_account_info,
+            order.maker_amount,
+            treasury.fee,
+            &[&order.signer_seeds()],
+        )?;
+
+        order.taker = bid.bidder;
+        order.taker_amount = bid.amount;
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        // Return the bid account rent to the winner
+        close_program_account(bid_account_info, bidder_info)
+    }
+}
@@ -0,0 +1,241 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    instruction::OrderAmounts,
+    state::SwapOrder,
+    utils::{close_program_account, get_token_balance, transfer_tokens},
+    validation::{
+        get_order_pda, validate_authority, validate_init_amounts, validate_mint,
+        validate_order_closable, validate_order_pda, validate_order_repriceable,
+        validate_order_unregistered, validate_signer, validate_system_program,
+        validate_token_account, validate_token_mint, validate_token_program,
+    },
+};
+
+use super::Processor;
+
+/// Accounts passed per order to the batch instructions
+const BATCH_ORDER_ACCOUNTS: usize = 2;
+
+/// Splits the remaining accounts into one (order, escrow) pair per level
+fn batch_order_accounts<'a, 'b>(
+    accounts: &'b [AccountInfo<'a>],
+    levels: usize,
+) -> Result<std::slice::ChunksExact<'b, AccountInfo<'a>>, ProgramError> {
+    let order_groups = accounts.chunks_exact(BATCH_ORDER_ACCOUNTS);
+    if !order_groups.remainder().is_empty() || order_groups.len() != levels || levels == 0 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    Ok(order_groups)
+}
+
+impl Processor {
+    pub(crate) fn process_batch_initialize_orders(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        base_id: [u8; 32],
+        levels: &[OrderAmounts],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let order_groups = batch_order_accounts(account_info_iter.as_slice(), levels.len())?;
+
+        validate_signer(maker_info)?;
+        validate_token_mint(maker_mint)?;
+        validate_token_mint(taker_mint)?;
+        validate_token_program(maker_mint, token_program.key)?;
+        validate_token_account(maker_ata, maker_info.key, maker_mint.key)?;
+        validate_system_program(system_program_info.key)?;
+
+        if levels.len() > u8::MAX as usize {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // the sysvar cache saves an account per transaction, which keeps a ladder in one tx
+        let rent = Rent::get()?;
+        let base_id = Pubkey::new_from_array(base_id);
+
+        for (index, (level, group)) in levels.iter().zip(order_groups).enumerate() {
+            let [order_account_info, order_maker_ata] = group else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            validate_init_amounts(level.maker_amount, level.taker_amount)?;
+
+            let id = SwapOrder::batch_id(&base_id, index as u8);
+            let (order_pda, bump) = get_order_pda(
+                program_id,
+                &id,
+                maker_info.key,
+                maker_mint.key,
+                taker_mint.key,
+            )?;
+            if order_pda != *order_account_info.key {
+                return Err(ProgramError::InvalidArgument);
+            }
+            validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+
+            let order = SwapOrder::new(
+                id,
+                *maker_info.key,
+                *maker_mint.key,
+                *taker_mint.key,
+                level.maker_amount,
+                level.taker_amount,
+                bump,
+            );
+
+            invoke_signed(
+                &system_instruction::create_account(
+                    maker_info.key,
+                    order_account_info.key,
+                    rent.minimum_balance(SwapOrder::LEN),
+                    SwapOrder::LEN as u64,
+                    program_id,
+                ),
+                &[
+                    maker_info.clone(),
+                    order_account_info.clone(),
+                    system_program_info.clone(),
+                ],
+                &[&order.signer_seeds()],
+            )?;
+
+            transfer_tokens(
+                token_program,
+                maker_ata,
+                maker_mint,
+                order_maker_ata,
+                maker_info,
+                level.maker_amount,
+                &[],
+            )?;
+
+            order.serialize(&mut *order_account_info.data.borrow_mut())?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn process_batch_change_order_amounts(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        levels: &[OrderAmounts],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let order_groups = batch_order_accounts(account_info_iter.as_slice(), levels.len())?;
+
+        validate_token_program(maker_mint, token_program.key)?;
+        validate_token_account(maker_ata, maker_info.key, maker_mint.key)?;
+
+        for (level, group) in levels.iter().zip(order_groups) {
+            let [order_account_info, order_maker_ata] = group else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+            validate_authority(maker_info, &order)?;
+            validate_order_repriceable(&order)?;
+            // registered orders are repriced and cancelled with their registry
+            validate_order_unregistered(&order)?;
+            validate_init_amounts(level.maker_amount, level.taker_amount)?;
+            validate_mint(maker_mint, &order.maker_token_mint)?;
+            validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+
+            let current_order_amount = get_token_balance(order_maker_ata)?;
+            if level.maker_amount > current_order_amount {
+                transfer_tokens(
+                    token_program,
+                    maker_ata,
+                    maker_mint,
+                    order_maker_ata,
+                    maker_info,
+                    level.maker_amount - current_order_amount,
+                    &[],
+                )?;
+            } else {
+                transfer_tokens(
+                    token_program,
+                    order_maker_ata,
+                    maker_mint,
+                    maker_ata,
+                    order_account_info,
+                    current_order_amount - level.maker_amount,
+                    &[&order.signer_seeds()],
+                )?;
+            }
+
+            order.maker_amount = level.maker_amount;
+            order.taker_amount = level.taker_amount;
+            order.serialize(&mut *order_account_info.data.borrow_mut())?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn process_batch_cancel_orders(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let order_accounts = account_info_iter.as_slice();
+        let order_groups =
+            batch_order_accounts(order_accounts, order_accounts.len() / BATCH_ORDER_ACCOUNTS)?;
+
+        validate_token_program(maker_mint, token_program.key)?;
+        validate_token_account(maker_ata, maker_info.key, maker_mint.key)?;
+
+        for group in order_groups {
+            let [order_account_info, order_maker_ata] = group else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            let (order, _) = validate_order_pda(program_id, order_account_info)?;
+            validate_authority(maker_info, &order)?;
+            validate_order_closable(&order)?;
+            // registered orders are repriced and cancelled with their registry
+            validate_order_unregistered(&order)?;
+            validate_mint(maker_mint, &order.maker_token_mint)?;
+            validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+
+            transfer_tokens(
+                token_program,
+                order_maker_ata,
+                maker_mint,
+                maker_ata,
+                order_account_info,
+                get_token_balance(order_maker_ata)?,
+                &[&order.signer_seeds()],
+            )?;
+
+            close_program_account(order_account_info, maker_info)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        program::{invoke, invoke_signed},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{OrderKind, SwapOrder},
+    validation::{
+        get_order_pda, validate_authority, validate_order_kind, validate_order_open,
+        validate_order_pda, validate_system_program, validate_token_mint,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_set_follow_up_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        taker_amount: u64,
+        end_ts: i64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let follow_up_taker_mint = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_kind(&order, OrderKind::Fixed)?;
+        validate_order_open(&order)?;
+        validate_token_mint(follow_up_taker_mint)?;
+        validate_system_program(system_program_info.key)?;
+
+        // a refill would need the proceeds the re-listed order already took
+        if order.funding_account != Pubkey::default() {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+        if taker_amount == 0 {
+            return Err(SwapError::InvalidAmount.into());
+        }
+        if end_ts != 0 && end_ts <= Clock::get()?.unix_timestamp {
+            return Err(SwapError::OfferExpired.into());
+        }
+
+        // the maker prepays the follow-up order's rent, it sits on this order until the fill
+        if order.follow_up_taker_mint == Pubkey::default() {
+            invoke(
+                &system_instruction::transfer(
+                    maker_info.key,
+                    order_account_info.key,
+                    Rent::get()?.minimum_balance(SwapOrder::LEN),
+                ),
+                &[
+                    maker_info.clone(),
+                    order_account_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+
+        order.follow_up_taker_mint = *follow_up_taker_mint.key;
+        order.follow_up_taker_amount = taker_amount;
+        order.follow_up_end_ts = end_ts;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Address of the order a chained order re-lists its proceeds into
+    pub(crate) fn get_follow_up_order_pda(
+        program_id: &Pubkey,
+        order_key: &Pubkey,
+        order: &SwapOrder,
+    ) -> Result<(Pubkey, u8), ProgramError> {
+        get_order_pda(
+            program_id,
+            &SwapOrder::follow_up_id(order_key),
+            &order.maker,
+            &order.taker_token_mint,
+            &order.follow_up_taker_mint,
+        )
+    }
+
+    /// Opens the follow-up order over `maker_amount` proceeds already sitting in its escrow,
+    /// paying its rent out of the lamports prepaid on the filled order
+    pub(crate) fn create_follow_up_order<'a>(
+        program_id: &Pubkey,
+        order_account_info: &AccountInfo<'a>,
+        order: &SwapOrder,
+        follow_up_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        maker_amount: u64,
+    ) -> ProgramResult {
+        validate_system_program(system_program_info.key)?;
+
+        let (follow_up_pda, bump) =
+            Self::get_follow_up_order_pda(program_id, order_account_info.key, order)?;
+        if follow_up_pda != *follow_up_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let follow_up = SwapOrder {
+            end_ts: order.follow_up_end_ts,
+            ..SwapOrder::new(
+                SwapOrder::follow_up_id(order_account_info.key),
+                order.maker,
+                order.taker_token_mint,
+                order.follow_up_taker_mint,
+                maker_amount,
+                order.follow_up_taker_amount,
+                bump,
+            )
+        };
+
+        let signer_seeds = follow_up.signer_seeds();
+        invoke_signed(
+            &system_instruction::allocate(follow_up_info.key, SwapOrder::LEN as u64),
+            &[follow_up_info.clone(), system_program_info.clone()],
+            &[&signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(follow_up_info.key, program_id),
+            &[follow_up_info.clone(), system_program_info.clone()],
+            &[&signer_seeds],
+        )?;
+
+        let rent_lamports = Rent::get()?.minimum_balance(SwapOrder::LEN);
+        **order_account_info.lamports.borrow_mut() = order_account_info
+            .lamports()
+            .checked_sub(rent_lamports)
+            .ok_or(SwapError::InsufficientFunds)?;
+        **follow_up_info.lamports.borrow_mut() = follow_up_info
+            .lamports()
+            .checked_add(rent_lamports)
+            .ok_or(SwapError::Overflow)?;
+
+        follow_up.serialize(&mut *follow_up_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
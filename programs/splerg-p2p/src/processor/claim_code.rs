@@ -0,0 +1,178 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        hash::hashv,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{ClaimCommitment, OrderKind},
+    utils::close_program_account,
+    validation::{
+        get_claim_commitment_pda, validate_authority, validate_claim_commitment_pda,
+        validate_order_kind, validate_order_open, validate_order_pda, validate_order_unreserved,
+        validate_signer, validate_system_program,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_set_claim_code(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        claim_code_hash: [u8; 32],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_kind(&order, OrderKind::Fixed)?;
+        validate_order_open(&order)?;
+        validate_order_unreserved(&order)?;
+        if order.taker != Pubkey::default() {
+            return Err(SwapError::TakerAlreadyAssigned.into());
+        }
+
+        order.claim_code_hash = claim_code_hash;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_commit_claim_code(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        commitment: [u8; 32],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let taker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let claim_commitment_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        validate_signer(taker_info)?;
+        validate_system_program(system_program_info.key)?;
+        let (order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_open(&order)?;
+        if order.claim_code_hash == [0; 32] || order.taker != Pubkey::default() {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        let (claim_commitment_pda, bump) =
+            get_claim_commitment_pda(program_id, order_account_info.key, taker_info.key)?;
+        if claim_commitment_pda != *claim_commitment_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        invoke_signed(
+            &system_instruction::create_account(
+                taker_info.key,
+                claim_commitment_info.key,
+                Rent::get()?.minimum_balance(ClaimCommitment::LEN),
+                ClaimCommitment::LEN as u64,
+                program_id,
+            ),
+            &[
+                taker_info.clone(),
+                claim_commitment_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[
+                b"claim_commitment",
+                order_account_info.key.as_ref(),
+                taker_info.key.as_ref(),
+                &[bump],
+            ]],
+        )?;
+
+        let claim_commitment = ClaimCommitment {
+            order: *order_account_info.key,
+            taker: *taker_info.key,
+            commitment,
+            slot: Clock::get()?.slot,
+            bump,
+        };
+        claim_commitment.serialize(&mut *claim_commitment_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_reveal_claim_code(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        secret: [u8; 32],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let taker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let claim_commitment_info = next_account_info(account_info_iter)?;
+
+        validate_signer(taker_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_open(&order)?;
+        if order.claim_code_hash == [0; 32] || order.taker != Pubkey::default() {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+        let claim_commitment = validate_claim_commitment_pda(
+            program_id,
+            claim_commitment_info,
+            order_account_info.key,
+            taker_info.key,
+        )?;
+
+        // committing and revealing in one slot would let an observer of the reveal
+        // replay it under their own commitment
+        if Clock::get()?.slot <= claim_commitment.slot {
+            return Err(SwapError::RevealNotOpen.into());
+        }
+        if hashv(&[&secret]).to_bytes() != order.claim_code_hash
+            || ClaimCommitment::commitment(&secret, taker_info.key) != claim_commitment.commitment
+        {
+            return Err(SwapError::InvalidCommitment.into());
+        }
+
+        order.taker = *taker_info.key;
+        order.claim_code_hash = [0; 32];
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        close_program_account(claim_commitment_info, taker_info)?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_withdraw_claim_commitment(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let taker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let claim_commitment_info = next_account_info(account_info_iter)?;
+
+        validate_signer(taker_info)?;
+        // the order may be gone already, the commitment PDA is bound to its key alone
+        validate_claim_commitment_pda(
+            program_id,
+            claim_commitment_info,
+            order_account_info.key,
+            taker_info.key,
+        )?;
+
+        close_program_account(claim_commitment_info, taker_info)?;
+
+        Ok(())
+    }
+}
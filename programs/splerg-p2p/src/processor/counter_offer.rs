@@ -0,0 +1,299 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{CounterOffer, OrderKind, OrderStatus},
+    utils::{
+        close_program_account, get_token_balance, get_token_program, transfer_tokens,
+        transfer_tokens_with_fee,
+    },
+    validation::{
+        get_counter_offer_pda, validate_authority, validate_counter_offer_pda,
+        validate_init_amounts, validate_mint, validate_order_kind, validate_order_open,
+        validate_order_pda, validate_order_ungrouped, validate_order_unregistered,
+        validate_order_unreserved, validate_rent_sysvar, validate_signer, validate_system_program,
+        validate_token_account, validate_token_program, validate_treasury_pda,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_propose_counter_offer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+        taker_amount: u64,
+        expires_ts: i64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let taker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let counter_offer_info = next_account_info(account_info_iter)?;
+        let offer_escrow = next_account_info(account_info_iter)?;
+        let taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(taker_info)?;
+
+        let (order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Fixed)?;
+        validate_order_open(&order)?;
+
+        // private orders only take counter offers from their taker
+        if order.taker != Pubkey::default() && order.taker != *taker_info.key {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+
+        validate_init_amounts(maker_amount, taker_amount)?;
+        if maker_amount > order.maker_amount {
+            return Err(SwapError::InvalidAmount.into());
+        }
+        if expires_ts <= Clock::get()?.unix_timestamp {
+            return Err(SwapError::OfferExpired.into());
+        }
+
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        validate_token_program(taker_mint, token_program.key)?;
+        validate_system_program(system_program_info.key)?;
+        validate_rent_sysvar(rent_info.key)?;
+        validate_token_account(taker_ata, taker_info.key, &order.taker_token_mint)?;
+
+        let (counter_offer_pda, bump) =
+            get_counter_offer_pda(program_id, order_account_info.key, taker_info.key)?;
+        if counter_offer_pda != *counter_offer_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        validate_token_account(offer_escrow, &counter_offer_pda, &order.taker_token_mint)?;
+
+        let rent = Rent::from_account_info(rent_info)?;
+        invoke_signed(
+            &system_instruction::create_account(
+                taker_info.key,
+                counter_offer_info.key,
+                rent.minimum_balance(CounterOffer::LEN),
+                CounterOffer::LEN as u64,
+                program_id,
+            ),
+            &[
+                taker_info.clone(),
+                counter_offer_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[
+                b"counter_offer",
+                order_account_info.key.as_ref(),
+                taker_info.key.as_ref(),
+                &[bump],
+            ]],
+        )?;
+
+        transfer_tokens(
+            token_program,
+            taker_ata,
+            taker_mint,
+            offer_escrow,
+            taker_info,
+            taker_amount,
+            &[],
+        )?;
+
+        let counter_offer = CounterOffer {
+            order: *order_account_info.key,
+            taker: *taker_info.key,
+            maker_amount,
+            taker_amount,
+            expires_ts,
+            bump,
+        };
+        counter_offer.serialize(&mut *counter_offer_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_accept_counter_offer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let counter_offer_info = next_account_info(account_info_iter)?;
+        let offer_escrow = next_account_info(account_info_iter)?;
+        let taker_info = next_account_info(account_info_iter)?;
+        let maker_taker_ata = next_account_info(account_info_iter)?;
+        let maker_maker_ata = next_account_info(account_info_iter)?;
+        let taker_maker_ata = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_taker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let token_program_2022 = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_kind(&order, OrderKind::Fixed)?;
+        validate_order_open(&order)?;
+        validate_order_ungrouped(&order)?;
+        // a reservation holder paid a bond for the exclusive right to fill
+        validate_order_unreserved(&order)?;
+        validate_order_unregistered(&order)?;
+
+        let counter_offer =
+            validate_counter_offer_pda(program_id, counter_offer_info, order_account_info.key)?;
+        if counter_offer.taker != *taker_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if Clock::get()?.unix_timestamp >= counter_offer.expires_ts {
+            return Err(SwapError::OfferExpired.into());
+        }
+        // the order may have been repriced below the offer since it was made
+        if counter_offer.maker_amount > order.maker_amount {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+
+        validate_token_account(maker_taker_ata, &order.maker, &order.taker_token_mint)?;
+        validate_token_account(maker_maker_ata, &order.maker, &order.maker_token_mint)?;
+        validate_token_account(
+            taker_maker_ata,
+            &counter_offer.taker,
+            &order.maker_token_mint,
+        )?;
+        validate_token_account(
+            order_maker_ata,
+            order_account_info.key,
+            &order.maker_token_mint,
+        )?;
+        validate_token_account(
+            offer_escrow,
+            counter_offer_info.key,
+            &order.taker_token_mint,
+        )?;
+        validate_token_account(
+            treasury_maker_ata,
+            treasury_account_info.key,
+            &order.maker_token_mint,
+        )?;
+        validate_token_account(
+            treasury_taker_ata,
+            treasury_account_info.key,
+            &order.taker_token_mint,
+        )?;
+
+        let maker_token_program = get_token_program(maker_mint, token_program, token_program_2022)?;
+        let taker_token_program = get_token_program(taker_mint, token_program, token_program_2022)?;
+
+        let escrow_balance = get_token_balance(order_maker_ata)?;
+        if escrow_balance < order.maker_amount {
+            return Err(SwapError::InsufficientFunds.into());
+        }
+
+        // escrowed payment -> maker, maker escrow -> taker at the offered terms
+        transfer_tokens_with_fee(
+            taker_token_program,
+            offer_escrow,
+            taker_mint,
+            maker_taker_ata,
+            treasury_taker_ata,
+            counter_offer_info,
+            counter_offer.taker_amount,
+            treasury.fee,
+            &[&counter_offer.signer_seeds()],
+        )?;
+
+        let order_seeds = order.signer_seeds();
+        transfer_tokens_with_fee(
+            maker_token_program,
+            order_maker_ata,
+            maker_mint,
+            taker_maker_ata,
+            treasury_maker_ata,
+            order_account_info,
+            counter_offer.maker_amount,
+            treasury.fee,
+            &[&order_seeds],
+        )?;
+
+        // anything the offer didn't ask for goes back to the maker
+        transfer_tokens(
+            maker_token_program,
+            order_maker_ata,
+            maker_mint,
+            maker_maker_ata,
+            order_account_info,
+            escrow_balance - counter_offer.maker_amount,
+            &[&order_seeds],
+        )?;
+
+        order.taker = counter_offer.taker;
+        order.maker_amount = counter_offer.maker_amount;
+        order.taker_amount = counter_offer.taker_amount;
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        // Return the counter offer rent to the taker
+        close_program_account(counter_offer_info, taker_info)
+    }
+
+    pub(crate) fn process_withdraw_counter_offer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let taker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let counter_offer_info = next_account_info(account_info_iter)?;
+        let offer_escrow = next_account_info(account_info_iter)?;
+        let taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(taker_info)?;
+
+        let counter_offer =
+            validate_counter_offer_pda(program_id, counter_offer_info, order_account_info.key)?;
+        if counter_offer.taker != *taker_info.key {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+
+        validate_token_program(taker_mint, token_program.key)?;
+        validate_token_account(offer_escrow, counter_offer_info.key, taker_mint.key)?;
+        validate_token_account(taker_ata, taker_info.key, taker_mint.key)?;
+
+        transfer_tokens(
+            token_program,
+            offer_escrow,
+            taker_mint,
+            taker_ata,
+            counter_offer_info,
+            counter_offer.taker_amount,
+            &[&counter_offer.signer_seeds()],
+        )?;
+
+        // Return the counter offer rent to the taker
+        close_program_account(counter_offer_info, taker_info)
+    }
+}
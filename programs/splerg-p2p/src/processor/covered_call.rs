@@ -0,0 +1,184 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{OrderKind, OrderStatus},
+    utils::{get_token_balance, transfer_tokens, transfer_tokens_with_fee},
+    validation::{
+        validate_authority, validate_mint, validate_order_kind, validate_order_open,
+        validate_order_pda, validate_signer, validate_token_account, validate_token_program,
+        validate_treasury_pda,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_initialize_covered_call(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+        strike_amount: u64,
+        premium: u64,
+        expiry_ts: i64,
+    ) -> ProgramResult {
+        if expiry_ts <= Clock::get()?.unix_timestamp {
+            return Err(SwapError::OfferExpired.into());
+        }
+        if premium == 0 {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        let (mut order, order_account_info) =
+            Self::create_order(program_id, accounts, maker_amount, strike_amount)?;
+        order.kind = OrderKind::CoveredCall;
+        order.end_ts = expiry_ts;
+        order.option_premium = premium;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_buy_option(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+        strike_amount: u64,
+        premium: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let buyer_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let buyer_taker_ata = next_account_info(account_info_iter)?;
+        let maker_taker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(buyer_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::CoveredCall)?;
+        validate_order_open(&order)?;
+        if order.taker != Pubkey::default() {
+            return Err(SwapError::TakerAlreadyAssigned.into());
+        }
+        if Clock::get()?.unix_timestamp >= order.end_ts {
+            return Err(SwapError::OfferExpired.into());
+        }
+        // the maker may have repriced since the buyer saw the call
+        if order.maker_amount != maker_amount
+            || order.taker_amount != strike_amount
+            || order.option_premium != premium
+        {
+            return Err(SwapError::LimitPriceExceeded.into());
+        }
+
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        validate_token_program(taker_mint, token_program.key)?;
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+        validate_token_account(buyer_taker_ata, buyer_info.key, taker_mint.key)?;
+        validate_token_account(maker_taker_ata, &order.maker, taker_mint.key)?;
+        validate_token_account(
+            treasury_taker_ata,
+            treasury_account_info.key,
+            taker_mint.key,
+        )?;
+
+        transfer_tokens_with_fee(
+            token_program,
+            buyer_taker_ata,
+            taker_mint,
+            maker_taker_ata,
+            treasury_taker_ata,
+            buyer_info,
+            order.option_premium,
+            treasury.fee,
+            &[],
+        )?;
+
+        order.taker = *buyer_info.key;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_transfer_option(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let holder_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let new_holder_info = next_account_info(account_info_iter)?;
+
+        validate_signer(holder_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::CoveredCall)?;
+        validate_order_open(&order)?;
+        if order.taker == Pubkey::default() || order.taker != *holder_info.key {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+        if *new_holder_info.key == Pubkey::default() {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        order.taker = *new_holder_info.key;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Returns the escrow of an unsold or expired covered call to the maker
+    pub(crate) fn process_reclaim_covered_call(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_kind(&order, OrderKind::CoveredCall)?;
+        validate_order_open(&order)?;
+        if order.taker != Pubkey::default() && Clock::get()?.unix_timestamp < order.end_ts {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_token_program(maker_mint, token_program.key)?;
+        validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+        validate_token_account(maker_ata, &order.maker, maker_mint.key)?;
+
+        transfer_tokens(
+            token_program,
+            order_maker_ata,
+            maker_mint,
+            maker_ata,
+            order_account_info,
+            get_token_balance(order_maker_ata)?,
+            &[&order.signer_seeds()],
+        )?;
+
+        // the order stays around as a record, the maker can close it now
+        order.maker_amount = 0;
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
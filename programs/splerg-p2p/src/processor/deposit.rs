@@ -0,0 +1,257 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{OrderKind, OrderStatus, SwapOrder},
+    utils::{get_token_balance, get_token_program, transfer_tokens, transfer_tokens_with_fee},
+    validation::{
+        validate_authority, validate_mint, validate_no_taker_deposit, validate_order_kind,
+        validate_order_open, validate_order_pda, validate_order_started, validate_signer,
+        validate_token_account, validate_token_program, validate_treasury_pda,
+    },
+};
+
+use super::Processor;
+
+/// Settlement pays the maker and taker directly, so the order can't also rely on
+/// anything `CompleteSwap` does on fill
+fn validate_plain_settlement(order: &SwapOrder) -> ProgramResult {
+    validate_order_kind(order, OrderKind::Fixed)?;
+    if order.funding_account != Pubkey::default()
+        || order.group != Pubkey::default()
+        || order.follow_up_taker_mint != Pubkey::default()
+        || order.vesting_duration != 0
+        || order.oracle != Pubkey::default()
+        || order.trigger_oracle != Pubkey::default()
+        || order.registry != Pubkey::default()
+    {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+impl Processor {
+    pub(crate) fn process_open_deposit_window(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        deposit_end_ts: i64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_open(&order)?;
+        validate_plain_settlement(&order)?;
+        validate_no_taker_deposit(&order)?;
+
+        // deposits are only taken from the counterparty named with `ChangeTaker`
+        if order.taker == Pubkey::default() {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+        if deposit_end_ts <= Clock::get()?.unix_timestamp {
+            return Err(SwapError::OfferExpired.into());
+        }
+
+        order.deposit_end_ts = deposit_end_ts;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_deposit_taker_tokens(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        expected_maker_amount: u64,
+        expected_taker_amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let taker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_taker_ata = next_account_info(account_info_iter)?;
+        let taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(taker_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_open(&order)?;
+        validate_no_taker_deposit(&order)?;
+        if order.taker != *taker_info.key {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+        if order.deposit_end_ts == 0 {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+        if Clock::get()?.unix_timestamp >= order.deposit_end_ts {
+            return Err(SwapError::OfferExpired.into());
+        }
+        // the maker may have repriced since the taker saw the order
+        if order.maker_amount != expected_maker_amount
+            || order.taker_amount != expected_taker_amount
+        {
+            return Err(SwapError::LimitPriceExceeded.into());
+        }
+
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        validate_token_program(taker_mint, token_program.key)?;
+        validate_token_account(order_taker_ata, order_account_info.key, taker_mint.key)?;
+        validate_token_account(taker_ata, taker_info.key, taker_mint.key)?;
+
+        transfer_tokens(
+            token_program,
+            taker_ata,
+            taker_mint,
+            order_taker_ata,
+            taker_info,
+            order.taker_amount,
+            &[],
+        )?;
+
+        order.taker_deposited = true;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Swaps both deposits once they're in place, callable by either party or a keeper
+    pub(crate) fn process_settle_deposits(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let settler_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let order_taker_ata = next_account_info(account_info_iter)?;
+        let maker_taker_ata = next_account_info(account_info_iter)?;
+        let taker_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_taker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let token_program_2022 = next_account_info(account_info_iter)?;
+
+        validate_signer(settler_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_open(&order)?;
+        validate_plain_settlement(&order)?;
+        validate_order_started(&order)?;
+        if !order.taker_deposited {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+
+        validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+        validate_token_account(order_taker_ata, order_account_info.key, taker_mint.key)?;
+        validate_token_account(maker_taker_ata, &order.maker, taker_mint.key)?;
+        validate_token_account(taker_maker_ata, &order.taker, maker_mint.key)?;
+        validate_token_account(
+            treasury_maker_ata,
+            treasury_account_info.key,
+            maker_mint.key,
+        )?;
+        validate_token_account(
+            treasury_taker_ata,
+            treasury_account_info.key,
+            taker_mint.key,
+        )?;
+
+        if get_token_balance(order_maker_ata)? < order.maker_amount {
+            return Err(SwapError::InsufficientFunds.into());
+        }
+
+        let order_seeds = order.signer_seeds();
+        transfer_tokens_with_fee(
+            get_token_program(taker_mint, token_program, token_program_2022)?,
+            order_taker_ata,
+            taker_mint,
+            maker_taker_ata,
+            treasury_taker_ata,
+            order_account_info,
+            order.taker_amount,
+            treasury.fee,
+            &[&order_seeds],
+        )?;
+        transfer_tokens_with_fee(
+            get_token_program(maker_mint, token_program, token_program_2022)?,
+            order_maker_ata,
+            maker_mint,
+            taker_maker_ata,
+            treasury_maker_ata,
+            order_account_info,
+            order.maker_amount,
+            treasury.fee,
+            &[&order_seeds],
+        )?;
+
+        order.taker_deposited = false;
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Returns an unsettled taker deposit once the deposit window has closed
+    pub(crate) fn process_withdraw_taker_deposit(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let taker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_taker_ata = next_account_info(account_info_iter)?;
+        let taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(taker_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        if !order.taker_deposited {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+        if order.taker != *taker_info.key {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+        // a maker can still fill the order another way, which frees the deposit early
+        if order.status == OrderStatus::Open && Clock::get()?.unix_timestamp < order.deposit_end_ts
+        {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        validate_token_program(taker_mint, token_program.key)?;
+        validate_token_account(order_taker_ata, order_account_info.key, taker_mint.key)?;
+        validate_token_account(taker_ata, taker_info.key, taker_mint.key)?;
+
+        transfer_tokens(
+            token_program,
+            order_taker_ata,
+            taker_mint,
+            taker_ata,
+            order_account_info,
+            order.taker_amount,
+            &[&order.signer_seeds()],
+        )?;
+
+        order.taker_deposited = false;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{OrderGroup, OrderKind, SwapOrder},
+    utils::{close_program_account, get_token_balance, transfer_tokens},
+    validation::{
+        get_order_group_pda, validate_authority, validate_mint, validate_order_closable,
+        validate_order_group_pda, validate_order_kind, validate_order_open, validate_order_pda,
+        validate_signer, validate_system_program, validate_token_account, validate_token_program,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_initialize_order_group(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        id: [u8; 32],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let group_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let member_infos = account_info_iter.as_slice();
+
+        validate_signer(maker_info)?;
+        validate_system_program(system_program_info.key)?;
+
+        if member_infos.len() < 2 || member_infos.len() > u8::MAX as usize {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        let id = Pubkey::new_from_array(id);
+        let (group_pda, bump) = get_order_group_pda(program_id, maker_info.key, &id)?;
+        if group_pda != *group_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        for (i, order_account_info) in member_infos.iter().enumerate() {
+            if member_infos[..i]
+                .iter()
+                .any(|other| other.key == order_account_info.key)
+            {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+            validate_authority(maker_info, &order)?;
+            validate_order_kind(&order, OrderKind::Fixed)?;
+            validate_order_open(&order)?;
+            // a refill would reopen a member after the group was settled
+            if order.group != Pubkey::default() || order.funding_account != Pubkey::default() {
+                return Err(SwapError::InvalidOrderState.into());
+            }
+
+            order.group = group_pda;
+            order.serialize(&mut *order_account_info.data.borrow_mut())?;
+        }
+
+        let group = OrderGroup::new(*maker_info.key, id, member_infos.len() as u8, bump);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                maker_info.key,
+                group_info.key,
+                Rent::get()?.minimum_balance(OrderGroup::LEN),
+                OrderGroup::LEN as u64,
+                program_id,
+            ),
+            &[
+                maker_info.clone(),
+                group_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[
+                b"order_group",
+                maker_info.key.as_ref(),
+                id.as_ref(),
+                &[bump],
+            ]],
+        )?;
+
+        group.serialize(&mut *group_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Claims the group for `order`, so none of the other members can be filled
+    pub(crate) fn fill_group_member(
+        program_id: &Pubkey,
+        group_info: &AccountInfo,
+        order_account_info: &AccountInfo,
+        order: &SwapOrder,
+    ) -> ProgramResult {
+        let mut group = validate_order_group_pda(program_id, group_info, order)?;
+        if group.filled_order != Pubkey::default() {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        group.filled_order = *order_account_info.key;
+        group.serialize(&mut *group_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_refund_group_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let group_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let maker_info = next_account_info(account_info_iter)?;
+        let maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        let (order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_open(&order)?;
+        validate_order_closable(&order)?;
+        let group = validate_order_group_pda(program_id, group_info, &order)?;
+        if group.filled_order == Pubkey::default() || group.filled_order == *order_account_info.key
+        {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        if *maker_info.key != order.maker {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_token_program(maker_mint, token_program.key)?;
+        validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+        validate_token_account(maker_ata, maker_info.key, maker_mint.key)?;
+
+        transfer_tokens(
+            token_program,
+            order_maker_ata,
+            maker_mint,
+            maker_ata,
+            order_account_info,
+            get_token_balance(order_maker_ata)?,
+            &[&order.signer_seeds()],
+        )?;
+
+        close_program_account(order_account_info, maker_info)?;
+
+        Ok(())
+    }
+}
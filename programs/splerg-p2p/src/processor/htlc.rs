@@ -0,0 +1,168 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        hash::hashv,
+        log::sol_log_data,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{OrderKind, OrderStatus},
+    utils::{get_token_balance, transfer_tokens, transfer_tokens_with_fee},
+    validation::{
+        validate_authority, validate_mint, validate_order_kind, validate_order_open,
+        validate_order_pda, validate_signer, validate_token_account, validate_token_program,
+        validate_treasury_pda,
+    },
+};
+
+use super::Processor;
+
+/// Number of accounts shared with `InitializeOrder`
+const INITIALIZE_ORDER_ACCOUNTS: usize = 10;
+
+impl Processor {
+    pub(crate) fn process_initialize_htlc_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+        taker_amount: u64,
+        hashlock: [u8; 32],
+        timelock_ts: i64,
+    ) -> ProgramResult {
+        if accounts.len() < INITIALIZE_ORDER_ACCOUNTS + 1 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (order_accounts, htlc_accounts) = accounts.split_at(INITIALIZE_ORDER_ACCOUNTS);
+        let counterparty_info = &htlc_accounts[0];
+
+        if timelock_ts <= Clock::get()?.unix_timestamp {
+            return Err(SwapError::OfferExpired.into());
+        }
+        // a revealed preimage could be replayed by anyone, so the escrow can only go
+        // to a named counterparty
+        if *counterparty_info.key == Pubkey::default() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let (mut order, order_account_info) =
+            Self::create_order(program_id, order_accounts, maker_amount, taker_amount)?;
+        order.kind = OrderKind::Htlc;
+        order.taker = *counterparty_info.key;
+        order.hashlock = hashlock;
+        order.end_ts = timelock_ts;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Releases the escrow to the counterparty against the hashlock's preimage
+    pub(crate) fn process_claim_htlc(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        preimage: [u8; 32],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let claimer_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let taker_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(claimer_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Htlc)?;
+        validate_order_open(&order)?;
+        if Clock::get()?.unix_timestamp >= order.end_ts {
+            return Err(SwapError::OfferExpired.into());
+        }
+        if hashv(&[&preimage]).to_bytes() != order.hashlock {
+            return Err(SwapError::InvalidCommitment.into());
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_token_program(maker_mint, token_program.key)?;
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+        validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+        validate_token_account(taker_maker_ata, &order.taker, maker_mint.key)?;
+        validate_token_account(
+            treasury_maker_ata,
+            treasury_account_info.key,
+            maker_mint.key,
+        )?;
+
+        transfer_tokens_with_fee(
+            token_program,
+            order_maker_ata,
+            maker_mint,
+            taker_maker_ata,
+            treasury_maker_ata,
+            order_account_info,
+            order.maker_amount,
+            treasury.fee,
+            &[&order.signer_seeds()],
+        )?;
+
+        // the other chain's leg is claimed with the same preimage
+        sol_log_data(&[order_account_info.key.as_ref(), &preimage]);
+
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Returns an unclaimed escrow to the maker after the timelock
+    pub(crate) fn process_reclaim_htlc(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_kind(&order, OrderKind::Htlc)?;
+        validate_order_open(&order)?;
+        if Clock::get()?.unix_timestamp < order.end_ts {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_token_program(maker_mint, token_program.key)?;
+        validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+        validate_token_account(maker_ata, &order.maker, maker_mint.key)?;
+
+        transfer_tokens(
+            token_program,
+            order_maker_ata,
+            maker_mint,
+            maker_ata,
+            order_account_info,
+            get_token_balance(order_maker_ata)?,
+            &[&order.signer_seeds()],
+        )?;
+
+        // the order stays around as a record, the maker can close it now
+        order.maker_amount = 0;
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
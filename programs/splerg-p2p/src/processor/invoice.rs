@@ -0,0 +1,213 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{OrderKind, OrderStatus},
+    utils::{get_token_balance, transfer_tokens, transfer_tokens_with_fee},
+    validation::{
+        validate_authority, validate_drop_amounts, validate_invoice_amounts, validate_mint,
+        validate_order_kind, validate_order_open, validate_order_pda, validate_signer,
+        validate_token_account, validate_token_program, validate_treasury_pda,
+    },
+};
+
+use super::Processor;
+
+/// Number of accounts shared with `InitializeOrder`
+const INITIALIZE_ORDER_ACCOUNTS: usize = 10;
+
+impl Processor {
+    pub(crate) fn process_initialize_invoice(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        taker_amount: u64,
+    ) -> ProgramResult {
+        validate_invoice_amounts(0, taker_amount)?;
+        Self::create_one_sided_order(program_id, accounts, OrderKind::Invoice, 0, taker_amount)
+    }
+
+    pub(crate) fn process_initialize_drop(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+    ) -> ProgramResult {
+        validate_drop_amounts(maker_amount, 0)?;
+        Self::create_one_sided_order(program_id, accounts, OrderKind::Drop, maker_amount, 0)
+    }
+
+    /// One-sided orders are only ever settled with the taker named at creation
+    fn create_one_sided_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        kind: OrderKind,
+        maker_amount: u64,
+        taker_amount: u64,
+    ) -> ProgramResult {
+        if accounts.len() < INITIALIZE_ORDER_ACCOUNTS + 1 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (order_accounts, taker_accounts) = accounts.split_at(INITIALIZE_ORDER_ACCOUNTS);
+        let taker_info = &taker_accounts[0];
+        if *taker_info.key == Pubkey::default() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let (mut order, order_account_info) =
+            Self::create_order_account(program_id, order_accounts, maker_amount, taker_amount)?;
+        order.kind = kind;
+        order.taker = *taker_info.key;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_pay_invoice(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let payer_taker_ata = next_account_info(account_info_iter)?;
+        let maker_taker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(payer_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Invoice)?;
+        validate_order_open(&order)?;
+        if order.taker != *payer_info.key {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        validate_token_program(taker_mint, token_program.key)?;
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+        validate_token_account(payer_taker_ata, payer_info.key, taker_mint.key)?;
+        validate_token_account(maker_taker_ata, &order.maker, taker_mint.key)?;
+        validate_token_account(
+            treasury_taker_ata,
+            treasury_account_info.key,
+            taker_mint.key,
+        )?;
+
+        transfer_tokens_with_fee(
+            token_program,
+            payer_taker_ata,
+            taker_mint,
+            maker_taker_ata,
+            treasury_taker_ata,
+            payer_info,
+            order.taker_amount,
+            treasury.fee,
+            &[],
+        )?;
+
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_claim_drop(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let recipient_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let recipient_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(recipient_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Drop)?;
+        validate_order_open(&order)?;
+        if order.taker != *recipient_info.key {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_token_program(maker_mint, token_program.key)?;
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+        validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+        validate_token_account(recipient_maker_ata, recipient_info.key, maker_mint.key)?;
+        validate_token_account(
+            treasury_maker_ata,
+            treasury_account_info.key,
+            maker_mint.key,
+        )?;
+
+        transfer_tokens_with_fee(
+            token_program,
+            order_maker_ata,
+            maker_mint,
+            recipient_maker_ata,
+            treasury_maker_ata,
+            order_account_info,
+            order.maker_amount,
+            treasury.fee,
+            &[&order.signer_seeds()],
+        )?;
+
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_reclaim_drop(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_kind(&order, OrderKind::Drop)?;
+        validate_order_open(&order)?;
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_token_program(maker_mint, token_program.key)?;
+        validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+        validate_token_account(maker_ata, &order.maker, maker_mint.key)?;
+
+        transfer_tokens(
+            token_program,
+            order_maker_ata,
+            maker_mint,
+            maker_ata,
+            order_account_info,
+            get_token_balance(order_maker_ata)?,
+            &[&order.signer_seeds()],
+        )?;
+
+        // the order stays around as a record, the maker can close it now
+        order.maker_amount = 0;
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,313 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    math::{fee::calculate_token_fee, matching::match_amounts},
+    state::{OrderKind, OrderStatus, SwapOrder},
+    utils::{get_token_balance, get_token_program, transfer_tokens, transfer_tokens_with_fee},
+    validation::{
+        validate_mint, validate_order_kind, validate_order_nonrecurring, validate_order_open,
+        validate_order_pda, validate_order_started, validate_order_unchained,
+        validate_order_unexpired, validate_order_ungrouped, validate_order_unpegged,
+        validate_order_unregistered, validate_order_unreserved, validate_order_untokenized,
+        validate_order_untriggered, validate_order_unvested, validate_signer,
+        validate_token_account, validate_treasury_pda,
+    },
+};
+
+use super::Processor;
+
+/// Accounts passed per order to `SettleRing`
+const RING_LEG_ACCOUNTS: usize = 6;
+
+/// Only open, public, fixed price orders can be matched by a third party
+fn validate_matchable(order: &SwapOrder) -> ProgramResult {
+    validate_order_kind(order, OrderKind::Fixed)?;
+    validate_order_open(order)?;
+    validate_order_ungrouped(order)?;
+    validate_order_nonrecurring(order)?;
+    validate_order_unchained(order)?;
+    validate_order_unvested(order)?;
+    validate_order_started(order)?;
+    validate_order_unexpired(order)?;
+    validate_order_unpegged(order)?;
+    validate_order_untriggered(order)?;
+    validate_order_unreserved(order)?;
+    validate_order_untokenized(order)?;
+    if order.taker != Pubkey::default() || order.claim_code_hash != [0; 32] {
+        return Err(SwapError::TakerAlreadyAssigned.into());
+    }
+
+    Ok(())
+}
+
+/// Reduces the order by a fill, leaving it open for the rest or marking it filled
+pub(super) fn apply_fill(
+    order: &mut SwapOrder,
+    maker_filled: u64,
+    taker_filled: u64,
+) -> ProgramResult {
+    order.maker_amount = order
+        .maker_amount
+        .checked_sub(maker_filled)
+        .ok_or(SwapError::Overflow)?;
+    order.taker_amount = order
+        .taker_amount
+        .checked_sub(taker_filled)
+        .ok_or(SwapError::Overflow)?;
+
+    match (order.maker_amount, order.taker_amount) {
+        (0, 0) => order.status = OrderStatus::Filled,
+        // rounding must not leave one side of the order for free
+        (0, _) | (_, 0) => return Err(SwapError::InvalidAmount.into()),
+        _ => {}
+    }
+
+    Ok(())
+}
+
+impl Processor {
+    pub(crate) fn process_match_orders(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let cranker_info = next_account_info(account_info_iter)?;
+        let order_a_info = next_account_info(account_info_iter)?;
+        let order_b_info = next_account_info(account_info_iter)?;
+        let order_a_escrow = next_account_info(account_info_iter)?;
+        let order_b_escrow = next_account_info(account_info_iter)?;
+        let maker_a_ata = next_account_info(account_info_iter)?;
+        let maker_b_ata = next_account_info(account_info_iter)?;
+        let cranker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_x_ata = next_account_info(account_info_iter)?;
+        let treasury_y_ata = next_account_info(account_info_iter)?;
+        let mint_x = next_account_info(account_info_iter)?;
+        let mint_y = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let token_program_2022 = next_account_info(account_info_iter)?;
+
+        validate_signer(cranker_info)?;
+
+        if order_a_info.key == order_b_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let (mut order_a, _) = validate_order_pda(program_id, order_a_info)?;
+        let (mut order_b, _) = validate_order_pda(program_id, order_b_info)?;
+        validate_matchable(&order_a)?;
+        validate_matchable(&order_b)?;
+        // registered orders have their maker registry trailing the accounts, A's first
+        let registry_a_info = Self::next_registry_account(&order_a, account_info_iter)?;
+        let registry_b_info = Self::next_registry_account(&order_b, account_info_iter)?;
+        let open_a_before = order_a.open_maker_amount();
+        let open_b_before = order_b.open_maker_amount();
+
+        if order_a.maker_token_mint != order_b.taker_token_mint
+            || order_a.taker_token_mint != order_b.maker_token_mint
+        {
+            return Err(SwapError::InvalidMint.into());
+        }
+        validate_mint(mint_x, &order_a.maker_token_mint)?;
+        validate_mint(mint_y, &order_a.taker_token_mint)?;
+
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+
+        validate_token_account(order_a_escrow, order_a_info.key, mint_x.key)?;
+        validate_token_account(order_b_escrow, order_b_info.key, mint_y.key)?;
+        validate_token_account(maker_a_ata, &order_a.maker, mint_y.key)?;
+        validate_token_account(maker_b_ata, &order_b.maker, mint_x.key)?;
+        validate_token_account(cranker_ata, cranker_info.key, mint_y.key)?;
+        validate_token_account(treasury_x_ata, treasury_account_info.key, mint_x.key)?;
+        validate_token_account(treasury_y_ata, treasury_account_info.key, mint_y.key)?;
+
+        let x_token_program = get_token_program(mint_x, token_program, token_program_2022)?;
+        let y_token_program = get_token_program(mint_y, token_program, token_program_2022)?;
+
+        if get_token_balance(order_a_escrow)? < order_a.maker_amount
+            || get_token_balance(order_b_escrow)? < order_b.maker_amount
+        {
+            return Err(SwapError::InsufficientFunds.into());
+        }
+
+        let fill = match_amounts(
+            order_a.maker_amount,
+            order_a.taker_amount,
+            order_b.maker_amount,
+            order_b.taker_amount,
+        )?;
+
+        // the cranker takes its cut of the price improvement, A keeps the rest
+        let cranker_tip: u64 =
+            calculate_token_fee(fill.surplus().into(), treasury.cranker_share_bps)?
+                .try_into()
+                .map_err(|_| SwapError::Overflow)?;
+        let a_received = fill.b_paid - cranker_tip;
+
+        // A's escrow -> B's maker
+        transfer_tokens_with_fee(
+            x_token_program,
+            order_a_escrow,
+            mint_x,
+            maker_b_ata,
+            treasury_x_ata,
+            order_a_info,
+            fill.base,
+            treasury.fee,
+            &[&order_a.signer_seeds()],
+        )?;
+
+        // B's escrow -> A's maker and the cranker
+        let order_b_seeds = order_b.signer_seeds();
+        transfer_tokens_with_fee(
+            y_token_program,
+            order_b_escrow,
+            mint_y,
+            maker_a_ata,
+            treasury_y_ata,
+            order_b_info,
+            a_received,
+            treasury.fee,
+            &[&order_b_seeds],
+        )?;
+        transfer_tokens(
+            y_token_program,
+            order_b_escrow,
+            mint_y,
+            cranker_ata,
+            order_b_info,
+            cranker_tip,
+            &[&order_b_seeds],
+        )?;
+
+        apply_fill(&mut order_a, fill.base, fill.a_owed)?;
+        apply_fill(&mut order_b, fill.b_paid, fill.base)?;
+
+        order_a.serialize(&mut *order_a_info.data.borrow_mut())?;
+        order_b.serialize(&mut *order_b_info.data.borrow_mut())?;
+        Self::update_registry(
+            program_id,
+            registry_a_info,
+            &order_a.maker,
+            open_a_before,
+            order_a.open_maker_amount(),
+        )?;
+        Self::update_registry(
+            program_id,
+            registry_b_info,
+            &order_b.maker,
+            open_b_before,
+            order_b.open_maker_amount(),
+        )?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_settle_ring(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let submitter_info = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let token_program_2022 = next_account_info(account_info_iter)?;
+        let leg_accounts = account_info_iter.as_slice();
+
+        validate_signer(submitter_info)?;
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+
+        let chunks = leg_accounts.chunks_exact(RING_LEG_ACCOUNTS);
+        if !chunks.remainder().is_empty() || chunks.len() < 2 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let legs: Vec<&[AccountInfo]> = chunks.collect();
+
+        let mut orders = Vec::with_capacity(legs.len());
+        for (i, leg) in legs.iter().enumerate() {
+            if legs[..i].iter().any(|other| other[0].key == leg[0].key) {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            let (order, _) = validate_order_pda(program_id, &leg[0])?;
+            validate_matchable(&order)?;
+            validate_order_unregistered(&order)?;
+            orders.push(order);
+        }
+
+        for (i, leg) in legs.iter().enumerate() {
+            let [order_info, escrow, mint, receiver_ata, treasury_ata, submitter_ata] = leg else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let order = &orders[i];
+            // this order's escrow pays the previous order's maker
+            let previous = &orders[(i + orders.len() - 1) % orders.len()];
+
+            if previous.taker_token_mint != order.maker_token_mint {
+                return Err(SwapError::InvalidMint.into());
+            }
+            if order.maker_amount < previous.taker_amount {
+                return Err(SwapError::OrdersNotCrossing.into());
+            }
+
+            validate_mint(mint, &order.maker_token_mint)?;
+            validate_token_account(escrow, order_info.key, mint.key)?;
+            validate_token_account(receiver_ata, &previous.maker, mint.key)?;
+            validate_token_account(treasury_ata, treasury_account_info.key, mint.key)?;
+            validate_token_account(submitter_ata, submitter_info.key, mint.key)?;
+
+            if get_token_balance(escrow)? < order.maker_amount {
+                return Err(SwapError::InsufficientFunds.into());
+            }
+        }
+
+        for (i, leg) in legs.iter().enumerate() {
+            let [order_info, escrow, mint, receiver_ata, treasury_ata, submitter_ata] = leg else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let order = &orders[i];
+            let previous = &orders[(i + orders.len() - 1) % orders.len()];
+            let leg_token_program = get_token_program(mint, token_program, token_program_2022)?;
+            let order_seeds = order.signer_seeds();
+
+            transfer_tokens_with_fee(
+                leg_token_program,
+                escrow,
+                mint,
+                receiver_ata,
+                treasury_ata,
+                order_info,
+                previous.taker_amount,
+                treasury.fee,
+                &[&order_seeds],
+            )?;
+
+            // whatever the previous order didn't ask for goes to the submitter
+            transfer_tokens(
+                leg_token_program,
+                escrow,
+                mint,
+                submitter_ata,
+                order_info,
+                order.maker_amount - previous.taker_amount,
+                &[&order_seeds],
+            )?;
+        }
+
+        for (leg, order) in legs.iter().zip(orders.iter_mut()) {
+            order.status = OrderStatus::Filled;
+            order.serialize(&mut *leg[0].data.borrow_mut())?;
+        }
+
+        Ok(())
+    }
+}
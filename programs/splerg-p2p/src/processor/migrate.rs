@@ -0,0 +1,95 @@
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{LegacySwapOrder, LegacyTreasury, SwapOrder, Treasury},
+    utils::grow_program_account,
+    validation::{get_order_pda, get_treasury_pda, validate_signer, validate_system_program},
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_migrate_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        validate_signer(payer_info)?;
+        validate_system_program(system_program_info.key)?;
+        if order_account_info.owner != program_id
+            || order_account_info.data_len() != LegacySwapOrder::LEN
+        {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        let legacy = LegacySwapOrder::try_from_slice(&order_account_info.data.borrow())?;
+        let (pda, bump) = get_order_pda(
+            program_id,
+            &legacy.id,
+            &legacy.maker,
+            &legacy.maker_token_mint,
+            &legacy.taker_token_mint,
+        )?;
+        if pda != *order_account_info.key || legacy.bump != bump {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        grow_program_account(
+            order_account_info,
+            payer_info,
+            system_program_info,
+            SwapOrder::LEN,
+        )?;
+        legacy
+            .migrate()
+            .serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_migrate_treasury(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        validate_signer(payer_info)?;
+        validate_system_program(system_program_info.key)?;
+        let (pda, _) = get_treasury_pda(program_id)?;
+        if pda != *treasury_account_info.key || treasury_account_info.owner != program_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if treasury_account_info.data_len() != LegacyTreasury::LEN {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        let legacy = LegacyTreasury::try_from_slice(&treasury_account_info.data.borrow())?;
+        grow_program_account(
+            treasury_account_info,
+            payer_info,
+            system_program_info,
+            Treasury::LEN,
+        )?;
+        legacy
+            .migrate()
+            .serialize(&mut *treasury_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
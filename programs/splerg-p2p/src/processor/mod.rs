@@ -19,16 +19,45 @@ use crate::{
     error::SwapError,
     instruction::SwapInstruction,
     math::fee::calculate_token_fee,
-    state::{SwapOrder, Treasury},
-    utils::get_mint_decimals,
+    state::{OrderKind, SwapOrder, Treasury},
+    utils::{get_mint_decimals, get_token_program},
     validation::{
-        get_order_pda, get_treasury_pda, validate_authority, validate_init_amounts, validate_mint,
-        validate_order_pda, validate_rent_sysvar, validate_signer, validate_system_program,
-        validate_taker, validate_token_account, validate_token_mint, validate_token_program,
-        validate_treasury_authority,
+        get_order_pda, get_position_holder, get_treasury_pda, get_vesting_pda, validate_authority,
+        validate_init_amounts, validate_mint, validate_no_taker_deposit, validate_order_closable,
+        validate_order_kind, validate_order_open, validate_order_pda, validate_order_repriceable,
+        validate_order_started, validate_order_unexpired, validate_order_unreserved,
+        validate_rent_sysvar, validate_signer, validate_system_program, validate_taker,
+        validate_token_account, validate_token_mint, validate_token_program,
+        validate_treasury_authority, validate_treasury_pda,
     },
 };
 
+mod arbitration;
+mod auction;
+mod batch;
+mod chain;
+mod claim_code;
+mod counter_offer;
+mod covered_call;
+mod deposit;
+mod group;
+mod htlc;
+mod invoice;
+mod matching;
+mod migrate;
+mod peg;
+mod position;
+mod recurring;
+mod registry;
+mod reservation;
+mod sale;
+mod schedule;
+mod sealed_auction;
+mod sweep;
+mod trigger;
+mod twap;
+mod vesting;
+
 pub struct Processor;
 
 impl Processor {
@@ -63,8 +92,305 @@ impl Processor {
             SwapInstruction::ChangeTaker { new_taker } => {
                 Self::process_change_taker(accounts, new_taker)
             }
-            SwapInstruction::CompleteSwap => Self::process_complete_swap(program_id, accounts),
+            SwapInstruction::CompleteSwap => {
+                Self::process_complete_swap(program_id, accounts, None)
+            }
             SwapInstruction::CloseOrder => Self::process_close_order(program_id, accounts),
+            SwapInstruction::InitializeAuction {
+                maker_amount,
+                reserve_price,
+                end_ts,
+            } => Self::process_initialize_auction(
+                program_id,
+                accounts,
+                maker_amount,
+                reserve_price,
+                end_ts,
+            ),
+            SwapInstruction::PlaceBid { amount } => {
+                Self::process_place_bid(program_id, accounts, amount)
+            }
+            SwapInstruction::SettleAuction => Self::process_settle_auction(program_id, accounts),
+            SwapInstruction::InitializeSealedAuction {
+                maker_amount,
+                reserve_price,
+                commit_end_ts,
+                reveal_end_ts,
+                penalty_bps,
+            } => Self::process_initialize_sealed_auction(
+                program_id,
+                accounts,
+                maker_amount,
+                reserve_price,
+                commit_end_ts,
+                reveal_end_ts,
+                penalty_bps,
+            ),
+            SwapInstruction::CommitBid {
+                commitment,
+                deposit,
+            } => Self::process_commit_bid(program_id, accounts, commitment, deposit),
+            SwapInstruction::RevealBid { amount, salt } => {
+                Self::process_reveal_bid(program_id, accounts, amount, salt)
+            }
+            SwapInstruction::SettleSealedAuction => {
+                Self::process_settle_sealed_auction(program_id, accounts)
+            }
+            SwapInstruction::RefundSealedBid => {
+                Self::process_refund_sealed_bid(program_id, accounts)
+            }
+            SwapInstruction::ProposeCounterOffer {
+                maker_amount,
+                taker_amount,
+                expires_ts,
+            } => Self::process_propose_counter_offer(
+                program_id,
+                accounts,
+                maker_amount,
+                taker_amount,
+                expires_ts,
+            ),
+            SwapInstruction::AcceptCounterOffer => {
+                Self::process_accept_counter_offer(program_id, accounts)
+            }
+            SwapInstruction::WithdrawCounterOffer => {
+                Self::process_withdraw_counter_offer(program_id, accounts)
+            }
+            SwapInstruction::UpdateCrankerShare { cranker_share_bps } => {
+                Self::process_update_cranker_share(accounts, cranker_share_bps)
+            }
+            SwapInstruction::MatchOrders => Self::process_match_orders(program_id, accounts),
+            SwapInstruction::SettleRing => Self::process_settle_ring(program_id, accounts),
+            SwapInstruction::SweepOrders {
+                max_taker_amount,
+                min_maker_amount,
+            } => {
+                Self::process_sweep_orders(program_id, accounts, max_taker_amount, min_maker_amount)
+            }
+            SwapInstruction::BatchInitializeOrders { base_id, levels } => {
+                Self::process_batch_initialize_orders(program_id, accounts, base_id, &levels)
+            }
+            SwapInstruction::BatchChangeOrderAmounts { levels } => {
+                Self::process_batch_change_order_amounts(program_id, accounts, &levels)
+            }
+            SwapInstruction::BatchCancelOrders => {
+                Self::process_batch_cancel_orders(program_id, accounts)
+            }
+            SwapInstruction::InitializeMakerAccount => {
+                Self::process_initialize_maker_account(program_id, accounts)
+            }
+            SwapInstruction::InitializeRegisteredOrder {
+                maker_amount,
+                taker_amount,
+            } => Self::process_initialize_registered_order(
+                program_id,
+                accounts,
+                maker_amount,
+                taker_amount,
+            ),
+            SwapInstruction::CancelAllOrders => {
+                Self::process_cancel_all_orders(program_id, accounts)
+            }
+            SwapInstruction::InitializeRecurringOrder {
+                maker_amount,
+                taker_amount,
+                max_refills,
+                refill_cap,
+            } => Self::process_initialize_recurring_order(
+                program_id,
+                accounts,
+                maker_amount,
+                taker_amount,
+                max_refills,
+                refill_cap,
+            ),
+            SwapInstruction::InitializeTwapOrder {
+                maker_amount,
+                taker_amount,
+                interval,
+                slice,
+            } => Self::process_initialize_twap_order(
+                program_id,
+                accounts,
+                maker_amount,
+                taker_amount,
+                interval,
+                slice,
+            ),
+            SwapInstruction::InitializeOrderGroup { id } => {
+                Self::process_initialize_order_group(program_id, accounts, id)
+            }
+            SwapInstruction::RefundGroupOrder => {
+                Self::process_refund_group_order(program_id, accounts)
+            }
+            SwapInstruction::SetFollowUpOrder {
+                taker_amount,
+                end_ts,
+            } => Self::process_set_follow_up_order(program_id, accounts, taker_amount, end_ts),
+            SwapInstruction::SetVestingSchedule { cliff, duration } => {
+                Self::process_set_vesting_schedule(program_id, accounts, cliff, duration)
+            }
+            SwapInstruction::ClaimVested => Self::process_claim_vested(program_id, accounts),
+            SwapInstruction::OpenDepositWindow { deposit_end_ts } => {
+                Self::process_open_deposit_window(program_id, accounts, deposit_end_ts)
+            }
+            SwapInstruction::DepositTakerTokens {
+                expected_maker_amount,
+                expected_taker_amount,
+            } => Self::process_deposit_taker_tokens(
+                program_id,
+                accounts,
+                expected_maker_amount,
+                expected_taker_amount,
+            ),
+            SwapInstruction::SettleDeposits => Self::process_settle_deposits(program_id, accounts),
+            SwapInstruction::WithdrawTakerDeposit => {
+                Self::process_withdraw_taker_deposit(program_id, accounts)
+            }
+            SwapInstruction::InitializeArbitratedOrder {
+                maker_amount,
+                deadline_ts,
+                milestones,
+            } => Self::process_initialize_arbitrated_order(
+                program_id,
+                accounts,
+                maker_amount,
+                deadline_ts,
+                milestones,
+            ),
+            SwapInstruction::ApproveMilestone => {
+                Self::process_approve_milestone(program_id, accounts)
+            }
+            SwapInstruction::RefundArbitratedOrder => {
+                Self::process_refund_arbitrated_order(program_id, accounts)
+            }
+            SwapInstruction::SetOraclePeg {
+                premium_bps,
+                max_age,
+                max_conf_bps,
+            } => Self::process_set_oracle_peg(
+                program_id,
+                accounts,
+                premium_bps,
+                max_age,
+                max_conf_bps,
+            ),
+            SwapInstruction::SetOrderTrigger {
+                condition,
+                trigger_price,
+                max_age,
+                max_conf_bps,
+            } => Self::process_set_order_trigger(
+                program_id,
+                accounts,
+                condition,
+                trigger_price,
+                max_age,
+                max_conf_bps,
+            ),
+            SwapInstruction::SetOrderStart { start_ts } => {
+                Self::process_set_order_start(program_id, accounts, start_ts)
+            }
+            SwapInstruction::InitializeHtlcOrder {
+                maker_amount,
+                taker_amount,
+                hashlock,
+                timelock_ts,
+            } => Self::process_initialize_htlc_order(
+                program_id,
+                accounts,
+                maker_amount,
+                taker_amount,
+                hashlock,
+                timelock_ts,
+            ),
+            SwapInstruction::ClaimHtlc { preimage } => {
+                Self::process_claim_htlc(program_id, accounts, preimage)
+            }
+            SwapInstruction::ReclaimHtlc => Self::process_reclaim_htlc(program_id, accounts),
+            SwapInstruction::SetClaimCode { claim_code_hash } => {
+                Self::process_set_claim_code(program_id, accounts, claim_code_hash)
+            }
+            SwapInstruction::CommitClaimCode { commitment } => {
+                Self::process_commit_claim_code(program_id, accounts, commitment)
+            }
+            SwapInstruction::RevealClaimCode { secret } => {
+                Self::process_reveal_claim_code(program_id, accounts, secret)
+            }
+            SwapInstruction::ReserveOrder { slots } => {
+                Self::process_reserve_order(program_id, accounts, slots)
+            }
+            SwapInstruction::ForfeitReservation => {
+                Self::process_forfeit_reservation(program_id, accounts)
+            }
+            SwapInstruction::InitializeCoveredCall {
+                maker_amount,
+                strike_amount,
+                premium,
+                expiry_ts,
+            } => Self::process_initialize_covered_call(
+                program_id,
+                accounts,
+                maker_amount,
+                strike_amount,
+                premium,
+                expiry_ts,
+            ),
+            SwapInstruction::BuyOption {
+                maker_amount,
+                strike_amount,
+                premium,
+            } => {
+                Self::process_buy_option(program_id, accounts, maker_amount, strike_amount, premium)
+            }
+            SwapInstruction::TransferOption => Self::process_transfer_option(program_id, accounts),
+            SwapInstruction::ReclaimCoveredCall => {
+                Self::process_reclaim_covered_call(program_id, accounts)
+            }
+            SwapInstruction::InitializeInvoice { taker_amount } => {
+                Self::process_initialize_invoice(program_id, accounts, taker_amount)
+            }
+            SwapInstruction::PayInvoice => Self::process_pay_invoice(program_id, accounts),
+            SwapInstruction::InitializeDrop { maker_amount } => {
+                Self::process_initialize_drop(program_id, accounts, maker_amount)
+            }
+            SwapInstruction::ClaimDrop => Self::process_claim_drop(program_id, accounts),
+            SwapInstruction::InitializeSale {
+                maker_amount,
+                taker_amount,
+                end_ts,
+                wallet_cap,
+                soft_cap,
+                hard_cap,
+            } => Self::process_initialize_sale(
+                program_id,
+                accounts,
+                maker_amount,
+                taker_amount,
+                end_ts,
+                wallet_cap,
+                soft_cap,
+                hard_cap,
+            ),
+            SwapInstruction::BuyFromSale { amount } => {
+                Self::process_buy_from_sale(program_id, accounts, amount)
+            }
+            SwapInstruction::ClaimSale => Self::process_claim_sale(program_id, accounts),
+            SwapInstruction::FinalizeSale => Self::process_finalize_sale(program_id, accounts),
+            SwapInstruction::MigrateOrder => Self::process_migrate_order(program_id, accounts),
+            SwapInstruction::ReclaimSealedAuction => {
+                Self::process_reclaim_sealed_auction(program_id, accounts)
+            }
+            SwapInstruction::MigrateTreasury => {
+                Self::process_migrate_treasury(program_id, accounts)
+            }
+            SwapInstruction::CompletePeggedSwap { max_taker_amount } => {
+                Self::process_complete_swap(program_id, accounts, Some(max_taker_amount))
+            }
+            SwapInstruction::ReclaimDrop => Self::process_reclaim_drop(program_id, accounts),
+            SwapInstruction::WithdrawClaimCommitment => {
+                Self::process_withdraw_claim_commitment(program_id, accounts)
+            }
         }
     }
 
@@ -142,6 +468,27 @@ impl Processor {
         Ok(())
     }
 
+    fn process_update_cranker_share(
+        accounts: &[AccountInfo],
+        cranker_share_bps: u16,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+
+        validate_treasury_authority(treasury_account_info, authority_info)?;
+
+        if cranker_share_bps > 10_000 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut treasury = Treasury::try_from_slice(&treasury_account_info.data.borrow())?;
+        treasury.cranker_share_bps = cranker_share_bps;
+        treasury.serialize(&mut *treasury_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
     fn process_harvest(accounts: &[AccountInfo]) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let authority_info = next_account_info(account_info_iter)?;
@@ -225,6 +572,34 @@ impl Processor {
         maker_amount: u64,
         taker_amount: u64,
     ) -> ProgramResult {
+        let (mut order, order_account_info) =
+            Self::create_order(program_id, accounts, maker_amount, taker_amount)?;
+        Self::mint_position(program_id, accounts, &mut order, order_account_info)?;
+
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Creates the order PDA and escrows the maker tokens, leaving the caller to
+    /// adjust and serialize the returned order
+    pub(crate) fn create_order<'a, 'b>(
+        program_id: &Pubkey,
+        accounts: &'b [AccountInfo<'a>],
+        maker_amount: u64,
+        taker_amount: u64,
+    ) -> Result<(SwapOrder, &'b AccountInfo<'a>), ProgramError> {
+        validate_init_amounts(maker_amount, taker_amount)?;
+        Self::create_order_account(program_id, accounts, maker_amount, taker_amount)
+    }
+
+    /// `create_order` without the amount checks, for order kinds that validate their own
+    pub(crate) fn create_order_account<'a, 'b>(
+        program_id: &Pubkey,
+        accounts: &'b [AccountInfo<'a>],
+        maker_amount: u64,
+        taker_amount: u64,
+    ) -> Result<(SwapOrder, &'b AccountInfo<'a>), ProgramError> {
         let account_info_iter = &mut accounts.iter();
         let maker_info = next_account_info(account_info_iter)?;
         let order_account_info = next_account_info(account_info_iter)?;
@@ -238,7 +613,6 @@ impl Processor {
         let token_program = next_account_info(account_info_iter)?;
 
         validate_signer(maker_info)?;
-        validate_init_amounts(maker_amount, taker_amount)?;
         validate_token_mint(maker_mint_info)?;
         validate_token_mint(taker_mint_info)?;
         check_spl_token_program_account(token_program.key)?;
@@ -283,38 +657,42 @@ impl Processor {
             ]],
         )?;
 
-        let transfer_instruction = if *token_program.key == spl_token::id() {
-            spl_token::instruction::transfer(
-                token_program.key,
-                maker_mint_ata.key,
-                order_maker_ata.key,
-                maker_info.key,
-                &[],
-                maker_amount,
-            )?
-        } else {
-            let account_data = spl_token_2022::state::Mint::unpack(&maker_mint_info.data.borrow())?;
-            spl_token_2022::instruction::transfer_checked(
-                token_program.key,
-                maker_mint_ata.key,
-                maker_mint_info.key,
-                order_maker_ata.key,
-                maker_info.key,
-                &[],
-                maker_amount,
-                account_data.decimals,
-            )?
-        };
+        // an invoice has nothing to escrow
+        if maker_amount > 0 {
+            let transfer_instruction = if *token_program.key == spl_token::id() {
+                spl_token::instruction::transfer(
+                    token_program.key,
+                    maker_mint_ata.key,
+                    order_maker_ata.key,
+                    maker_info.key,
+                    &[],
+                    maker_amount,
+                )?
+            } else {
+                let account_data =
+                    spl_token_2022::state::Mint::unpack(&maker_mint_info.data.borrow())?;
+                spl_token_2022::instruction::transfer_checked(
+                    token_program.key,
+                    maker_mint_ata.key,
+                    maker_mint_info.key,
+                    order_maker_ata.key,
+                    maker_info.key,
+                    &[],
+                    maker_amount,
+                    account_data.decimals,
+                )?
+            };
 
-        invoke(
-            &transfer_instruction,
-            &[
-                maker_mint_ata.clone(),
-                order_maker_ata.clone(),
-                maker_info.clone(),
-                token_program.clone(),
-            ],
-        )?;
+            invoke(
+                &transfer_instruction,
+                &[
+                    maker_mint_ata.clone(),
+                    order_maker_ata.clone(),
+                    maker_info.clone(),
+                    token_program.clone(),
+                ],
+            )?;
+        }
 
         let order = SwapOrder::new(
             *order_id.key,
@@ -326,9 +704,7 @@ impl Processor {
             bump,
         );
 
-        order.serialize(&mut *order_account_info.data.borrow_mut())?;
-
-        Ok(())
+        Ok((order, order_account_info))
     }
 
     fn process_change_order_amounts(
@@ -347,7 +723,10 @@ impl Processor {
 
         let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
 
-        validate_authority(maker_info, &order)?;
+        Self::validate_maker_rights(maker_info, &order, account_info_iter)?;
+        let registry_info = Self::next_registry_account(&order, account_info_iter)?;
+        validate_order_repriceable(&order)?;
+        let open_before = order.open_maker_amount();
         validate_mint(mint_info, &order.maker_token_mint)?;
         check_spl_token_program_account(token_program.key)?;
         validate_token_account(
@@ -430,14 +809,7 @@ impl Processor {
                             order_account_info.clone(),
                             token_program.clone(),
                         ],
-                        &[&[
-                            b"order",
-                            &order.id.to_bytes(),
-                            maker_info.key.as_ref(),
-                            &order.maker_token_mint.to_bytes(),
-                            &order.taker_token_mint.to_bytes(),
-                            &[order.bump],
-                        ]],
+                        &[&order.signer_seeds()],
                     )?;
                 } else {
                     invoke_signed(
@@ -457,14 +829,7 @@ impl Processor {
                             order_account_info.clone(),
                             token_program.clone(),
                         ],
-                        &[&[
-                            b"order",
-                            &order.id.to_bytes(),
-                            maker_info.key.as_ref(),
-                            &order.maker_token_mint.to_bytes(),
-                            &order.taker_token_mint.to_bytes(),
-                            &[order.bump],
-                        ]],
+                        &[&order.signer_seeds()],
                     )?;
                 }
             }
@@ -475,6 +840,14 @@ impl Processor {
         order.taker_amount = new_taker_amount;
         order.serialize(&mut *order_account_info.data.borrow_mut())?;
 
+        Self::update_registry(
+            program_id,
+            registry_info,
+            &order.maker,
+            open_before,
+            order.open_maker_amount(),
+        )?;
+
         Ok(())
     }
 
@@ -486,6 +859,9 @@ impl Processor {
 
         let mut order = SwapOrder::try_from_slice(&order_account_info.data.borrow())?;
         validate_authority(maker_info, &order)?;
+        validate_order_kind(&order, OrderKind::Fixed)?;
+        validate_no_taker_deposit(&order)?;
+        validate_order_unreserved(&order)?;
 
         if Pubkey::new_from_array(new_taker) != *new_taker_info.key {
             return Err(ProgramError::InvalidArgument);
@@ -497,7 +873,11 @@ impl Processor {
         Ok(())
     }
 
-    fn process_complete_swap(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    fn process_complete_swap(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        max_taker_amount: Option<u64>,
+    ) -> ProgramResult {
         let account_info_iter: &mut std::slice::Iter<'_, AccountInfo<'_>> = &mut accounts.iter();
         let taker_info = next_account_info(account_info_iter)?;
         let order_account_info = next_account_info(account_info_iter)?;
@@ -513,14 +893,87 @@ impl Processor {
         let token_program = next_account_info(account_info_iter)?;
         let token_program_2022 = next_account_info(account_info_iter)?;
 
-        let (order, _) = validate_order_pda(program_id, order_account_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_open(&order)?;
+        validate_order_started(&order)?;
+        validate_order_unexpired(&order)?;
+        validate_no_taker_deposit(&order)?;
         validate_taker(taker_info, &order)?;
+        let open_before = order.open_maker_amount();
         validate_mint(maker_mint, &order.maker_token_mint)?;
         validate_mint(taker_mint, &order.taker_token_mint)?;
 
+        // trailing accounts are only passed for the features the order uses
+        let funding_account = if order.funding_account != Pubkey::default() {
+            Some(next_account_info(account_info_iter)?)
+        } else {
+            None
+        };
+        if order.group != Pubkey::default() {
+            let group_info = next_account_info(account_info_iter)?;
+            Self::fill_group_member(program_id, group_info, order_account_info, &order)?;
+        }
+        let follow_up_accounts = if order.follow_up_taker_mint != Pubkey::default() {
+            Some((
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+            ))
+        } else {
+            None
+        };
+
+        let vesting_accounts = if order.vesting_duration != 0 {
+            Some((
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+            ))
+        } else {
+            None
+        };
+        let oracle_info = if order.oracle != Pubkey::default() {
+            Some(next_account_info(account_info_iter)?)
+        } else {
+            None
+        };
+        if order.trigger_oracle != Pubkey::default() {
+            let trigger_oracle_info = next_account_info(account_info_iter)?;
+            Self::validate_order_triggered(&order, trigger_oracle_info)?;
+        }
+        if order.reserved_by != Pubkey::default() {
+            let order_taker_ata = next_account_info(account_info_iter)?;
+            Self::release_reservation(
+                &mut order,
+                order_account_info,
+                order_taker_ata,
+                taker_info,
+                taker_ata,
+                taker_mint,
+                get_token_program(taker_mint, token_program, token_program_2022)?,
+            )?;
+        }
+
+        // a tokenized order pays whoever holds the position token
+        let maker_rights_holder = if order.position_mint != Pubkey::default() {
+            get_position_holder(next_account_info(account_info_iter)?, &order)?
+        } else {
+            order.maker
+        };
+        let registry_info = Self::next_registry_account(&order, account_info_iter)?;
+
+        // a chained order's proceeds go straight into the follow-up order's escrow
+        let proceeds_owner = match follow_up_accounts {
+            Some(_) => Self::get_follow_up_order_pda(program_id, order_account_info.key, &order)?.0,
+            None => maker_rights_holder,
+        };
+        // and a vesting order's maker tokens into the taker's vesting escrow
+        let delivery_owner = match vesting_accounts {
+            Some(_) => get_vesting_pda(program_id, order_account_info.key)?.0,
+            None => *taker_info.key,
+        };
+
         check_spl_token_program_account(token_program.key)?;
-        validate_token_account(maker_taker_ata, &order.maker, &order.taker_token_mint)?;
-        validate_token_account(taker_maker_ata, taker_info.key, &order.maker_token_mint)?;
+        validate_token_account(maker_taker_ata, &proceeds_owner, &order.taker_token_mint)?;
+        validate_token_account(taker_maker_ata, &delivery_owner, &order.maker_token_mint)?;
         validate_token_account(taker_ata, taker_info.key, &order.taker_token_mint)?;
         validate_token_account(
             order_maker_ata,
@@ -533,19 +986,44 @@ impl Processor {
             return Err(SwapError::InsufficientFunds.into());
         }
 
-        let treasury = Treasury::try_from_slice(&treasury_account_info.data.borrow())?;
+        let (maker_amount, taker_amount) = match order.kind {
+            OrderKind::Fixed => (order.maker_amount, order.taker_amount),
+            OrderKind::Twap => Self::twap_fill_amounts(&order)?,
+            // only the holder of the option right can exercise it
+            OrderKind::CoveredCall if order.taker != Pubkey::default() => {
+                (order.maker_amount, order.taker_amount)
+            }
+            _ => return Err(SwapError::InvalidOrderState.into()),
+        };
+        let taker_amount = match oracle_info {
+            // the oracle sets the price, so the taker has to cap it
+            Some(oracle_info) => {
+                let taker_amount = Self::pegged_taker_amount(
+                    &order,
+                    oracle_info,
+                    maker_mint,
+                    taker_mint,
+                    maker_amount,
+                )?;
+                if taker_amount > max_taker_amount.ok_or(SwapError::InvalidInstruction)? {
+                    return Err(SwapError::LimitPriceExceeded.into());
+                }
+                taker_amount
+            }
+            None => taker_amount,
+        };
+
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
 
-        let maker_fee = calculate_token_fee(order.maker_amount.into(), treasury.fee)
+        let maker_fee = calculate_token_fee(maker_amount.into(), treasury.fee)
             .map_err(|_| ProgramError::from(SwapError::Overflow))?;
-        let taker_fee = calculate_token_fee(order.taker_amount.into(), treasury.fee)
+        let taker_fee = calculate_token_fee(taker_amount.into(), treasury.fee)
             .map_err(|_| ProgramError::from(SwapError::Overflow))?;
 
-        let maker_amount_after_fee = order
-            .maker_amount
+        let maker_amount_after_fee = maker_amount
             .checked_sub(maker_fee.try_into().map_err(|_| SwapError::Overflow)?)
             .ok_or(SwapError::Overflow)?;
-        let taker_amount_after_fee = order
-            .taker_amount
+        let taker_amount_after_fee = taker_amount
             .checked_sub(taker_fee.try_into().map_err(|_| SwapError::Overflow)?)
             .ok_or(SwapError::Overflow)?;
 
@@ -757,6 +1235,49 @@ impl Processor {
             }
         }
 
+        if order.kind == OrderKind::Twap {
+            Self::apply_twap_fill(&mut order, maker_amount, taker_amount)?;
+        } else {
+            Self::refill_or_fill_order(
+                &mut order,
+                order_account_info,
+                order_maker_ata,
+                funding_account,
+                maker_mint,
+                get_token_program(maker_mint, token_program, token_program_2022)?,
+            )?;
+        }
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+        Self::update_registry(
+            program_id,
+            registry_info,
+            &order.maker,
+            open_before,
+            order.open_maker_amount(),
+        )?;
+
+        if let Some((follow_up_info, system_program_info)) = follow_up_accounts {
+            Self::create_follow_up_order(
+                program_id,
+                order_account_info,
+                &order,
+                follow_up_info,
+                system_program_info,
+                taker_amount_after_fee,
+            )?;
+        }
+        if let Some((vesting_info, system_program_info)) = vesting_accounts {
+            Self::create_vesting(
+                program_id,
+                order_account_info,
+                &order,
+                taker_info,
+                vesting_info,
+                system_program_info,
+                maker_amount_after_fee,
+            )?;
+        }
+
         Ok(())
     }
 
@@ -766,7 +1287,17 @@ impl Processor {
         let order_account_info = next_account_info(account_info_iter)?;
 
         let (order, _) = validate_order_pda(program_id, order_account_info)?;
-        validate_authority(authority_info, &order)?;
+        Self::validate_maker_rights(authority_info, &order, account_info_iter)?;
+        let registry_info = Self::next_registry_account(&order, account_info_iter)?;
+
+        validate_order_closable(&order)?;
+        Self::update_registry(
+            program_id,
+            registry_info,
+            &order.maker,
+            order.open_maker_amount(),
+            None,
+        )?;
 
         // Transfer rent to authority
         let rent_lamports = order_account_info.lamports();
@@ -0,0 +1,90 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    math::oracle::pegged_taker_amount,
+    oracle::{load_price, read_price},
+    state::{OrderKind, SwapOrder},
+    utils::get_mint_decimals,
+    validation::{
+        validate_authority, validate_order_kind, validate_order_open, validate_order_pda,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_set_oracle_peg(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        premium_bps: i16,
+        max_age: i64,
+        max_conf_bps: u16,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let oracle_info = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_kind(&order, OrderKind::Fixed)?;
+        validate_order_open(&order)?;
+        // a taker deposit was sized for the fixed price
+        if order.deposit_end_ts != 0 {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        read_price(oracle_info)?;
+        if premium_bps <= -10_000 || max_age < 0 {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        order.oracle = *oracle_info.key;
+        order.oracle_premium_bps = premium_bps;
+        order.oracle_max_age = max_age;
+        order.oracle_max_conf_bps = max_conf_bps;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Taker amount for `maker_amount` of a pegged order at the current oracle price
+    pub(crate) fn pegged_taker_amount(
+        order: &SwapOrder,
+        oracle_info: &AccountInfo,
+        maker_mint: &AccountInfo,
+        taker_mint: &AccountInfo,
+        maker_amount: u64,
+    ) -> Result<u64, ProgramError> {
+        if *oracle_info.key != order.oracle {
+            return Err(SwapError::InvalidOracle.into());
+        }
+
+        let price = load_price(
+            oracle_info,
+            Clock::get()?.unix_timestamp,
+            order.oracle_max_age,
+            order.oracle_max_conf_bps,
+        )?;
+
+        Ok(pegged_taker_amount(
+            maker_amount,
+            price.price,
+            price.expo,
+            get_mint_decimals(maker_mint)?,
+            get_mint_decimals(taker_mint)?,
+            order.oracle_premium_bps,
+        )?)
+    }
+}
@@ -0,0 +1,160 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_instruction,
+    sysvar::rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    error::SwapError,
+    state::SwapOrder,
+    validation::{get_position_mint_pda, validate_authority, validate_position_holder},
+};
+
+use super::Processor;
+
+/// Number of accounts shared with `InitializeOrder`
+const INITIALIZE_ORDER_ACCOUNTS: usize = 10;
+
+impl Processor {
+    /// Mints a single position token to the maker when `InitializeOrder` gets the trailing
+    /// position accounts, from then on the maker's rights follow the token
+    pub(crate) fn mint_position<'a>(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo<'a>],
+        order: &mut SwapOrder,
+        order_account_info: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        if accounts.len() <= INITIALIZE_ORDER_ACCOUNTS {
+            return Ok(());
+        }
+        let maker_info = &accounts[0];
+        let system_program_info = &accounts[7];
+        let rent_info = &accounts[8];
+
+        let account_info_iter = &mut accounts[INITIALIZE_ORDER_ACCOUNTS..].iter();
+        let position_mint_info = next_account_info(account_info_iter)?;
+        let maker_position_ata = next_account_info(account_info_iter)?;
+        let token_program_2022 = next_account_info(account_info_iter)?;
+        let associated_token_program = next_account_info(account_info_iter)?;
+
+        if *token_program_2022.key != spl_token_2022::id() {
+            return Err(SwapError::InvalidTokenProgram.into());
+        }
+        if *associated_token_program.key != spl_associated_token_account::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let (position_mint, bump) = get_position_mint_pda(program_id, order_account_info.key)?;
+        if position_mint != *position_mint_info.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let rent = Rent::from_account_info(rent_info)?;
+        let space = spl_token_2022::state::Mint::LEN;
+        invoke_signed(
+            &system_instruction::create_account(
+                maker_info.key,
+                position_mint_info.key,
+                rent.minimum_balance(space),
+                space as u64,
+                token_program_2022.key,
+            ),
+            &[
+                maker_info.clone(),
+                position_mint_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[b"position", order_account_info.key.as_ref(), &[bump]]],
+        )?;
+
+        invoke(
+            &spl_token_2022::instruction::initialize_mint2(
+                token_program_2022.key,
+                position_mint_info.key,
+                order_account_info.key,
+                None,
+                0,
+            )?,
+            &[position_mint_info.clone(), token_program_2022.clone()],
+        )?;
+
+        invoke(
+            &spl_associated_token_account::instruction::create_associated_token_account(
+                maker_info.key,
+                maker_info.key,
+                position_mint_info.key,
+                token_program_2022.key,
+            ),
+            &[
+                maker_info.clone(),
+                maker_position_ata.clone(),
+                maker_info.clone(),
+                position_mint_info.clone(),
+                system_program_info.clone(),
+                token_program_2022.clone(),
+                associated_token_program.clone(),
+            ],
+        )?;
+
+        let order_seeds = order.signer_seeds();
+        invoke_signed(
+            &spl_token_2022::instruction::mint_to(
+                token_program_2022.key,
+                position_mint_info.key,
+                maker_position_ata.key,
+                order_account_info.key,
+                &[],
+                1,
+            )?,
+            &[
+                position_mint_info.clone(),
+                maker_position_ata.clone(),
+                order_account_info.clone(),
+                token_program_2022.clone(),
+            ],
+            &[&order_seeds],
+        )?;
+
+        // drop the mint authority so the position can never be duplicated
+        invoke_signed(
+            &spl_token_2022::instruction::set_authority(
+                token_program_2022.key,
+                position_mint_info.key,
+                None,
+                spl_token_2022::instruction::AuthorityType::MintTokens,
+                order_account_info.key,
+                &[],
+            )?,
+            &[
+                position_mint_info.clone(),
+                order_account_info.clone(),
+                token_program_2022.clone(),
+            ],
+            &[&order_seeds],
+        )?;
+
+        order.position_mint = *position_mint_info.key;
+
+        Ok(())
+    }
+
+    /// Checks the authority holds the maker's rights, which is `maker` itself unless the
+    /// order was tokenized, then the holder's position token account is the next account
+    pub(crate) fn validate_maker_rights<'a, 'b>(
+        authority: &AccountInfo<'a>,
+        order: &SwapOrder,
+        account_info_iter: &mut std::slice::Iter<'b, AccountInfo<'a>>,
+    ) -> ProgramResult {
+        if order.position_mint == Pubkey::default() {
+            return validate_authority(authority, order);
+        }
+
+        let position_account = next_account_info(account_info_iter)?;
+        validate_position_holder(authority, position_account, order)
+    }
+}
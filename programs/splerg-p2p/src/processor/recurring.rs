@@ -0,0 +1,95 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{OrderStatus, SwapOrder},
+    utils::{get_delegated_balance, transfer_tokens},
+    validation::validate_token_account,
+};
+
+use super::Processor;
+
+/// Number of accounts shared with `InitializeOrder`
+const INITIALIZE_ORDER_ACCOUNTS: usize = 10;
+
+impl Processor {
+    pub(crate) fn process_initialize_recurring_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+        taker_amount: u64,
+        max_refills: u64,
+        refill_cap: u64,
+    ) -> ProgramResult {
+        if accounts.len() <= INITIALIZE_ORDER_ACCOUNTS {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (order_accounts, recurring_accounts) = accounts.split_at(INITIALIZE_ORDER_ACCOUNTS);
+        let maker_info = &order_accounts[0];
+        let maker_mint_info = &order_accounts[5];
+        let funding_account = &recurring_accounts[0];
+
+        validate_token_account(funding_account, maker_info.key, maker_mint_info.key)?;
+
+        let (mut order, order_account_info) =
+            Self::create_order(program_id, order_accounts, maker_amount, taker_amount)?;
+
+        order.funding_account = *funding_account.key;
+        order.refills_remaining = max_refills;
+        order.refill_cap = refill_cap;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Marks a completely filled order as filled, or re-escrows `maker_amount` from the
+    /// funding account and keeps it open if the order is recurring and can still refill
+    pub(crate) fn refill_or_fill_order<'a>(
+        order: &mut SwapOrder,
+        order_account_info: &AccountInfo<'a>,
+        order_maker_ata: &AccountInfo<'a>,
+        funding_account: Option<&AccountInfo<'a>>,
+        maker_mint: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        order.status = OrderStatus::Filled;
+        if order.funding_account == Pubkey::default() {
+            return Ok(());
+        }
+
+        let funding_account = funding_account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if *funding_account.key != order.funding_account {
+            return Err(SwapError::InvalidTokenAccount.into());
+        }
+
+        // an exhausted cap or a revoked delegation ends the order like a normal fill
+        if order.refills_remaining == 0
+            || order.refill_cap < order.maker_amount
+            || get_delegated_balance(funding_account, order_account_info.key)? < order.maker_amount
+        {
+            return Ok(());
+        }
+
+        transfer_tokens(
+            token_program,
+            funding_account,
+            maker_mint,
+            order_maker_ata,
+            order_account_info,
+            order.maker_amount,
+            &[&order.signer_seeds()],
+        )?;
+
+        order.refills_remaining -= 1;
+        order.refill_cap -= order.maker_amount;
+        order.status = OrderStatus::Open;
+
+        Ok(())
+    }
+}
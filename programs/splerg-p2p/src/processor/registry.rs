@@ -0,0 +1,232 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::{MakerAccount, SwapOrder},
+    utils::{close_program_account, get_token_balance, get_token_program, transfer_tokens},
+    validation::{
+        get_maker_account_pda, validate_authority, validate_maker_account_pda, validate_mint,
+        validate_order_closable, validate_order_pda, validate_rent_sysvar, validate_signer,
+        validate_system_program, validate_token_account,
+    },
+};
+
+use super::Processor;
+
+/// Accounts passed per order to `CancelAllOrders`
+const CANCEL_ORDER_ACCOUNTS: usize = 4;
+
+/// Number of accounts shared with `InitializeOrder`
+const INITIALIZE_ORDER_ACCOUNTS: usize = 10;
+
+/// Moves the registry's counters from an order's open maker amount before an instruction
+/// to the one after it, see `SwapOrder::open_maker_amount`
+fn track_order(
+    maker_account: &mut MakerAccount,
+    before: Option<u64>,
+    after: Option<u64>,
+) -> ProgramResult {
+    maker_account.open_orders = match (before, after) {
+        (None, Some(_)) => maker_account.open_orders.checked_add(1),
+        (Some(_), None) => maker_account.open_orders.checked_sub(1),
+        _ => Some(maker_account.open_orders),
+    }
+    .ok_or(SwapError::Overflow)?;
+    maker_account.escrowed_amount = maker_account
+        .escrowed_amount
+        .checked_sub(before.unwrap_or(0))
+        .and_then(|amount| amount.checked_add(after.unwrap_or(0)))
+        .ok_or(SwapError::Overflow)?;
+    Ok(())
+}
+
+impl Processor {
+    pub(crate) fn process_initialize_maker_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let maker_account_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+
+        validate_signer(maker_info)?;
+        validate_system_program(system_program_info.key)?;
+        validate_rent_sysvar(rent_info.key)?;
+
+        let (maker_account_pda, bump) = get_maker_account_pda(program_id, maker_info.key)?;
+        if maker_account_pda != *maker_account_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let rent = Rent::from_account_info(rent_info)?;
+        invoke_signed(
+            &system_instruction::create_account(
+                maker_info.key,
+                maker_account_info.key,
+                rent.minimum_balance(MakerAccount::LEN),
+                MakerAccount::LEN as u64,
+                program_id,
+            ),
+            &[
+                maker_info.clone(),
+                maker_account_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[b"maker", maker_info.key.as_ref(), &[bump]]],
+        )?;
+
+        let maker_account = MakerAccount::new(*maker_info.key, bump);
+        maker_account.serialize(&mut *maker_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_initialize_registered_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+        taker_amount: u64,
+    ) -> ProgramResult {
+        if accounts.len() <= INITIALIZE_ORDER_ACCOUNTS {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (order_accounts, registry_accounts) = accounts.split_at(INITIALIZE_ORDER_ACCOUNTS);
+        let maker_info = &order_accounts[0];
+        let order_id = &order_accounts[4];
+        let maker_account_info = &registry_accounts[0];
+
+        let mut maker_account =
+            validate_maker_account_pda(program_id, maker_account_info, maker_info.key)?;
+        if *order_id.key != MakerAccount::order_id(maker_info.key, maker_account.nonce) {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let (mut order, order_account_info) =
+            Self::create_order(program_id, order_accounts, maker_amount, taker_amount)?;
+        order.registry = *maker_account_info.key;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        maker_account.nonce = maker_account
+            .nonce
+            .checked_add(1)
+            .ok_or(SwapError::Overflow)?;
+        track_order(&mut maker_account, None, order.open_maker_amount())?;
+        maker_account.serialize(&mut *maker_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_cancel_all_orders(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let maker_account_info = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let token_program_2022 = next_account_info(account_info_iter)?;
+
+        validate_signer(maker_info)?;
+        let mut maker_account =
+            validate_maker_account_pda(program_id, maker_account_info, maker_info.key)?;
+
+        let order_groups = account_info_iter
+            .as_slice()
+            .chunks_exact(CANCEL_ORDER_ACCOUNTS);
+        if !order_groups.remainder().is_empty() {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        // walks the registry nonces alongside the orders, which also rules out duplicates
+        let mut nonce = 0;
+        for group in order_groups {
+            let [order_account_info, order_maker_ata, maker_ata, maker_mint] = group else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            let (order, _) = validate_order_pda(program_id, order_account_info)?;
+            validate_authority(maker_info, &order)?;
+            validate_order_closable(&order)?;
+
+            loop {
+                if nonce >= maker_account.nonce {
+                    return Err(SwapError::InvalidOrderState.into());
+                }
+                if MakerAccount::order_id(maker_info.key, nonce) == order.id {
+                    break;
+                }
+                nonce += 1;
+            }
+            nonce += 1;
+
+            validate_mint(maker_mint, &order.maker_token_mint)?;
+            validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+            validate_token_account(maker_ata, maker_info.key, maker_mint.key)?;
+
+            transfer_tokens(
+                get_token_program(maker_mint, token_program, token_program_2022)?,
+                order_maker_ata,
+                maker_mint,
+                maker_ata,
+                order_account_info,
+                get_token_balance(order_maker_ata)?,
+                &[&order.signer_seeds()],
+            )?;
+
+            close_program_account(order_account_info, maker_info)?;
+            track_order(&mut maker_account, order.open_maker_amount(), None)?;
+        }
+
+        maker_account.serialize(&mut *maker_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Takes the maker registry account trailing a registered order's accounts
+    pub(crate) fn next_registry_account<'a, 'b>(
+        order: &SwapOrder,
+        account_info_iter: &mut std::slice::Iter<'b, AccountInfo<'a>>,
+    ) -> Result<Option<&'b AccountInfo<'a>>, ProgramError> {
+        if order.registry == Pubkey::default() {
+            return Ok(None);
+        }
+        let registry_info = next_account_info(account_info_iter)?;
+        if *registry_info.key != order.registry {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(Some(registry_info))
+    }
+
+    /// Moves a registered order's maker registry from the order's `open_maker_amount`
+    /// before the instruction to the one after it
+    pub(crate) fn update_registry(
+        program_id: &Pubkey,
+        registry_info: Option<&AccountInfo>,
+        maker: &Pubkey,
+        open_before: Option<u64>,
+        open_after: Option<u64>,
+    ) -> ProgramResult {
+        let Some(registry_info) = registry_info else {
+            return Ok(());
+        };
+        let mut maker_account = validate_maker_account_pda(program_id, registry_info, maker)?;
+        track_order(&mut maker_account, open_before, open_after)?;
+        maker_account.serialize(&mut *registry_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
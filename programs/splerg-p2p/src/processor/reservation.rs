@@ -0,0 +1,169 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    math::fee::calculate_token_fee,
+    state::{OrderKind, SwapOrder},
+    utils::transfer_tokens,
+    validation::{
+        validate_mint, validate_no_taker_deposit, validate_order_kind, validate_order_open,
+        validate_order_pda, validate_order_unreserved, validate_order_untokenized, validate_signer,
+        validate_token_account, validate_token_program,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_reserve_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        slots: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let taker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let taker_ata = next_account_info(account_info_iter)?;
+        let order_taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(taker_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Fixed)?;
+        validate_order_open(&order)?;
+        validate_order_unreserved(&order)?;
+        // a forfeited bond is paid to `maker`, who may no longer hold the position
+        validate_order_untokenized(&order)?;
+        validate_no_taker_deposit(&order)?;
+        // only open races can be reserved
+        if order.taker != Pubkey::default() || order.claim_code_hash != [0; 32] {
+            return Err(SwapError::TakerAlreadyAssigned.into());
+        }
+        if slots == 0 || slots > SwapOrder::MAX_RESERVATION_SLOTS {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        validate_token_program(taker_mint, token_program.key)?;
+        validate_token_account(taker_ata, taker_info.key, taker_mint.key)?;
+        validate_token_account(order_taker_ata, order_account_info.key, taker_mint.key)?;
+
+        let bond: u64 =
+            calculate_token_fee(order.taker_amount.into(), SwapOrder::RESERVATION_BOND_BPS)?
+                .try_into()
+                .map_err(|_| SwapError::Overflow)?;
+        let bond = bond.max(1);
+
+        transfer_tokens(
+            token_program,
+            taker_ata,
+            taker_mint,
+            order_taker_ata,
+            taker_info,
+            bond,
+            &[],
+        )?;
+
+        order.reserved_by = *taker_info.key;
+        order.reserved_until_slot = Clock::get()?
+            .slot
+            .checked_add(slots)
+            .ok_or(SwapError::Overflow)?;
+        order.reservation_bond = bond;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Pays a lapsed reservation's bond to the maker, callable by anyone
+    pub(crate) fn process_forfeit_reservation(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_taker_ata = next_account_info(account_info_iter)?;
+        let maker_taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        if order.reserved_by == Pubkey::default() {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+        if Clock::get()?.slot < order.reserved_until_slot {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        validate_token_program(taker_mint, token_program.key)?;
+        validate_token_account(order_taker_ata, order_account_info.key, taker_mint.key)?;
+        validate_token_account(maker_taker_ata, &order.maker, taker_mint.key)?;
+
+        transfer_tokens(
+            token_program,
+            order_taker_ata,
+            taker_mint,
+            maker_taker_ata,
+            order_account_info,
+            order.reservation_bond,
+            &[&order.signer_seeds()],
+        )?;
+
+        clear_reservation(&mut order);
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Returns the bond of a reservation being filled by its holder, everyone else has
+    /// to wait for it to lapse and be forfeited
+    pub(crate) fn release_reservation<'a>(
+        order: &mut SwapOrder,
+        order_account_info: &AccountInfo<'a>,
+        order_taker_ata: &AccountInfo<'a>,
+        taker_info: &AccountInfo<'a>,
+        taker_ata: &AccountInfo<'a>,
+        taker_mint: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        validate_signer(taker_info)?;
+        if order.reserved_by != *taker_info.key {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+        if Clock::get()?.slot >= order.reserved_until_slot {
+            return Err(SwapError::OfferExpired.into());
+        }
+        validate_token_account(order_taker_ata, order_account_info.key, taker_mint.key)?;
+
+        transfer_tokens(
+            token_program,
+            order_taker_ata,
+            taker_mint,
+            taker_ata,
+            order_account_info,
+            order.reservation_bond,
+            &[&order.signer_seeds()],
+        )?;
+
+        clear_reservation(order);
+
+        Ok(())
+    }
+}
+
+fn clear_reservation(order: &mut SwapOrder) {
+    order.reserved_by = Pubkey::default();
+    order.reserved_until_slot = 0;
+    order.reservation_bond = 0;
+}
@@ -0,0 +1,329 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    math::sale::sale_cost,
+    state::{FillRecord, OrderKind, OrderStatus},
+    utils::{close_program_account, get_token_program, transfer_tokens, transfer_tokens_with_fee},
+    validation::{
+        get_fill_record_pda, validate_fill_record_pda, validate_mint, validate_order_kind,
+        validate_order_open, validate_order_pda, validate_order_started, validate_signer,
+        validate_system_program, validate_token_account, validate_token_program,
+        validate_treasury_pda,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn process_initialize_sale(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+        taker_amount: u64,
+        end_ts: i64,
+        wallet_cap: u64,
+        soft_cap: u64,
+        hard_cap: u64,
+    ) -> ProgramResult {
+        if end_ts <= Clock::get()?.unix_timestamp {
+            return Err(SwapError::OfferExpired.into());
+        }
+        let hard_cap = if hard_cap == 0 {
+            maker_amount
+        } else {
+            hard_cap
+        };
+        if hard_cap > maker_amount || soft_cap > hard_cap {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        let (mut order, order_account_info) =
+            Self::create_order(program_id, accounts, maker_amount, taker_amount)?;
+        order.kind = OrderKind::Sale;
+        order.end_ts = end_ts;
+        order.sale_wallet_cap = wallet_cap;
+        order.sale_soft_cap = soft_cap;
+        order.sale_hard_cap = hard_cap;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_buy_from_sale(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let buyer_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let fill_record_info = next_account_info(account_info_iter)?;
+        let buyer_taker_ata = next_account_info(account_info_iter)?;
+        let order_taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        validate_signer(buyer_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Sale)?;
+        validate_order_open(&order)?;
+        validate_order_started(&order)?;
+        if order.sale_ended(Clock::get()?.unix_timestamp) {
+            return Err(SwapError::OfferExpired.into());
+        }
+
+        let sold = order
+            .sale_sold
+            .checked_add(amount)
+            .ok_or(SwapError::Overflow)?;
+        if amount == 0 || sold > order.sale_hard_cap {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        validate_token_program(taker_mint, token_program.key)?;
+        validate_system_program(system_program_info.key)?;
+        validate_token_account(buyer_taker_ata, buyer_info.key, taker_mint.key)?;
+        validate_token_account(order_taker_ata, order_account_info.key, taker_mint.key)?;
+
+        // the first purchase opens the buyer's fill record
+        let mut fill_record = if fill_record_info.data_is_empty() {
+            let (fill_record_pda, bump) =
+                get_fill_record_pda(program_id, order_account_info.key, buyer_info.key)?;
+            if fill_record_pda != *fill_record_info.key {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            invoke_signed(
+                &system_instruction::create_account(
+                    buyer_info.key,
+                    fill_record_info.key,
+                    Rent::get()?.minimum_balance(FillRecord::LEN),
+                    FillRecord::LEN as u64,
+                    program_id,
+                ),
+                &[
+                    buyer_info.clone(),
+                    fill_record_info.clone(),
+                    system_program_info.clone(),
+                ],
+                &[&[
+                    b"fill_record",
+                    order_account_info.key.as_ref(),
+                    buyer_info.key.as_ref(),
+                    &[bump],
+                ]],
+            )?;
+            order.sale_open_records += 1;
+
+            FillRecord {
+                order: *order_account_info.key,
+                buyer: *buyer_info.key,
+                purchased: 0,
+                paid: 0,
+                bump,
+            }
+        } else {
+            validate_fill_record_pda(
+                program_id,
+                fill_record_info,
+                order_account_info.key,
+                buyer_info.key,
+            )?
+        };
+
+        let purchased = fill_record
+            .purchased
+            .checked_add(amount)
+            .ok_or(SwapError::Overflow)?;
+        if order.sale_wallet_cap != 0 && purchased > order.sale_wallet_cap {
+            return Err(SwapError::InvalidAmount.into());
+        }
+        let cost = sale_cost(amount, order.maker_amount, order.taker_amount)?;
+
+        transfer_tokens(
+            token_program,
+            buyer_taker_ata,
+            taker_mint,
+            order_taker_ata,
+            buyer_info,
+            cost,
+            &[],
+        )?;
+
+        fill_record.purchased = purchased;
+        fill_record.paid = fill_record
+            .paid
+            .checked_add(cost)
+            .ok_or(SwapError::Overflow)?;
+        order.sale_sold = sold;
+        order.sale_raised = order
+            .sale_raised
+            .checked_add(cost)
+            .ok_or(SwapError::Overflow)?;
+
+        fill_record.serialize(&mut *fill_record_info.data.borrow_mut())?;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_claim_sale(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let buyer_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let fill_record_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let buyer_maker_ata = next_account_info(account_info_iter)?;
+        let order_taker_ata = next_account_info(account_info_iter)?;
+        let buyer_taker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let token_program_2022 = next_account_info(account_info_iter)?;
+
+        validate_signer(buyer_info)?;
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Sale)?;
+        if !order.sale_ended(Clock::get()?.unix_timestamp) {
+            return Err(SwapError::AuctionNotEnded.into());
+        }
+        let fill_record = validate_fill_record_pda(
+            program_id,
+            fill_record_info,
+            order_account_info.key,
+            buyer_info.key,
+        )?;
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+
+        if order.sale_sold >= order.sale_soft_cap {
+            validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+            validate_token_account(buyer_maker_ata, buyer_info.key, maker_mint.key)?;
+            transfer_tokens(
+                get_token_program(maker_mint, token_program, token_program_2022)?,
+                order_maker_ata,
+                maker_mint,
+                buyer_maker_ata,
+                order_account_info,
+                fill_record.purchased,
+                &[&order.signer_seeds()],
+            )?;
+        } else {
+            validate_token_account(order_taker_ata, order_account_info.key, taker_mint.key)?;
+            validate_token_account(buyer_taker_ata, buyer_info.key, taker_mint.key)?;
+            transfer_tokens(
+                get_token_program(taker_mint, token_program, token_program_2022)?,
+                order_taker_ata,
+                taker_mint,
+                buyer_taker_ata,
+                order_account_info,
+                fill_record.paid,
+                &[&order.signer_seeds()],
+            )?;
+        }
+
+        order.sale_open_records -= 1;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        close_program_account(fill_record_info, buyer_info)?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_finalize_sale(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let maker_maker_ata = next_account_info(account_info_iter)?;
+        let order_taker_ata = next_account_info(account_info_iter)?;
+        let maker_taker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_taker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let token_program_2022 = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::Sale)?;
+        validate_order_open(&order)?;
+        if !order.sale_ended(Clock::get()?.unix_timestamp) {
+            return Err(SwapError::AuctionNotEnded.into());
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+        validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+        validate_token_account(maker_maker_ata, &order.maker, maker_mint.key)?;
+        let maker_token_program = get_token_program(maker_mint, token_program, token_program_2022)?;
+
+        // a failed sale keeps the payments for the buyers' refunds
+        let succeeded = order.sale_sold >= order.sale_soft_cap;
+        let unsold = if succeeded {
+            order.maker_amount - order.sale_sold
+        } else {
+            order.maker_amount
+        };
+
+        transfer_tokens(
+            maker_token_program,
+            order_maker_ata,
+            maker_mint,
+            maker_maker_ata,
+            order_account_info,
+            unsold,
+            &[&order.signer_seeds()],
+        )?;
+
+        if succeeded {
+            validate_token_account(order_taker_ata, order_account_info.key, taker_mint.key)?;
+            validate_token_account(maker_taker_ata, &order.maker, taker_mint.key)?;
+            validate_token_account(
+                treasury_taker_ata,
+                treasury_account_info.key,
+                taker_mint.key,
+            )?;
+            transfer_tokens_with_fee(
+                get_token_program(taker_mint, token_program, token_program_2022)?,
+                order_taker_ata,
+                taker_mint,
+                maker_taker_ata,
+                treasury_taker_ata,
+                order_account_info,
+                order.sale_raised,
+                treasury.fee,
+                &[&order.signer_seeds()],
+            )?;
+        }
+
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
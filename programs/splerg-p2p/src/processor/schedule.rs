@@ -0,0 +1,57 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    state::OrderKind,
+    validation::{validate_authority, validate_order_open, validate_order_pda},
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_set_order_start(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        start_ts: i64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_open(&order)?;
+        // auctions run on their own clock
+        if !matches!(
+            order.kind,
+            OrderKind::Fixed | OrderKind::Twap | OrderKind::Sale
+        ) {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        if order.start_ts != 0 && order.start_ts <= now {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+        if start_ts != 0 && start_ts <= now {
+            return Err(SwapError::InvalidAmount.into());
+        }
+        if order.end_ts != 0 && start_ts >= order.end_ts {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        order.start_ts = start_ts;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
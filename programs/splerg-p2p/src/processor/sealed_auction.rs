@@ -0,0 +1,450 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    math::fee::calculate_token_fee,
+    state::{OrderKind, OrderStatus, SealedBid},
+    utils::{
+        close_program_account, get_token_balance, get_token_program, transfer_tokens,
+        transfer_tokens_with_fee,
+    },
+    validation::{
+        get_sealed_bid_pda, validate_authority, validate_mint, validate_order_kind,
+        validate_order_open, validate_order_pda, validate_rent_sysvar, validate_sealed_bid_pda,
+        validate_signer, validate_system_program, validate_token_account, validate_token_program,
+        validate_treasury_pda,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_initialize_sealed_auction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+        reserve_price: u64,
+        commit_end_ts: i64,
+        reveal_end_ts: i64,
+        penalty_bps: u16,
+    ) -> ProgramResult {
+        if commit_end_ts <= Clock::get()?.unix_timestamp {
+            return Err(SwapError::AuctionEnded.into());
+        }
+        if reveal_end_ts <= commit_end_ts || penalty_bps > 10_000 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let (mut order, order_account_info) =
+            Self::create_order(program_id, accounts, maker_amount, reserve_price)?;
+
+        order.kind = OrderKind::SealedAuction;
+        order.end_ts = commit_end_ts;
+        order.reveal_end_ts = reveal_end_ts;
+        order.penalty_bps = penalty_bps;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_commit_bid(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        commitment: [u8; 32],
+        deposit: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let bidder_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let sealed_bid_info = next_account_info(account_info_iter)?;
+        let bid_escrow = next_account_info(account_info_iter)?;
+        let bidder_taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(bidder_info)?;
+
+        let (order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::SealedAuction)?;
+        validate_order_open(&order)?;
+
+        if Clock::get()?.unix_timestamp >= order.end_ts {
+            return Err(SwapError::AuctionEnded.into());
+        }
+        if deposit == 0 {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        validate_token_program(taker_mint, token_program.key)?;
+        validate_system_program(system_program_info.key)?;
+        validate_rent_sysvar(rent_info.key)?;
+        validate_token_account(bidder_taker_ata, bidder_info.key, &order.taker_token_mint)?;
+
+        let (sealed_bid_pda, bump) =
+            get_sealed_bid_pda(program_id, order_account_info.key, bidder_info.key)?;
+        if sealed_bid_pda != *sealed_bid_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        validate_token_account(bid_escrow, &sealed_bid_pda, &order.taker_token_mint)?;
+
+        let rent = Rent::from_account_info(rent_info)?;
+        invoke_signed(
+            &system_instruction::create_account(
+                bidder_info.key,
+                sealed_bid_info.key,
+                rent.minimum_balance(SealedBid::LEN),
+                SealedBid::LEN as u64,
+                program_id,
+            ),
+            &[
+                bidder_info.clone(),
+                sealed_bid_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[
+                b"sealed_bid",
+                order_account_info.key.as_ref(),
+                bidder_info.key.as_ref(),
+                &[bump],
+            ]],
+        )?;
+
+        transfer_tokens(
+            token_program,
+            bidder_taker_ata,
+            taker_mint,
+            bid_escrow,
+            bidder_info,
+            deposit,
+            &[],
+        )?;
+
+        let penalty = calculate_token_fee(deposit.into(), order.penalty_bps)?
+            .try_into()
+            .map_err(|_| SwapError::Overflow)?;
+
+        let sealed_bid = SealedBid {
+            order: *order_account_info.key,
+            bidder: *bidder_info.key,
+            maker: order.maker,
+            commitment,
+            deposit,
+            penalty,
+            reveal_end_ts: order.reveal_end_ts,
+            amount: 0,
+            revealed: false,
+            bump,
+        };
+        sealed_bid.serialize(&mut *sealed_bid_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_reveal_bid(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+        salt: [u8; 32],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let bidder_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let sealed_bid_info = next_account_info(account_info_iter)?;
+
+        validate_signer(bidder_info)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::SealedAuction)?;
+        validate_order_open(&order)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        if now < order.end_ts || now >= order.reveal_end_ts {
+            return Err(SwapError::RevealNotOpen.into());
+        }
+
+        let mut sealed_bid =
+            validate_sealed_bid_pda(program_id, sealed_bid_info, order_account_info.key)?;
+        if sealed_bid.bidder != *bidder_info.key {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+        if sealed_bid.revealed {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+        if SealedBid::commitment(amount, &salt, bidder_info.key) != sealed_bid.commitment {
+            return Err(SwapError::InvalidCommitment.into());
+        }
+        if amount > sealed_bid.deposit {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        sealed_bid.revealed = true;
+        sealed_bid.amount = amount;
+        sealed_bid.serialize(&mut *sealed_bid_info.data.borrow_mut())?;
+
+        // the reserve has to be met, after that a reveal has to beat the leading bid
+        let leads = if order.taker == Pubkey::default() {
+            amount >= order.taker_amount
+        } else {
+            amount > order.taker_amount
+        };
+        if leads {
+            order.taker = *bidder_info.key;
+            order.taker_amount = amount;
+            order.serialize(&mut *order_account_info.data.borrow_mut())?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn process_settle_sealed_auction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let order_account_info = next_account_info(account_info_iter)?;
+        let sealed_bid_info = next_account_info(account_info_iter)?;
+        let bid_escrow = next_account_info(account_info_iter)?;
+        let bidder_info = next_account_info(account_info_iter)?;
+        let maker_taker_ata = next_account_info(account_info_iter)?;
+        let bidder_taker_ata = next_account_info(account_info_iter)?;
+        let bidder_maker_ata = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_taker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let token_program_2022 = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_order_kind(&order, OrderKind::SealedAuction)?;
+        validate_order_open(&order)?;
+
+        if Clock::get()?.unix_timestamp < order.reveal_end_ts {
+            return Err(SwapError::AuctionNotEnded.into());
+        }
+        if order.taker == Pubkey::default() {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        let sealed_bid =
+            validate_sealed_bid_pda(program_id, sealed_bid_info, order_account_info.key)?;
+        if sealed_bid.bidder != order.taker || sealed_bid.bidder != *bidder_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_mint(taker_mint, &order.taker_token_mint)?;
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+
+        validate_token_account(maker_taker_ata, &order.maker, &order.taker_token_mint)?;
+        validate_token_account(
+            bidder_taker_ata,
+            &sealed_bid.bidder,
+            &order.taker_token_mint,
+        )?;
+        validate_token_account(
+            bidder_maker_ata,
+            &sealed_bid.bidder,
+            &order.maker_token_mint,
+        )?;
+        validate_token_account(
+            order_maker_ata,
+            order_account_info.key,
+            &order.maker_token_mint,
+        )?;
+        validate_token_account(bid_escrow, sealed_bid_info.key, &order.taker_token_mint)?;
+        validate_token_account(
+            treasury_maker_ata,
+            treasury_account_info.key,
+            &order.maker_token_mint,
+        )?;
+        validate_token_account(
+            treasury_taker_ata,
+            treasury_account_info.key,
+            &order.taker_token_mint,
+        )?;
+
+        let maker_token_program = get_token_program(maker_mint, token_program, token_program_2022)?;
+        let taker_token_program = get_token_program(taker_mint, token_program, token_program_2022)?;
+
+        if get_token_balance(order_maker_ata)? < order.maker_amount {
+            return Err(SwapError::InsufficientFunds.into());
+        }
+
+        // winning bid -> maker, the rest of the deposit back to the bidder
+        let bid_seeds = sealed_bid.signer_seeds();
+        transfer_tokens_with_fee(
+            taker_token_program,
+            bid_escrow,
+            taker_mint,
+            maker_taker_ata,
+            treasury_taker_ata,
+            sealed_bid_info,
+            sealed_bid.amount,
+            treasury.fee,
+            &[&bid_seeds],
+        )?;
+        transfer_tokens(
+            taker_token_program,
+            bid_escrow,
+            taker_mint,
+            bidder_taker_ata,
+            sealed_bid_info,
+            sealed_bid.deposit - sealed_bid.amount,
+            &[&bid_seeds],
+        )?;
+
+        // maker escrow -> winning bidder
+        transfer_tokens_with_fee(
+            maker_token_program,
+            order_maker_ata,
+            maker_mint,
+            bidder_maker_ata,
+            treasury_maker_ata,
+            order_account_info,
+            order.maker_amount,
+            treasury.fee,
+            &[&order.signer_seeds()],
+        )?;
+
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        // Return the sealed bid rent to the winner
+        close_program_account(sealed_bid_info, bidder_info)
+    }
+
+    pub(crate) fn process_refund_sealed_bid(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let bidder_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let sealed_bid_info = next_account_info(account_info_iter)?;
+        let bid_escrow = next_account_info(account_info_iter)?;
+        let bidder_taker_ata = next_account_info(account_info_iter)?;
+        let maker_taker_ata = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        let sealed_bid =
+            validate_sealed_bid_pda(program_id, sealed_bid_info, order_account_info.key)?;
+        if sealed_bid.bidder != *bidder_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if Clock::get()?.unix_timestamp < sealed_bid.reveal_end_ts {
+            return Err(SwapError::AuctionNotEnded.into());
+        }
+
+        // The leading bid is paid out by settlement, not refunded. A closed order
+        // was either settled or never had a leader.
+        if order_account_info.owner == program_id && !order_account_info.data_is_empty() {
+            let (order, _) = validate_order_pda(program_id, order_account_info)?;
+            if order.status == OrderStatus::Open && order.taker == sealed_bid.bidder {
+                return Err(SwapError::InvalidOrderState.into());
+            }
+        }
+
+        validate_token_program(taker_mint, token_program.key)?;
+        validate_token_account(bid_escrow, sealed_bid_info.key, taker_mint.key)?;
+        validate_token_account(bidder_taker_ata, &sealed_bid.bidder, taker_mint.key)?;
+        validate_token_account(maker_taker_ata, &sealed_bid.maker, taker_mint.key)?;
+
+        let penalty = if sealed_bid.revealed {
+            0
+        } else {
+            sealed_bid.penalty
+        };
+
+        let bid_seeds = sealed_bid.signer_seeds();
+        transfer_tokens(
+            token_program,
+            bid_escrow,
+            taker_mint,
+            maker_taker_ata,
+            sealed_bid_info,
+            penalty,
+            &[&bid_seeds],
+        )?;
+        transfer_tokens(
+            token_program,
+            bid_escrow,
+            taker_mint,
+            bidder_taker_ata,
+            sealed_bid_info,
+            sealed_bid.deposit - penalty,
+            &[&bid_seeds],
+        )?;
+
+        // Return the sealed bid rent to the bidder
+        close_program_account(sealed_bid_info, bidder_info)
+    }
+
+    /// Returns the lot of a sealed auction that ended without a bid meeting the reserve
+    pub(crate) fn process_reclaim_sealed_auction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let order_maker_ata = next_account_info(account_info_iter)?;
+        let maker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_kind(&order, OrderKind::SealedAuction)?;
+        validate_order_open(&order)?;
+        if Clock::get()?.unix_timestamp < order.reveal_end_ts {
+            return Err(SwapError::AuctionNotEnded.into());
+        }
+        // A leading bid is paid out by settlement
+        if order.taker != Pubkey::default() {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        validate_mint(maker_mint, &order.maker_token_mint)?;
+        validate_token_program(maker_mint, token_program.key)?;
+        validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+        validate_token_account(maker_ata, &order.maker, maker_mint.key)?;
+
+        transfer_tokens(
+            token_program,
+            order_maker_ata,
+            maker_mint,
+            maker_ata,
+            order_account_info,
+            get_token_balance(order_maker_ata)?,
+            &[&order.signer_seeds()],
+        )?;
+
+        // the order stays around as a record, the maker can close it now
+        order.maker_amount = 0;
+        order.status = OrderStatus::Filled;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
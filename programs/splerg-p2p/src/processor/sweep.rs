@@ -0,0 +1,190 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    math::{fee::split_token_fee, matching::fill_for_budget},
+    state::OrderKind,
+    utils::{get_token_balance, get_token_program, transfer_tokens},
+    validation::{
+        validate_mint, validate_no_taker_deposit, validate_order_kind, validate_order_nonrecurring,
+        validate_order_open, validate_order_pda, validate_order_started, validate_order_unchained,
+        validate_order_unexpired, validate_order_ungrouped, validate_order_unpegged,
+        validate_order_unregistered, validate_order_unreserved, validate_order_untokenized,
+        validate_order_untriggered, validate_order_unvested, validate_signer, validate_taker,
+        validate_token_account, validate_treasury_pda,
+    },
+};
+
+use super::{matching::apply_fill, Processor};
+
+/// Accounts passed per order to `SweepOrders`
+const SWEEP_ORDER_ACCOUNTS: usize = 3;
+
+impl Processor {
+    pub(crate) fn process_sweep_orders(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        max_taker_amount: u64,
+        min_maker_amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let taker_info = next_account_info(account_info_iter)?;
+        let taker_ata = next_account_info(account_info_iter)?;
+        let taker_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+        let treasury_maker_ata = next_account_info(account_info_iter)?;
+        let treasury_taker_ata = next_account_info(account_info_iter)?;
+        let maker_mint = next_account_info(account_info_iter)?;
+        let taker_mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        let token_program_2022 = next_account_info(account_info_iter)?;
+        let order_accounts = account_info_iter.as_slice();
+
+        validate_signer(taker_info)?;
+
+        let order_groups = order_accounts.chunks_exact(SWEEP_ORDER_ACCOUNTS);
+        if !order_groups.remainder().is_empty() || order_groups.len() == 0 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        let treasury = validate_treasury_pda(program_id, treasury_account_info)?;
+
+        validate_token_account(taker_ata, taker_info.key, taker_mint.key)?;
+        validate_token_account(taker_maker_ata, taker_info.key, maker_mint.key)?;
+        validate_token_account(
+            treasury_maker_ata,
+            treasury_account_info.key,
+            maker_mint.key,
+        )?;
+        validate_token_account(
+            treasury_taker_ata,
+            treasury_account_info.key,
+            taker_mint.key,
+        )?;
+
+        let maker_token_program = get_token_program(maker_mint, token_program, token_program_2022)?;
+        let taker_token_program = get_token_program(taker_mint, token_program, token_program_2022)?;
+
+        let mut remaining_budget = max_taker_amount;
+        let mut maker_received: u64 = 0;
+        let mut maker_fees: u64 = 0;
+        let mut taker_fees: u64 = 0;
+
+        for (i, group) in order_groups.enumerate() {
+            let [order_account_info, order_maker_ata, maker_taker_ata] = group else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            if order_accounts[..i * SWEEP_ORDER_ACCOUNTS]
+                .iter()
+                .step_by(SWEEP_ORDER_ACCOUNTS)
+                .any(|other| other.key == order_account_info.key)
+            {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+            validate_order_kind(&order, OrderKind::Fixed)?;
+            validate_order_open(&order)?;
+            validate_order_ungrouped(&order)?;
+            validate_order_nonrecurring(&order)?;
+            validate_order_unchained(&order)?;
+            validate_order_unvested(&order)?;
+            validate_order_started(&order)?;
+            validate_order_unexpired(&order)?;
+            validate_order_unpegged(&order)?;
+            validate_order_untriggered(&order)?;
+            validate_order_unreserved(&order)?;
+            validate_order_untokenized(&order)?;
+            validate_order_unregistered(&order)?;
+            validate_no_taker_deposit(&order)?;
+            validate_taker(taker_info, &order)?;
+            validate_mint(maker_mint, &order.maker_token_mint)?;
+            validate_mint(taker_mint, &order.taker_token_mint)?;
+            validate_token_account(order_maker_ata, order_account_info.key, maker_mint.key)?;
+            validate_token_account(maker_taker_ata, &order.maker, taker_mint.key)?;
+
+            if get_token_balance(order_maker_ata)? < order.maker_amount {
+                return Err(SwapError::InsufficientFunds.into());
+            }
+
+            let (maker_filled, taker_filled) =
+                fill_for_budget(order.maker_amount, order.taker_amount, remaining_budget)?;
+            if maker_filled == 0 {
+                break;
+            }
+            remaining_budget -= taker_filled;
+
+            let (maker_after_fee, maker_fee) = split_token_fee(maker_filled, treasury.fee)?;
+            let (taker_after_fee, taker_fee) = split_token_fee(taker_filled, treasury.fee)?;
+
+            // taker -> maker, fees are collected once after the sweep
+            transfer_tokens(
+                taker_token_program,
+                taker_ata,
+                taker_mint,
+                maker_taker_ata,
+                taker_info,
+                taker_after_fee,
+                &[],
+            )?;
+
+            transfer_tokens(
+                maker_token_program,
+                order_maker_ata,
+                maker_mint,
+                taker_maker_ata,
+                order_account_info,
+                maker_filled,
+                &[&order.signer_seeds()],
+            )?;
+
+            maker_received = maker_received
+                .checked_add(maker_after_fee)
+                .ok_or(SwapError::Overflow)?;
+            maker_fees = maker_fees
+                .checked_add(maker_fee)
+                .ok_or(SwapError::Overflow)?;
+            taker_fees = taker_fees
+                .checked_add(taker_fee)
+                .ok_or(SwapError::Overflow)?;
+
+            apply_fill(&mut order, maker_filled, taker_filled)?;
+            order.serialize(&mut *order_account_info.data.borrow_mut())?;
+        }
+
+        if maker_received < min_maker_amount {
+            return Err(SwapError::LimitPriceExceeded.into());
+        }
+
+        transfer_tokens(
+            taker_token_program,
+            taker_ata,
+            taker_mint,
+            treasury_taker_ata,
+            taker_info,
+            taker_fees,
+            &[],
+        )?;
+
+        transfer_tokens(
+            maker_token_program,
+            taker_maker_ata,
+            maker_mint,
+            treasury_maker_ata,
+            taker_info,
+            maker_fees,
+            &[],
+        )?;
+
+        Ok(())
+    }
+}
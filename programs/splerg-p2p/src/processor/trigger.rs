@@ -0,0 +1,90 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    oracle::{load_price, read_price},
+    state::{OrderKind, SwapOrder, TriggerCondition},
+    validation::{
+        validate_authority, validate_order_kind, validate_order_open, validate_order_pda,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_set_order_trigger(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        condition: TriggerCondition,
+        trigger_price: i64,
+        max_age: i64,
+        max_conf_bps: u16,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+        let oracle_info = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_kind(&order, OrderKind::Fixed)?;
+        validate_order_open(&order)?;
+        // settlement of deposits doesn't read the oracle
+        if order.deposit_end_ts != 0 {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+
+        let price = read_price(oracle_info)?;
+        if trigger_price <= 0 || max_age < 0 {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        order.trigger_oracle = *oracle_info.key;
+        order.trigger_condition = condition;
+        order.trigger_price = trigger_price;
+        order.trigger_expo = price.expo;
+        order.trigger_max_age = max_age;
+        order.trigger_max_conf_bps = max_conf_bps;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Fails unless the trigger oracle's current price meets the order's trigger
+    pub(crate) fn validate_order_triggered(
+        order: &SwapOrder,
+        oracle_info: &AccountInfo,
+    ) -> ProgramResult {
+        if *oracle_info.key != order.trigger_oracle {
+            return Err(SwapError::InvalidOracle.into());
+        }
+
+        let price = load_price(
+            oracle_info,
+            Clock::get()?.unix_timestamp,
+            order.trigger_max_age,
+            order.trigger_max_conf_bps,
+        )?;
+        // the threshold is only comparable at the exponent it was set with
+        if price.expo != order.trigger_expo {
+            return Err(SwapError::InvalidOracle.into());
+        }
+        if !order
+            .trigger_condition
+            .is_met(price.price, order.trigger_price)
+        {
+            return Err(SwapError::TriggerNotMet.into());
+        }
+
+        Ok(())
+    }
+}
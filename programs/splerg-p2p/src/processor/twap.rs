@@ -0,0 +1,94 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+        program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    math::twap::unlocked_amount,
+    state::{OrderKind, SwapOrder},
+};
+
+use super::{matching::apply_fill, Processor};
+
+/// Number of accounts shared with `InitializeOrder`
+const INITIALIZE_ORDER_ACCOUNTS: usize = 10;
+
+impl Processor {
+    pub(crate) fn process_initialize_twap_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        maker_amount: u64,
+        taker_amount: u64,
+        interval: i64,
+        slice: u64,
+    ) -> ProgramResult {
+        if accounts.len() < INITIALIZE_ORDER_ACCOUNTS {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        if interval <= 0 || slice == 0 || slice > maker_amount {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        let (mut order, order_account_info) = Self::create_order(
+            program_id,
+            &accounts[..INITIALIZE_ORDER_ACCOUNTS],
+            maker_amount,
+            taker_amount,
+        )?;
+
+        order.kind = OrderKind::Twap;
+        order.twap_start_ts = Clock::get()?.unix_timestamp;
+        order.twap_interval = interval;
+        order.twap_slice = slice;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Maker and taker amounts a `CompleteSwap` fills right now, capped at the
+    /// released part of a TWAP schedule and priced at the order's rate
+    pub(crate) fn twap_fill_amounts(order: &SwapOrder) -> Result<(u64, u64), ProgramError> {
+        let unlocked = unlocked_amount(
+            order.twap_start_ts,
+            order.twap_interval,
+            order.twap_slice,
+            Clock::get()?.unix_timestamp,
+        )?;
+        let available = unlocked.saturating_sub(order.twap_filled);
+        if available == 0 {
+            return Err(SwapError::NothingUnlocked.into());
+        }
+        if available >= order.maker_amount {
+            return Ok((order.maker_amount, order.taker_amount));
+        }
+
+        // rounds up so the remainder is never priced below the order's rate
+        let taker_filled = (available as u128)
+            .checked_mul(order.taker_amount.into())
+            .ok_or(SwapError::Overflow)?
+            .div_ceil(order.maker_amount.into());
+
+        Ok((
+            available,
+            taker_filled.try_into().map_err(|_| SwapError::Overflow)?,
+        ))
+    }
+
+    /// Records a TWAP fill against the schedule, the order stays open until drained
+    pub(crate) fn apply_twap_fill(
+        order: &mut SwapOrder,
+        maker_filled: u64,
+        taker_filled: u64,
+    ) -> ProgramResult {
+        apply_fill(order, maker_filled, taker_filled)?;
+        order.twap_filled = order
+            .twap_filled
+            .checked_add(maker_filled)
+            .ok_or(SwapError::Overflow)?;
+        Ok(())
+    }
+}
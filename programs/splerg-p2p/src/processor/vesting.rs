@@ -0,0 +1,170 @@
+use {
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::rent::Rent,
+        sysvar::Sysvar,
+    },
+};
+
+use crate::{
+    error::SwapError,
+    math::vesting::vested_amount,
+    state::{OrderKind, SwapOrder, Vesting},
+    utils::{close_program_account, transfer_tokens},
+    validation::{
+        get_vesting_pda, validate_authority, validate_mint, validate_order_kind,
+        validate_order_open, validate_order_pda, validate_signer, validate_system_program,
+        validate_token_account, validate_token_program, validate_vesting_pda,
+    },
+};
+
+use super::Processor;
+
+impl Processor {
+    pub(crate) fn process_set_vesting_schedule(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        cliff: i64,
+        duration: i64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker_info = next_account_info(account_info_iter)?;
+        let order_account_info = next_account_info(account_info_iter)?;
+
+        let (mut order, _) = validate_order_pda(program_id, order_account_info)?;
+        validate_authority(maker_info, &order)?;
+        validate_order_kind(&order, OrderKind::Fixed)?;
+        validate_order_open(&order)?;
+
+        // the vesting PDA is per order, so the order must fill exactly once
+        if order.funding_account != Pubkey::default() {
+            return Err(SwapError::InvalidOrderState.into());
+        }
+        if duration < 0 || cliff < 0 || cliff > duration {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        order.vesting_cliff = cliff;
+        order.vesting_duration = duration;
+        order.serialize(&mut *order_account_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Opens the taker's vesting schedule over `amount` maker tokens already sitting
+    /// in the vesting PDA's escrow, starting at the fill
+    pub(crate) fn create_vesting<'a>(
+        program_id: &Pubkey,
+        order_account_info: &AccountInfo<'a>,
+        order: &SwapOrder,
+        taker_info: &AccountInfo<'a>,
+        vesting_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        validate_system_program(system_program_info.key)?;
+
+        let (vesting_pda, bump) = get_vesting_pda(program_id, order_account_info.key)?;
+        if vesting_pda != *vesting_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let start_ts = Clock::get()?.unix_timestamp;
+        let vesting = Vesting {
+            order: *order_account_info.key,
+            beneficiary: *taker_info.key,
+            mint: order.maker_token_mint,
+            total_amount: amount,
+            claimed_amount: 0,
+            start_ts,
+            cliff_ts: start_ts
+                .checked_add(order.vesting_cliff)
+                .ok_or(SwapError::Overflow)?,
+            end_ts: start_ts
+                .checked_add(order.vesting_duration)
+                .ok_or(SwapError::Overflow)?,
+            bump,
+        };
+
+        invoke_signed(
+            &system_instruction::create_account(
+                taker_info.key,
+                vesting_info.key,
+                Rent::get()?.minimum_balance(Vesting::LEN),
+                Vesting::LEN as u64,
+                program_id,
+            ),
+            &[
+                taker_info.clone(),
+                vesting_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&vesting.signer_seeds()],
+        )?;
+
+        vesting.serialize(&mut *vesting_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub(crate) fn process_claim_vested(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let beneficiary_info = next_account_info(account_info_iter)?;
+        let vesting_info = next_account_info(account_info_iter)?;
+        let vesting_ata = next_account_info(account_info_iter)?;
+        let beneficiary_ata = next_account_info(account_info_iter)?;
+        let mint = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+
+        validate_signer(beneficiary_info)?;
+        let mut vesting = validate_vesting_pda(program_id, vesting_info)?;
+        if vesting.beneficiary != *beneficiary_info.key {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
+        validate_mint(mint, &vesting.mint)?;
+        validate_token_program(mint, token_program.key)?;
+        validate_token_account(vesting_ata, vesting_info.key, mint.key)?;
+        validate_token_account(beneficiary_ata, beneficiary_info.key, mint.key)?;
+
+        let vested = vested_amount(
+            vesting.total_amount,
+            vesting.start_ts,
+            vesting.cliff_ts,
+            vesting.end_ts,
+            Clock::get()?.unix_timestamp,
+        )?;
+        let claimable = vested.saturating_sub(vesting.claimed_amount);
+        if claimable == 0 {
+            return Err(SwapError::NothingUnlocked.into());
+        }
+
+        transfer_tokens(
+            token_program,
+            vesting_ata,
+            mint,
+            beneficiary_ata,
+            vesting_info,
+            claimable,
+            &[&vesting.signer_seeds()],
+        )?;
+
+        vesting.claimed_amount += claimable;
+        if vesting.claimed_amount == vesting.total_amount {
+            close_program_account(vesting_info, beneficiary_info)?;
+        } else {
+            vesting.serialize(&mut *vesting_info.data.borrow_mut())?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,60 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
-use solana_program::pubkey::Pubkey;
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderKind {
+    /// Fixed price order filled through `CompleteSwap`
+    Fixed,
+    /// English auction, `taker_amount` is the reserve price
+    Auction,
+    /// Sealed-bid auction, `taker_amount` is the reserve until the first valid reveal
+    /// and the leading bid afterwards
+    SealedAuction,
+    /// Fixed price order whose escrow is released to takers in `twap_slice` steps
+    Twap,
+    /// Escrow released to the named taker in milestone tranches on an arbiter's approval.
+    /// The consideration is settled off-chain, `taker_amount` is always 0
+    Arbitrated,
+    /// Escrow released to the named taker on the preimage of `hashlock`, refundable to
+    /// the maker from `end_ts`. `taker_amount` is the leg settled on the other chain
+    Htlc,
+    /// Call option over the escrow: the holder in `taker` can fill at `taker_amount`
+    /// until `end_ts` after buying the right for `option_premium`
+    CoveredCall,
+    /// Payment request: the named taker pays `taker_amount`, `maker_amount` is 0 and
+    /// nothing is escrowed
+    Invoice,
+    /// Escrow claimable by the named taker for nothing, `taker_amount` is 0
+    Drop,
+    /// Token sale of the escrow at the `maker_amount` to `taker_amount` price, settled
+    /// per buyer from their `FillRecord` once it ends at `end_ts` or sells out
+    Sale,
+}
+
+/// Side of the threshold the trigger oracle's price has to be on for a fill
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerCondition {
+    /// Fillable while the price is at or above the threshold
+    Above,
+    /// Fillable while the price is at or below the threshold
+    Below,
+}
+
+impl TriggerCondition {
+    pub fn is_met(&self, price: i64, threshold: i64) -> bool {
+        match self {
+            TriggerCondition::Above => price >= threshold,
+            TriggerCondition::Below => price <= threshold,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStatus {
+    Open,
+    Filled,
+}
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount, PartialEq)]
 pub struct SwapOrder {
@@ -12,6 +66,89 @@ pub struct SwapOrder {
     pub maker_amount: u64,
     pub taker_amount: u64,
     pub bump: u8,
+    pub kind: OrderKind,
+    pub status: OrderStatus,
+    pub end_ts: i64,
+    pub reveal_end_ts: i64,
+    pub penalty_bps: u16,
+    /// Maker token account the order PDA is delegated to refill from, default if not recurring
+    pub funding_account: Pubkey,
+    pub refills_remaining: u64,
+    /// Maker tokens that may still be re-escrowed by refills
+    pub refill_cap: u64,
+    /// Start of the TWAP schedule, the first slice is fillable from here
+    pub twap_start_ts: i64,
+    pub twap_interval: i64,
+    /// Maker tokens released every `twap_interval` seconds
+    pub twap_slice: u64,
+    /// Maker tokens already filled against the schedule
+    pub twap_filled: u64,
+    /// One-cancels-other group the order belongs to, default if ungrouped
+    pub group: Pubkey,
+    /// Mint the proceeds are re-listed for on fill, default if the order isn't chained
+    pub follow_up_taker_mint: Pubkey,
+    pub follow_up_taker_amount: u64,
+    /// Expiry of the re-listed order, 0 if it doesn't expire
+    pub follow_up_end_ts: i64,
+    /// Seconds after the fill before the taker can claim anything
+    pub vesting_cliff: i64,
+    /// Seconds over which the maker tokens vest linearly after the fill, 0 delivers them
+    pub vesting_duration: i64,
+    /// End of the window for the taker to deposit ahead of settlement, 0 if not two-sided
+    pub deposit_end_ts: i64,
+    /// Whether the taker's `taker_amount` sits in the order PDA's taker mint ATA
+    pub taker_deposited: bool,
+    /// Price account the taker amount is derived from at fill time, default if not pegged
+    pub oracle: Pubkey,
+    /// Premium over the oracle price, a discount if negative
+    pub oracle_premium_bps: i16,
+    /// Oldest oracle publish time accepted, in seconds before the fill
+    pub oracle_max_age: i64,
+    /// Widest oracle confidence interval accepted, relative to the price
+    pub oracle_max_conf_bps: u16,
+    /// Price account gating fills, default if the order has no trigger
+    pub trigger_oracle: Pubkey,
+    pub trigger_condition: TriggerCondition,
+    /// Threshold in the trigger oracle's units, `trigger_price * 10^trigger_expo`
+    pub trigger_price: i64,
+    /// Exponent of the trigger oracle when the trigger was set
+    pub trigger_expo: i32,
+    pub trigger_max_age: i64,
+    pub trigger_max_conf_bps: u16,
+    /// Fills are rejected before this time, 0 if the order is fillable right away
+    pub start_ts: i64,
+    /// SHA-256 hash whose preimage releases an HTLC order's escrow
+    pub hashlock: [u8; 32],
+    /// Hash of the secret a private order's taker has to present, zero if public
+    pub claim_code_hash: [u8; 32],
+    /// Taker holding a reservation, default if the order isn't reserved
+    pub reserved_by: Pubkey,
+    /// First slot the reservation no longer covers
+    pub reserved_until_slot: u64,
+    /// Taker tokens bonded for the reservation in the order PDA's taker mint ATA
+    pub reservation_bond: u64,
+    /// Taker tokens paid to the maker for a covered call's option right
+    pub option_premium: u64,
+    /// Maker tokens a single wallet can buy from a sale, 0 if uncapped
+    pub sale_wallet_cap: u64,
+    /// Maker tokens that have to sell for the sale to go through, buyers are refunded
+    /// otherwise
+    pub sale_soft_cap: u64,
+    /// Maker tokens on offer, at most `maker_amount`
+    pub sale_hard_cap: u64,
+    pub sale_sold: u64,
+    /// Taker tokens paid into the order PDA's taker mint ATA
+    pub sale_raised: u64,
+    /// Fill records that haven't been claimed yet
+    pub sale_open_records: u64,
+    /// Token-2022 mint whose single token carries the maker's rights, or default
+    /// when the rights stay with `maker`
+    pub position_mint: Pubkey,
+    /// Maker registry counting the order, default if it wasn't created through one
+    pub registry: Pubkey,
+    /// Layout of the account, orders created before layouts were versioned go through
+    /// `MigrateOrder` first
+    pub version: u8,
 }
 
 impl SwapOrder {
@@ -22,7 +159,61 @@ impl SwapOrder {
         32 + // taker_token_mint
         8 + // maker_amount
         8 + // taker_amount
-        1; // bump
+        1 + // bump
+        1 + // kind
+        1 + // status
+        8 + // end_ts
+        8 + // reveal_end_ts
+        2 + // penalty_bps
+        32 + // funding_account
+        8 + // refills_remaining
+        8 + // refill_cap
+        8 + // twap_start_ts
+        8 + // twap_interval
+        8 + // twap_slice
+        8 + // twap_filled
+        32 + // group
+        32 + // follow_up_taker_mint
+        8 + // follow_up_taker_amount
+        8 + // follow_up_end_ts
+        8 + // vesting_cliff
+        8 + // vesting_duration
+        8 + // deposit_end_ts
+        1 + // taker_deposited
+        32 + // oracle
+        2 + // oracle_premium_bps
+        8 + // oracle_max_age
+        2 + // oracle_max_conf_bps
+        32 + // trigger_oracle
+        1 + // trigger_condition
+        8 + // trigger_price
+        4 + // trigger_expo
+        8 + // trigger_max_age
+        2 + // trigger_max_conf_bps
+        8 + // start_ts
+        32 + // hashlock
+        32 + // claim_code_hash
+        32 + // reserved_by
+        8 + // reserved_until_slot
+        8 + // reservation_bond
+        8 + // option_premium
+        8 + // sale_wallet_cap
+        8 + // sale_soft_cap
+        8 + // sale_hard_cap
+        8 + // sale_sold
+        8 + // sale_raised
+        8 + // sale_open_records
+        32 + // position_mint
+        32 + // registry
+        1; // version
+
+    /// Current layout version
+    pub const VERSION: u8 = 1;
+
+    /// Bond posted to reserve an order, relative to its `taker_amount`
+    pub const RESERVATION_BOND_BPS: u16 = 100;
+    /// Longest reservation a taker can take out, about a minute
+    pub const MAX_RESERVATION_SLOTS: u64 = 150;
 
     pub fn new(
         id: Pubkey,
@@ -42,8 +233,135 @@ impl SwapOrder {
             maker_amount,
             taker_amount,
             bump,
+            kind: OrderKind::Fixed,
+            status: OrderStatus::Open,
+            end_ts: 0,
+            reveal_end_ts: 0,
+            penalty_bps: 0,
+            funding_account: Pubkey::default(),
+            refills_remaining: 0,
+            refill_cap: 0,
+            twap_start_ts: 0,
+            twap_interval: 0,
+            twap_slice: 0,
+            twap_filled: 0,
+            group: Pubkey::default(),
+            follow_up_taker_mint: Pubkey::default(),
+            follow_up_taker_amount: 0,
+            follow_up_end_ts: 0,
+            vesting_cliff: 0,
+            vesting_duration: 0,
+            deposit_end_ts: 0,
+            taker_deposited: false,
+            oracle: Pubkey::default(),
+            oracle_premium_bps: 0,
+            oracle_max_age: 0,
+            oracle_max_conf_bps: 0,
+            trigger_oracle: Pubkey::default(),
+            trigger_condition: TriggerCondition::Above,
+            trigger_price: 0,
+            trigger_expo: 0,
+            trigger_max_age: 0,
+            trigger_max_conf_bps: 0,
+            start_ts: 0,
+            hashlock: [0; 32],
+            claim_code_hash: [0; 32],
+            reserved_by: Pubkey::default(),
+            reserved_until_slot: 0,
+            reservation_bond: 0,
+            option_premium: 0,
+            sale_wallet_cap: 0,
+            sale_soft_cap: 0,
+            sale_hard_cap: 0,
+            sale_sold: 0,
+            sale_raised: 0,
+            sale_open_records: 0,
+            position_mint: Pubkey::default(),
+            registry: Pubkey::default(),
+            version: Self::VERSION,
         }
     }
+
+    /// Id of the `index`th order created by a batch, so the ids don't have to be passed
+    pub fn batch_id(base_id: &Pubkey, index: u8) -> Pubkey {
+        Pubkey::new_from_array(hashv(&[base_id.as_ref(), &[index]]).to_bytes())
+    }
+
+    /// Id of the order a chained order re-lists its proceeds into
+    pub fn follow_up_id(order: &Pubkey) -> Pubkey {
+        Pubkey::new_from_array(hashv(&[b"follow_up", order.as_ref()]).to_bytes())
+    }
+
+    /// Whether a sale stopped taking purchases, by running out of time or inventory
+    pub fn sale_ended(&self, now: i64) -> bool {
+        now >= self.end_ts || self.sale_sold == self.sale_hard_cap
+    }
+
+    /// Maker tokens a registry counts as escrowed for the order, `None` once it isn't open
+    pub fn open_maker_amount(&self) -> Option<u64> {
+        (self.status == OrderStatus::Open).then_some(self.maker_amount)
+    }
+
+    /// Seeds used by the order PDA to sign for its escrow
+    pub fn signer_seeds(&self) -> [&[u8]; 6] {
+        [
+            b"order",
+            self.id.as_ref(),
+            self.maker.as_ref(),
+            self.maker_token_mint.as_ref(),
+            self.taker_token_mint.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
+/// Order layout from before order kinds, left behind by the original program
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct LegacySwapOrder {
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub id: Pubkey,
+    pub maker_token_mint: Pubkey,
+    pub taker_token_mint: Pubkey,
+    pub maker_amount: u64,
+    pub taker_amount: u64,
+    pub bump: u8,
+}
+
+impl LegacySwapOrder {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 32 + 8 + 8 + 1;
+
+    /// The same order in the current layout, a legacy order is an open fixed price order
+    pub fn migrate(&self) -> SwapOrder {
+        let mut order = SwapOrder::new(
+            self.id,
+            self.maker,
+            self.maker_token_mint,
+            self.taker_token_mint,
+            self.maker_amount,
+            self.taker_amount,
+            self.bump,
+        );
+        order.taker = self.taker;
+        order
+    }
+}
+
+/// Treasury as the original program laid it out, before the cranker share was added
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct LegacyTreasury {
+    pub authority: Pubkey,
+    pub fee: u16,
+    pub bump: u8,
+}
+
+impl LegacyTreasury {
+    pub const LEN: usize = 32 + 2 + 1;
+
+    /// The same treasury in the current layout, crankers get no share until it's set
+    pub fn migrate(&self) -> Treasury {
+        Treasury::new(self.authority, self.fee, self.bump)
+    }
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount, PartialEq)]
@@ -51,16 +369,306 @@ pub struct Treasury {
     pub authority: Pubkey,
     pub fee: u16,
     pub bump: u8,
+    /// Share of a match's price improvement paid to the cranker
+    pub cranker_share_bps: u16,
 }
 
 impl Treasury {
-    pub const LEN: usize = 32 + 2 + 1;
+    pub const LEN: usize = 32 + 2 + 1 + 2;
 
     pub fn new(authority: Pubkey, fee: u16, bump: u8) -> Self {
         Self {
             authority,
             fee,
             bump,
+            cranker_share_bps: 0,
+        }
+    }
+}
+
+/// Highest bid on an auction order, the bid tokens sit in this PDA's ATA
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount, PartialEq)]
+pub struct Bid {
+    pub order: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl Bid {
+    pub const LEN: usize = 32 + 32 + 8 + 1;
+
+    pub fn new(order: Pubkey, bidder: Pubkey, amount: u64, bump: u8) -> Self {
+        Self {
+            order,
+            bidder,
+            amount,
+            bump,
+        }
+    }
+
+    /// Seeds used by the bid PDA to sign for its escrow
+    pub fn signer_seeds(&self) -> [&[u8]; 3] {
+        [
+            b"bid",
+            self.order.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
+/// Committed bid on a sealed auction, the deposit sits in this PDA's ATA.
+/// Refund terms are copied from the order so they outlive it.
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount, PartialEq)]
+pub struct SealedBid {
+    pub order: Pubkey,
+    pub bidder: Pubkey,
+    pub maker: Pubkey,
+    pub commitment: [u8; 32],
+    pub deposit: u64,
+    pub penalty: u64,
+    pub reveal_end_ts: i64,
+    pub amount: u64,
+    pub revealed: bool,
+    pub bump: u8,
+}
+
+impl SealedBid {
+    pub const LEN: usize = 32 + // order
+        32 + // bidder
+        32 + // maker
+        32 + // commitment
+        8 + // deposit
+        8 + // penalty
+        8 + // reveal_end_ts
+        8 + // amount
+        1 + // revealed
+        1; // bump
+
+    /// Commitment posted for `amount`, bound to the bidder so it can't be replayed by others
+    pub fn commitment(amount: u64, salt: &[u8; 32], bidder: &Pubkey) -> [u8; 32] {
+        hashv(&[&amount.to_le_bytes(), salt, bidder.as_ref()]).to_bytes()
+    }
+
+    /// Seeds used by the sealed bid PDA to sign for its escrow
+    pub fn signer_seeds(&self) -> [&[u8]; 4] {
+        [
+            b"sealed_bid",
+            self.order.as_ref(),
+            self.bidder.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
+/// Taker's commitment to a private order's claim code, revealed in a later slot
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount, PartialEq)]
+pub struct ClaimCommitment {
+    pub order: Pubkey,
+    pub taker: Pubkey,
+    pub commitment: [u8; 32],
+    /// Slot the commitment was made in, the reveal has to land after it
+    pub slot: u64,
+    pub bump: u8,
+}
+
+impl ClaimCommitment {
+    pub const LEN: usize = 32 + // order
+        32 + // taker
+        32 + // commitment
+        8 + // slot
+        1; // bump
+
+    /// Commitment to `secret`, bound to the taker so a copied reveal doesn't match
+    pub fn commitment(secret: &[u8; 32], taker: &Pubkey) -> [u8; 32] {
+        hashv(&[secret, taker.as_ref()]).to_bytes()
+    }
+}
+
+/// A buyer's cumulative purchases from a sale, claimed once the sale ends
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount, PartialEq)]
+pub struct FillRecord {
+    pub order: Pubkey,
+    pub buyer: Pubkey,
+    /// Maker tokens bought
+    pub purchased: u64,
+    /// Taker tokens paid, refunded if the sale misses its soft cap
+    pub paid: u64,
+    pub bump: u8,
+}
+
+impl FillRecord {
+    pub const LEN: usize = 32 + // order
+        32 + // buyer
+        8 + // purchased
+        8 + // paid
+        1; // bump
+}
+
+/// Taker's counter proposal on an order, the payment sits in this PDA's ATA
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount, PartialEq)]
+pub struct CounterOffer {
+    pub order: Pubkey,
+    pub taker: Pubkey,
+    pub maker_amount: u64,
+    pub taker_amount: u64,
+    pub expires_ts: i64,
+    pub bump: u8,
+}
+
+impl CounterOffer {
+    pub const LEN: usize = 32 + // order
+        32 + // taker
+        8 + // maker_amount
+        8 + // taker_amount
+        8 + // expires_ts
+        1; // bump
+
+    /// Seeds used by the counter offer PDA to sign for its escrow
+    pub fn signer_seeds(&self) -> [&[u8]; 4] {
+        [
+            b"counter_offer",
+            self.order.as_ref(),
+            self.taker.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
+/// Per-maker registry handing out sequential order ids
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount, PartialEq)]
+pub struct MakerAccount {
+    pub maker: Pubkey,
+    /// Nonce of the next registered order
+    pub nonce: u64,
+    /// Registered orders that are still open
+    pub open_orders: u64,
+    /// Maker tokens escrowed by registered orders that are still open
+    pub escrowed_amount: u64,
+    pub bump: u8,
+}
+
+impl MakerAccount {
+    pub const LEN: usize = 32 + // maker
+        8 + // nonce
+        8 + // open_orders
+        8 + // escrowed_amount
+        1; // bump
+
+    pub fn new(maker: Pubkey, bump: u8) -> Self {
+        Self {
+            maker,
+            nonce: 0,
+            open_orders: 0,
+            escrowed_amount: 0,
+            bump,
+        }
+    }
+
+    /// Order id seeding the maker's `nonce`th registered order
+    pub fn order_id(maker: &Pubkey, nonce: u64) -> Pubkey {
+        Pubkey::new_from_array(
+            hashv(&[b"maker_order", maker.as_ref(), &nonce.to_le_bytes()]).to_bytes(),
+        )
+    }
+}
+
+/// One-cancels-other link between orders, the first member filled voids the rest
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount, PartialEq)]
+pub struct OrderGroup {
+    pub maker: Pubkey,
+    pub id: Pubkey,
+    /// Member that was filled, default while every member is still fillable
+    pub filled_order: Pubkey,
+    pub members: u8,
+    pub bump: u8,
+}
+
+impl OrderGroup {
+    pub const LEN: usize = 32 + // maker
+        32 + // id
+        32 + // filled_order
+        1 + // members
+        1; // bump
+
+    pub fn new(maker: Pubkey, id: Pubkey, members: u8, bump: u8) -> Self {
+        Self {
+            maker,
+            id,
+            filled_order: Pubkey::default(),
+            members,
+            bump,
         }
     }
 }
+
+/// Maker tokens bought from a vesting order, they sit in this PDA's ATA until claimed
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount, PartialEq)]
+pub struct Vesting {
+    pub order: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+    pub bump: u8,
+}
+
+impl Vesting {
+    pub const LEN: usize = 32 + // order
+        32 + // beneficiary
+        32 + // mint
+        8 + // total_amount
+        8 + // claimed_amount
+        8 + // start_ts
+        8 + // cliff_ts
+        8 + // end_ts
+        1; // bump
+
+    /// Seeds used by the vesting PDA to sign for its escrow
+    pub fn signer_seeds(&self) -> [&[u8]; 3] {
+        [
+            b"vesting",
+            self.order.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
+/// Arbiter and milestone tranches of an arbitrated order
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount, PartialEq)]
+pub struct Arbitration {
+    pub order: Pubkey,
+    pub arbiter: Pubkey,
+    /// After this the escrow can be refunded without the arbiter
+    pub deadline_ts: i64,
+    /// Number of milestones released so far
+    pub released: u8,
+    pub bump: u8,
+    /// Maker tokens released per milestone, summing to the order's `maker_amount`
+    pub milestones: Vec<u64>,
+}
+
+impl Arbitration {
+    pub const MAX_MILESTONES: usize = 16;
+
+    pub fn len(milestones: usize) -> usize {
+        32 + // order
+        32 + // arbiter
+        8 + // deadline_ts
+        1 + // released
+        1 + // bump
+        4 + 8 * milestones // milestones
+    }
+
+    /// Seeds used by the arbitration PDA to sign for its creation
+    pub fn signer_seeds(&self) -> [&[u8]; 3] {
+        [
+            b"arbitration",
+            self.order.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
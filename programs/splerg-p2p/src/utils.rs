@@ -1,4 +1,16 @@
-use solana_program::{account_info::AccountInfo, program_error::ProgramError, program_pack::Pack};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use crate::{error::SwapError, math::fee::split_token_fee};
 
 pub fn get_mint_decimals(mint_account: &AccountInfo) -> Result<u8, ProgramError> {
     spl_token::state::Mint::unpack(&mint_account.data.borrow())
@@ -8,3 +20,186 @@ pub fn get_mint_decimals(mint_account: &AccountInfo) -> Result<u8, ProgramError>
                 .map(|mint| mint.decimals)
         })
 }
+
+/// Closes a program owned account, sending its rent to `destination`
+pub fn close_program_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let rent_lamports = account.lamports();
+    **account.lamports.borrow_mut() = 0;
+    **destination.lamports.borrow_mut() = destination
+        .lamports()
+        .checked_add(rent_lamports)
+        .ok_or(SwapError::Overflow)?;
+    account.data.borrow_mut().fill(0);
+
+    Ok(())
+}
+
+/// Grows a program owned account to `new_len`, `payer` tops up the rent
+pub fn grow_program_account<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    new_len: usize,
+) -> ProgramResult {
+    let rent_lamports = Rent::get()?.minimum_balance(new_len);
+    let top_up = rent_lamports.saturating_sub(account.lamports());
+    if top_up > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, top_up),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+
+    // `resize` isn't available on every supported solana-program version yet
+    #[allow(deprecated)]
+    account.realloc(new_len, true)
+}
+
+/// Returns the token program account that owns the given mint
+pub fn get_token_program<'a, 'b>(
+    mint: &AccountInfo,
+    token_program: &'b AccountInfo<'a>,
+    token_program_2022: &'b AccountInfo<'a>,
+) -> Result<&'b AccountInfo<'a>, ProgramError> {
+    if *mint.owner == spl_token::id() && *token_program.key == spl_token::id() {
+        Ok(token_program)
+    } else if *mint.owner == spl_token_2022::id() && *token_program_2022.key == spl_token_2022::id()
+    {
+        Ok(token_program_2022)
+    } else {
+        Err(SwapError::InvalidTokenProgram.into())
+    }
+}
+
+/// Reads the balance of an SPL Token or Token-2022 account
+pub fn get_token_balance(token_account: &AccountInfo) -> Result<u64, ProgramError> {
+    if *token_account.owner == spl_token_2022::id() {
+        let data = token_account.data.borrow();
+        let account = spl_token_2022::extension::StateWithExtensions::<
+            spl_token_2022::state::Account,
+        >::unpack(&data)?;
+        Ok(account.base.amount)
+    } else {
+        Ok(spl_token::state::Account::unpack(&token_account.data.borrow())?.amount)
+    }
+}
+
+/// Returns how much `delegate` can currently move out of the token account
+pub fn get_delegated_balance(
+    token_account: &AccountInfo,
+    delegate: &Pubkey,
+) -> Result<u64, ProgramError> {
+    let (account_delegate, delegated_amount, amount) =
+        if *token_account.owner == spl_token_2022::id() {
+            let data = token_account.data.borrow();
+            let account = spl_token_2022::extension::StateWithExtensions::<
+                spl_token_2022::state::Account,
+            >::unpack(&data)?;
+            (
+                account.base.delegate,
+                account.base.delegated_amount,
+                account.base.amount,
+            )
+        } else {
+            let account = spl_token::state::Account::unpack(&token_account.data.borrow())?;
+            (account.delegate, account.delegated_amount, account.amount)
+        };
+
+    if account_delegate != COption::Some(*delegate) {
+        return Ok(0);
+    }
+    Ok(delegated_amount.min(amount))
+}
+
+/// Transfers tokens between accounts, using `transfer_checked` for Token-2022 mints.
+/// Pass empty `signer_seeds` when the authority signed the transaction itself.
+pub fn transfer_tokens<'a>(
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    if *token_program.key == spl_token::id() {
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                source.key,
+                destination.key,
+                authority.key,
+                &[],
+                amount,
+            )?,
+            &[
+                source.clone(),
+                destination.clone(),
+                authority.clone(),
+                token_program.clone(),
+            ],
+            signer_seeds,
+        )
+    } else {
+        invoke_signed(
+            &spl_token_2022::instruction::transfer_checked(
+                token_program.key,
+                source.key,
+                mint.key,
+                destination.key,
+                authority.key,
+                &[],
+                amount,
+                get_mint_decimals(mint)?,
+            )?,
+            &[
+                source.clone(),
+                mint.clone(),
+                destination.clone(),
+                authority.clone(),
+                token_program.clone(),
+            ],
+            signer_seeds,
+        )
+    }
+}
+
+/// Transfers `amount` from `source`, routing the treasury fee to `treasury_token_account`
+/// and the remainder to `destination`
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_tokens_with_fee<'a>(
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    treasury_token_account: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    amount: u64,
+    fee_basis_points: u16,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let (amount_after_fee, fee) = split_token_fee(amount, fee_basis_points)?;
+
+    transfer_tokens(
+        token_program,
+        source,
+        mint,
+        destination,
+        authority,
+        amount_after_fee,
+        signer_seeds,
+    )?;
+    transfer_tokens(
+        token_program,
+        source,
+        mint,
+        treasury_token_account,
+        authority,
+        fee,
+        signer_seeds,
+    )
+}
@@ -1,12 +1,17 @@
 use borsh::BorshDeserialize;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    program_pack::Pack, pubkey::Pubkey, sysvar::rent::Rent,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, sysvar::rent::Rent,
+    sysvar::Sysvar,
 };
 
 use crate::{
     error::SwapError,
-    state::{SwapOrder, Treasury},
+    state::{
+        Arbitration, Bid, ClaimCommitment, CounterOffer, FillRecord, LegacySwapOrder,
+        LegacyTreasury, MakerAccount, OrderGroup, OrderKind, OrderStatus, SealedBid, SwapOrder,
+        Treasury, Vesting,
+    },
 };
 
 /// Validates that a mint account is a valid SPL Token or Token-2022 mint
@@ -72,7 +77,8 @@ pub fn validate_signer(account: &AccountInfo) -> ProgramResult {
 /// Validates that the account is the expected authority
 pub fn validate_authority(authority: &AccountInfo, order: &SwapOrder) -> ProgramResult {
     validate_signer(authority)?;
-    if order.maker != *authority.key {
+    // a tokenized order's rights travel with its position token instead
+    if order.maker != *authority.key || order.position_mint != Pubkey::default() {
         return Err(SwapError::UnauthorizedSigner.into());
     }
     Ok(())
@@ -81,6 +87,10 @@ pub fn validate_authority(authority: &AccountInfo, order: &SwapOrder) -> Program
 /// Validate on if the order is open or closed
 pub fn validate_taker(taker: &AccountInfo, order: &SwapOrder) -> ProgramResult {
     if order.taker == Pubkey::default() {
+        // a private order gets its taker once the claim code is revealed
+        if order.claim_code_hash != [0; 32] {
+            return Err(SwapError::UnauthorizedSigner.into());
+        }
         return Ok(());
     }
 
@@ -100,6 +110,22 @@ pub fn validate_init_amounts(maker_amount: u64, taker_amount: u64) -> ProgramRes
     Ok(())
 }
 
+/// Validates the amounts of an invoice, which only asks for taker tokens
+pub fn validate_invoice_amounts(maker_amount: u64, taker_amount: u64) -> ProgramResult {
+    if maker_amount != 0 || taker_amount == 0 {
+        return Err(SwapError::InvalidAmount.into());
+    }
+    Ok(())
+}
+
+/// Validates the amounts of a drop, which only hands out maker tokens
+pub fn validate_drop_amounts(maker_amount: u64, taker_amount: u64) -> ProgramResult {
+    if maker_amount == 0 || taker_amount != 0 {
+        return Err(SwapError::InvalidAmount.into());
+    }
+    Ok(())
+}
+
 /// Validates that an account is rent-exempt
 pub fn validate_rent_exempt(rent: &Rent, account: &AccountInfo) -> ProgramResult {
     if !rent.is_exempt(account.lamports(), account.data_len()) {
@@ -150,7 +176,13 @@ pub fn validate_order_pda(
     program_id: &Pubkey,
     account_info: &AccountInfo,
 ) -> Result<(SwapOrder, u8), ProgramError> {
+    if account_info.data_len() == LegacySwapOrder::LEN {
+        return Err(SwapError::AccountNotMigrated.into());
+    }
     let order = SwapOrder::try_from_slice(&account_info.data.borrow())?;
+    if order.version != SwapOrder::VERSION {
+        return Err(SwapError::AccountNotMigrated.into());
+    }
     let (pda, bump) = get_order_pda(
         program_id,
         &order.id,
@@ -166,16 +198,544 @@ pub fn validate_order_pda(
     Ok((order, bump))
 }
 
+/// Validates that the order has not been filled yet
+pub fn validate_order_open(order: &SwapOrder) -> ProgramResult {
+    if order.status != OrderStatus::Open {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that no taker deposit is waiting on the order, the deposit can only be
+/// settled or withdrawn
+pub fn validate_no_taker_deposit(order: &SwapOrder) -> ProgramResult {
+    if order.taker_deposited {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that the order amounts can still be changed by the maker
+pub fn validate_order_repriceable(order: &SwapOrder) -> ProgramResult {
+    validate_order_open(order)?;
+    validate_no_taker_deposit(order)?;
+    validate_order_unreserved(order)?;
+    // the reserve can't move once someone has bid against it, sealed bids
+    // could have been committed at any time
+    let has_bids = match order.kind {
+        OrderKind::Fixed | OrderKind::Twap => false,
+        OrderKind::Auction | OrderKind::CoveredCall => order.taker != Pubkey::default(),
+        // one-sided orders would need their own amount checks
+        OrderKind::SealedAuction
+        | OrderKind::Arbitrated
+        | OrderKind::Htlc
+        | OrderKind::Invoice
+        | OrderKind::Drop
+        | OrderKind::Sale => true,
+    };
+    if has_bids {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that the maker can close the order
+pub fn validate_order_closable(order: &SwapOrder) -> ProgramResult {
+    validate_no_taker_deposit(order)?;
+    validate_order_unreserved(order)?;
+    // An open arbitrated order can only be released or refunded through the arbiter
+    if order.kind == OrderKind::Arbitrated && order.status == OrderStatus::Open {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    // An open HTLC can only be claimed or reclaimed after the timelock, the taker may
+    // already have locked the other leg
+    if order.kind == OrderKind::Htlc && order.status == OrderStatus::Open {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    // An open covered call's escrow is returned with `ReclaimCoveredCall`
+    if order.kind == OrderKind::CoveredCall && order.status == OrderStatus::Open {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    // An unclaimed drop's escrow is returned with `ReclaimDrop`
+    if order.kind == OrderKind::Drop && order.status == OrderStatus::Open {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    // A sale has to be finalized and every buyer's tokens claimed first
+    if order.kind == OrderKind::Sale
+        && (order.status == OrderStatus::Open || order.sale_open_records != 0)
+    {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    // An open sealed auction is settled, or its lot returned with `ReclaimSealedAuction`
+    // once reveals are over, the bidders would look like non-revealers otherwise
+    if order.kind == OrderKind::SealedAuction && order.status == OrderStatus::Open {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    // An open auction with a leading bid can only be settled
+    if order.kind == OrderKind::Auction
+        && order.status == OrderStatus::Open
+        && order.taker != Pubkey::default()
+    {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that the order was created as the expected kind
+pub fn validate_order_kind(order: &SwapOrder, kind: OrderKind) -> ProgramResult {
+    if order.kind != kind {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that a fixed price order with an expiry, such as a re-listed chained
+/// order or a covered call, can still be filled
+pub fn validate_order_unexpired(order: &SwapOrder) -> ProgramResult {
+    if matches!(order.kind, OrderKind::Fixed | OrderKind::CoveredCall)
+        && order.end_ts != 0
+        && Clock::get()?.unix_timestamp >= order.end_ts
+    {
+        return Err(SwapError::OfferExpired.into());
+    }
+    Ok(())
+}
+
+/// Validates that a scheduled order's start time has passed
+pub fn validate_order_started(order: &SwapOrder) -> ProgramResult {
+    if order.start_ts != 0 && Clock::get()?.unix_timestamp < order.start_ts {
+        return Err(SwapError::OrderNotStarted.into());
+    }
+    Ok(())
+}
+
+/// Validates that the order's price is the stored `taker_amount` rather than pegged to
+/// an oracle, which only `CompleteSwap` reads
+pub fn validate_order_unpegged(order: &SwapOrder) -> ProgramResult {
+    if order.oracle != Pubkey::default() {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that no taker reservation is held on the order, lapsed reservations have to
+/// be forfeited first so the bond doesn't get stuck
+pub fn validate_order_unreserved(order: &SwapOrder) -> ProgramResult {
+    if order.reserved_by != Pubkey::default() {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that the order wasn't created through a maker registry, for paths that
+/// don't keep the registry's counters
+pub fn validate_order_unregistered(order: &SwapOrder) -> ProgramResult {
+    if order.registry != Pubkey::default() {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that the order's maker rights weren't tokenized, for paths that pay `maker`
+pub fn validate_order_untokenized(order: &SwapOrder) -> ProgramResult {
+    if order.position_mint != Pubkey::default() {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Returns the owner of a token account holding the order's position token
+pub fn get_position_holder(
+    position_account: &AccountInfo,
+    order: &SwapOrder,
+) -> Result<Pubkey, ProgramError> {
+    if position_account.owner != &spl_token_2022::id() {
+        return Err(SwapError::InvalidTokenAccount.into());
+    }
+    let account_data = spl_token_2022::state::Account::unpack(&position_account.data.borrow())?;
+    if account_data.mint != order.position_mint || account_data.amount != 1 {
+        return Err(SwapError::InvalidTokenAccount.into());
+    }
+    Ok(account_data.owner)
+}
+
+/// Validates that the authority signs and holds the order's position token
+pub fn validate_position_holder(
+    authority: &AccountInfo,
+    position_account: &AccountInfo,
+    order: &SwapOrder,
+) -> ProgramResult {
+    validate_signer(authority)?;
+    if get_position_holder(position_account, order)? != *authority.key {
+        return Err(SwapError::UnauthorizedSigner.into());
+    }
+    Ok(())
+}
+
+/// Validates that the order has no price trigger, which only `CompleteSwap` checks
+pub fn validate_order_untriggered(order: &SwapOrder) -> ProgramResult {
+    if order.trigger_oracle != Pubkey::default() {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that the order isn't part of a one-cancels-other group, which only
+/// `CompleteSwap` fills
+pub fn validate_order_ungrouped(order: &SwapOrder) -> ProgramResult {
+    if order.group != Pubkey::default() {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that the order doesn't refill, only `CompleteSwap` refills and a partial fill
+/// would change the amount every refill escrows
+pub fn validate_order_nonrecurring(order: &SwapOrder) -> ProgramResult {
+    if order.funding_account != Pubkey::default() {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that no follow-up order is chained to the fill, only `CompleteSwap` re-lists
+/// the proceeds
+pub fn validate_order_unchained(order: &SwapOrder) -> ProgramResult {
+    if order.follow_up_taker_mint != Pubkey::default() {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Validates that the fill isn't vested, only `CompleteSwap` escrows the maker tokens on
+/// the schedule
+pub fn validate_order_unvested(order: &SwapOrder) -> ProgramResult {
+    if order.vesting_duration != 0 {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+    Ok(())
+}
+
+/// Get bid PDA
+pub fn get_bid_pda(program_id: &Pubkey, order: &Pubkey) -> Result<(Pubkey, u8), ProgramError> {
+    let (pda, bump) = Pubkey::find_program_address(&[b"bid", order.as_ref()], program_id);
+    Ok((pda, bump))
+}
+
+/// Validate bid PDA belongs to the order
+pub fn validate_bid_pda(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    order: &Pubkey,
+) -> Result<Bid, ProgramError> {
+    if account_info.owner != program_id {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    let bid = Bid::try_from_slice(&account_info.data.borrow())?;
+    let (pda, bump) = get_bid_pda(program_id, order)?;
+
+    if pda != *account_info.key || bid.order != *order || bid.bump != bump {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    Ok(bid)
+}
+
+/// Get sealed bid PDA
+pub fn get_sealed_bid_pda(
+    program_id: &Pubkey,
+    order: &Pubkey,
+    bidder: &Pubkey,
+) -> Result<(Pubkey, u8), ProgramError> {
+    let (pda, bump) = Pubkey::find_program_address(
+        &[b"sealed_bid", order.as_ref(), bidder.as_ref()],
+        program_id,
+    );
+    Ok((pda, bump))
+}
+
+/// Validate sealed bid PDA belongs to the order
+pub fn validate_sealed_bid_pda(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    order: &Pubkey,
+) -> Result<SealedBid, ProgramError> {
+    if account_info.owner != program_id {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    let sealed_bid = SealedBid::try_from_slice(&account_info.data.borrow())?;
+    let (pda, bump) = get_sealed_bid_pda(program_id, order, &sealed_bid.bidder)?;
+
+    if pda != *account_info.key || sealed_bid.order != *order || sealed_bid.bump != bump {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    Ok(sealed_bid)
+}
+
+/// Get claim commitment PDA
+pub fn get_claim_commitment_pda(
+    program_id: &Pubkey,
+    order: &Pubkey,
+    taker: &Pubkey,
+) -> Result<(Pubkey, u8), ProgramError> {
+    let (pda, bump) = Pubkey::find_program_address(
+        &[b"claim_commitment", order.as_ref(), taker.as_ref()],
+        program_id,
+    );
+    Ok((pda, bump))
+}
+
+/// Validate claim commitment PDA belongs to the order and taker
+pub fn validate_claim_commitment_pda(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    order: &Pubkey,
+    taker: &Pubkey,
+) -> Result<ClaimCommitment, ProgramError> {
+    if account_info.owner != program_id {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    let claim_commitment = ClaimCommitment::try_from_slice(&account_info.data.borrow())?;
+    let (pda, bump) = get_claim_commitment_pda(program_id, order, taker)?;
+
+    if pda != *account_info.key
+        || claim_commitment.order != *order
+        || claim_commitment.taker != *taker
+        || claim_commitment.bump != bump
+    {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    Ok(claim_commitment)
+}
+
+/// Get fill record PDA
+pub fn get_fill_record_pda(
+    program_id: &Pubkey,
+    order: &Pubkey,
+    buyer: &Pubkey,
+) -> Result<(Pubkey, u8), ProgramError> {
+    let (pda, bump) = Pubkey::find_program_address(
+        &[b"fill_record", order.as_ref(), buyer.as_ref()],
+        program_id,
+    );
+    Ok((pda, bump))
+}
+
+/// Validate fill record PDA belongs to the order and buyer
+pub fn validate_fill_record_pda(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    order: &Pubkey,
+    buyer: &Pubkey,
+) -> Result<FillRecord, ProgramError> {
+    if account_info.owner != program_id {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    let fill_record = FillRecord::try_from_slice(&account_info.data.borrow())?;
+    let (pda, bump) = get_fill_record_pda(program_id, order, buyer)?;
+
+    if pda != *account_info.key
+        || fill_record.order != *order
+        || fill_record.buyer != *buyer
+        || fill_record.bump != bump
+    {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    Ok(fill_record)
+}
+
+/// Get position mint PDA
+pub fn get_position_mint_pda(
+    program_id: &Pubkey,
+    order: &Pubkey,
+) -> Result<(Pubkey, u8), ProgramError> {
+    let (pda, bump) = Pubkey::find_program_address(&[b"position", order.as_ref()], program_id);
+    Ok((pda, bump))
+}
+
+/// Get counter offer PDA
+pub fn get_counter_offer_pda(
+    program_id: &Pubkey,
+    order: &Pubkey,
+    taker: &Pubkey,
+) -> Result<(Pubkey, u8), ProgramError> {
+    let (pda, bump) = Pubkey::find_program_address(
+        &[b"counter_offer", order.as_ref(), taker.as_ref()],
+        program_id,
+    );
+    Ok((pda, bump))
+}
+
+/// Validate counter offer PDA belongs to the order
+pub fn validate_counter_offer_pda(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    order: &Pubkey,
+) -> Result<CounterOffer, ProgramError> {
+    if account_info.owner != program_id {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    let counter_offer = CounterOffer::try_from_slice(&account_info.data.borrow())?;
+    let (pda, bump) = get_counter_offer_pda(program_id, order, &counter_offer.taker)?;
+
+    if pda != *account_info.key || counter_offer.order != *order || counter_offer.bump != bump {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    Ok(counter_offer)
+}
+
+/// Get maker registry PDA
+pub fn get_maker_account_pda(
+    program_id: &Pubkey,
+    maker: &Pubkey,
+) -> Result<(Pubkey, u8), ProgramError> {
+    let (pda, bump) = Pubkey::find_program_address(&[b"maker", maker.as_ref()], program_id);
+    Ok((pda, bump))
+}
+
+/// Validate maker registry PDA belongs to the maker
+pub fn validate_maker_account_pda(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    maker: &Pubkey,
+) -> Result<MakerAccount, ProgramError> {
+    if account_info.owner != program_id {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    let maker_account = MakerAccount::try_from_slice(&account_info.data.borrow())?;
+    let (pda, bump) = get_maker_account_pda(program_id, maker)?;
+
+    if pda != *account_info.key || maker_account.maker != *maker || maker_account.bump != bump {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    Ok(maker_account)
+}
+
+/// Get order group PDA
+pub fn get_order_group_pda(
+    program_id: &Pubkey,
+    maker: &Pubkey,
+    id: &Pubkey,
+) -> Result<(Pubkey, u8), ProgramError> {
+    let (pda, bump) =
+        Pubkey::find_program_address(&[b"order_group", maker.as_ref(), id.as_ref()], program_id);
+    Ok((pda, bump))
+}
+
+/// Validate order group PDA and that the order is one of its members
+pub fn validate_order_group_pda(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    order: &SwapOrder,
+) -> Result<OrderGroup, ProgramError> {
+    if account_info.owner != program_id || order.group != *account_info.key {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    let group = OrderGroup::try_from_slice(&account_info.data.borrow())?;
+    let (pda, bump) = get_order_group_pda(program_id, &group.maker, &group.id)?;
+
+    if pda != *account_info.key || group.maker != order.maker || group.bump != bump {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    Ok(group)
+}
+
+/// Get vesting PDA
+pub fn get_vesting_pda(program_id: &Pubkey, order: &Pubkey) -> Result<(Pubkey, u8), ProgramError> {
+    let (pda, bump) = Pubkey::find_program_address(&[b"vesting", order.as_ref()], program_id);
+    Ok((pda, bump))
+}
+
+/// Validate vesting PDA and load its state
+pub fn validate_vesting_pda(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+) -> Result<Vesting, ProgramError> {
+    if account_info.owner != program_id {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    let vesting = Vesting::try_from_slice(&account_info.data.borrow())?;
+    let (pda, bump) = get_vesting_pda(program_id, &vesting.order)?;
+
+    if pda != *account_info.key || vesting.bump != bump {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    Ok(vesting)
+}
+
+/// Get arbitration PDA
+pub fn get_arbitration_pda(
+    program_id: &Pubkey,
+    order: &Pubkey,
+) -> Result<(Pubkey, u8), ProgramError> {
+    let (pda, bump) = Pubkey::find_program_address(&[b"arbitration", order.as_ref()], program_id);
+    Ok((pda, bump))
+}
+
+/// Validate arbitration PDA belongs to the order
+pub fn validate_arbitration_pda(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    order: &Pubkey,
+) -> Result<Arbitration, ProgramError> {
+    if account_info.owner != program_id {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    let arbitration = Arbitration::try_from_slice(&account_info.data.borrow())?;
+    let (pda, bump) = get_arbitration_pda(program_id, order)?;
+
+    if pda != *account_info.key || arbitration.order != *order || arbitration.bump != bump {
+        return Err(SwapError::InvalidOrderState.into());
+    }
+
+    Ok(arbitration)
+}
+
 pub fn get_treasury_pda(program_id: &Pubkey) -> Result<(Pubkey, u8), ProgramError> {
     let (pda, bump) = Pubkey::find_program_address(&[b"treasury"], program_id);
     Ok((pda, bump))
 }
 
+/// Validate treasury PDA and load its state
+pub fn validate_treasury_pda(
+    program_id: &Pubkey,
+    treasury_account: &AccountInfo,
+) -> Result<Treasury, ProgramError> {
+    let (pda, _) = get_treasury_pda(program_id)?;
+    if pda != *treasury_account.key || treasury_account.owner != program_id {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if treasury_account.data_len() == LegacyTreasury::LEN {
+        return Err(SwapError::AccountNotMigrated.into());
+    }
+
+    Ok(Treasury::try_from_slice(&treasury_account.data.borrow())?)
+}
+
 /// Validate treasury PDA and authority
 pub fn validate_treasury_authority(
     treasury_account: &AccountInfo,
     authority: &AccountInfo,
 ) -> ProgramResult {
+    if treasury_account.data_len() == LegacyTreasury::LEN {
+        return Err(SwapError::AccountNotMigrated.into());
+    }
     let treasury = Treasury::try_from_slice(&treasury_account.data.borrow())?;
 
     validate_signer(authority)?;
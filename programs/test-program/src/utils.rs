@@ -1,8 +1,11 @@
 use std::path::PathBuf;
 
+use borsh::BorshDeserialize;
 use litesvm::LiteSVM;
 use solana_sdk::{
-    instruction::AccountMeta,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
     pubkey::Pubkey,
     rent::sysvar,
     signature::Keypair,
@@ -10,7 +13,11 @@ use solana_sdk::{
     system_program,
     transaction::Transaction,
 };
-use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
+
+use splerg_p2p::state::SwapOrder;
 
 use crate::{
     mints::{mint_to_ata, setup_mint},
@@ -175,4 +182,262 @@ impl TestSetup {
             self.svm.latest_blockhash(),
         )
     }
+
+    pub fn initialize_auction(
+        &mut self,
+        maker_amount: u64,
+        reserve_price: u64,
+        end_ts: i64,
+    ) -> Transaction {
+        let mut ix_data = vec![8]; // variant 8 for InitializeAuction
+        ix_data.extend_from_slice(&maker_amount.to_le_bytes());
+        ix_data.extend_from_slice(&reserve_price.to_le_bytes());
+        ix_data.extend_from_slice(&end_ts.to_le_bytes());
+
+        self.create_order_tx(ix_data)
+    }
+
+    pub fn initialize_sealed_auction(
+        &mut self,
+        maker_amount: u64,
+        reserve_price: u64,
+        commit_end_ts: i64,
+        reveal_end_ts: i64,
+        penalty_bps: u16,
+    ) -> Transaction {
+        let mut ix_data = vec![11]; // variant 11 for InitializeSealedAuction
+        ix_data.extend_from_slice(&maker_amount.to_le_bytes());
+        ix_data.extend_from_slice(&reserve_price.to_le_bytes());
+        ix_data.extend_from_slice(&commit_end_ts.to_le_bytes());
+        ix_data.extend_from_slice(&reveal_end_ts.to_le_bytes());
+        ix_data.extend_from_slice(&penalty_bps.to_le_bytes());
+
+        self.create_order_tx(ix_data)
+    }
+
+    /// Builds an order creating instruction, these all share the `InitializeOrder` accounts
+    pub fn create_order_tx(&self, ix_data: Vec<u8>) -> Transaction {
+        let create_order_ix = solana_program::instruction::Instruction {
+            program_id: PROGRAM_KEY,
+            accounts: vec![
+                AccountMeta::new(self.payer.pubkey(), true),
+                AccountMeta::new(self.order_pda, false),
+                AccountMeta::new(self.maker_token_ata, false),
+                AccountMeta::new(self.order_maker_token_ata, false),
+                AccountMeta::new_readonly(self.order_id, false),
+                AccountMeta::new_readonly(self.maker_mint.pubkey(), false),
+                AccountMeta::new_readonly(self.taker_mint.pubkey(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
+            data: ix_data,
+        };
+
+        Transaction::new_signed_with_payer(
+            &[create_order_ix],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            self.svm.latest_blockhash(),
+        )
+    }
+
+    /// Creates a fixed order for any maker and mint pair, returning the order PDA
+    pub fn initialize_order_for(
+        &mut self,
+        maker: &Keypair,
+        maker_mint: &Pubkey,
+        taker_mint: &Pubkey,
+        maker_amount: u64,
+        taker_amount: u64,
+    ) -> Pubkey {
+        let id = KP::new();
+        let (order_pda, _) = Pubkey::find_program_address(
+            &[
+                b"order",
+                id.pubkey().as_ref(),
+                maker.pubkey().as_ref(),
+                maker_mint.as_ref(),
+                taker_mint.as_ref(),
+            ],
+            &PROGRAM_KEY,
+        );
+        let order_ata = self.create_ata(&order_pda, maker_mint);
+
+        let mut ix_data = vec![3]; // variant 3 for InitializeOrder
+        ix_data.extend_from_slice(&maker_amount.to_le_bytes());
+        ix_data.extend_from_slice(&taker_amount.to_le_bytes());
+
+        let initialize_order_ix = solana_program::instruction::Instruction {
+            program_id: PROGRAM_KEY,
+            accounts: vec![
+                AccountMeta::new(maker.pubkey(), true),
+                AccountMeta::new(order_pda, false),
+                AccountMeta::new(
+                    get_associated_token_address(&maker.pubkey(), maker_mint),
+                    false,
+                ),
+                AccountMeta::new(order_ata, false),
+                AccountMeta::new_readonly(id.pubkey(), false),
+                AccountMeta::new_readonly(*maker_mint, false),
+                AccountMeta::new_readonly(*taker_mint, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
+            data: ix_data,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[initialize_order_ix],
+            Some(&maker.pubkey()),
+            &[maker],
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(tx).unwrap();
+
+        order_pda
+    }
+
+    /// Creates the ATA for `owner` if it doesn't exist yet
+    pub fn create_ata(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let ata = get_associated_token_address(owner, mint);
+
+        if self.svm.get_account(&ata).is_none() {
+            let tx = Transaction::new_signed_with_payer(
+                &[create_associated_token_account(
+                    &self.payer.pubkey(),
+                    owner,
+                    mint,
+                    &spl_token::id(),
+                )],
+                Some(&self.payer.pubkey()),
+                &[&self.payer],
+                self.svm.latest_blockhash(),
+            );
+            self.svm.send_transaction(tx).unwrap();
+        }
+
+        ata
+    }
+
+    /// Sends `ixs` signed and paid for by `signer`, expiring the blockhash first so
+    /// repeated sends aren't rejected as duplicates
+    pub fn send(&mut self, ixs: &[Instruction], signer: &Keypair) -> bool {
+        self.svm.expire_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&signer.pubkey()),
+            &[signer],
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(tx).is_ok()
+    }
+
+    /// Builds a `CompleteSwap` for any order, using the taker's, order's and treasury's
+    /// ATAs. `trailing_accounts` are the order's feature accounts
+    pub fn complete_swap_ix(
+        taker: &Pubkey,
+        order: &Pubkey,
+        maker_receiving_ata: &Pubkey,
+        maker_mint: &Pubkey,
+        taker_mint: &Pubkey,
+        trailing_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+
+        let mut accounts = vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new(*order, false),
+            AccountMeta::new(*maker_receiving_ata, false),
+            AccountMeta::new(get_associated_token_address(taker, taker_mint), false),
+            AccountMeta::new(get_associated_token_address(taker, maker_mint), false),
+            AccountMeta::new(get_associated_token_address(order, maker_mint), false),
+            AccountMeta::new(treasury_pda, false),
+            AccountMeta::new(
+                get_associated_token_address(&treasury_pda, maker_mint),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(&treasury_pda, taker_mint),
+                false,
+            ),
+            AccountMeta::new(*maker_mint, false),
+            AccountMeta::new(*taker_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ];
+        accounts.extend(trailing_accounts);
+
+        Instruction {
+            program_id: PROGRAM_KEY,
+            accounts,
+            data: vec![6], // variant 6 for CompleteSwap
+        }
+    }
+
+    /// `complete_swap_ix` for the setup's own order, paying the maker's taker mint ATA
+    pub fn complete_order_ix(
+        &self,
+        taker: &Pubkey,
+        trailing_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        let maker_mint = self.maker_mint.pubkey();
+        let taker_mint = self.taker_mint.pubkey();
+        Self::complete_swap_ix(
+            taker,
+            &self.order_pda,
+            &get_associated_token_address(&self.payer.pubkey(), &taker_mint),
+            &maker_mint,
+            &taker_mint,
+            trailing_accounts,
+        )
+    }
+
+    pub fn token_balance(&self, token_account: &Pubkey) -> u64 {
+        let account = self.svm.get_account(token_account).unwrap();
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
+    pub fn unix_timestamp(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    pub fn set_unix_timestamp(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    pub fn order_state(&self, order: &Pubkey) -> SwapOrder {
+        let account = self.svm.get_account(order).unwrap();
+        SwapOrder::try_from_slice(&account.data).unwrap()
+    }
+
+    /// Overwrites an order's state, for orders in states that take a whole flow to reach
+    pub fn set_order_state(&mut self, order: &Pubkey, state: &SwapOrder) {
+        let mut account = self.svm.get_account(order).unwrap();
+        account.data = borsh::to_vec(state).unwrap();
+        self.svm.set_account(*order, account).unwrap();
+    }
+
+    /// Sends the instructions with `update` applied to the order's state, restoring the
+    /// state afterwards
+    pub fn send_with_order_state(
+        &mut self,
+        order: &Pubkey,
+        update: impl FnOnce(&mut SwapOrder),
+        ixs: &[Instruction],
+        signer: &Keypair,
+    ) -> bool {
+        let original = self.order_state(order);
+        let mut state = self.order_state(order);
+        update(&mut state);
+        self.set_order_state(order, &state);
+        let sent = self.send(ixs, signer);
+        self.set_order_state(order, &original);
+        sent
+    }
 }
@@ -0,0 +1,245 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::state::{Arbitration, OrderStatus, SwapOrder};
+use test_program::{utils::TestSetup, PROGRAM_KEY};
+
+fn arbitration_pda(setup: &TestSetup) -> Pubkey {
+    Pubkey::find_program_address(&[b"arbitration", setup.order_pda.as_ref()], &PROGRAM_KEY).0
+}
+
+/// Escrows 100_000 X for `counterparty`, paid out in `milestones`
+fn initialize_arbitrated_order(
+    setup: &mut TestSetup,
+    counterparty: &Pubkey,
+    arbiter: &Pubkey,
+    deadline_ts: i64,
+    milestones: &[u64],
+) {
+    let maker = setup.payer.pubkey();
+    let mut ix_data = vec![40]; // variant 40 for InitializeArbitratedOrder
+    ix_data.extend_from_slice(&100_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&deadline_ts.to_le_bytes());
+    ix_data.extend_from_slice(&(milestones.len() as u32).to_le_bytes());
+    for milestone in milestones {
+        ix_data.extend_from_slice(&milestone.to_le_bytes());
+    }
+    let initialize_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new_readonly(setup.order_id, false),
+            AccountMeta::new_readonly(setup.maker_mint.pubkey(), false),
+            AccountMeta::new_readonly(setup.taker_mint.pubkey(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(*counterparty, false),
+            AccountMeta::new_readonly(*arbiter, false),
+            AccountMeta::new(arbitration_pda(setup), false),
+        ],
+        data: ix_data,
+    };
+    let payer = setup.payer.insecure_clone();
+    assert!(setup.send(&[initialize_ix], &payer));
+}
+
+fn approve_milestone_ix(setup: &TestSetup, arbiter: &Pubkey, counterparty: &Pubkey) -> Instruction {
+    let mint_x = setup.maker_mint.pubkey();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(*arbiter, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(arbitration_pda(setup), false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(get_associated_token_address(counterparty, &mint_x), false),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(get_associated_token_address(&treasury_pda, &mint_x), false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(setup.payer.pubkey(), false),
+        ],
+        data: vec![41], // variant 41 for ApproveMilestone
+    }
+}
+
+fn refund_ix(setup: &TestSetup, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(arbitration_pda(setup), false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new_readonly(setup.maker_mint.pubkey(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(setup.payer.pubkey(), false),
+        ],
+        data: vec![42], // variant 42 for RefundArbitratedOrder
+    }
+}
+
+#[test]
+fn test_arbitrated_order_releases_milestones_then_refunds() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let maker_kp = setup.payer.insecure_clone();
+    let mint_x = setup.maker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_x);
+
+    let counterparty = Keypair::new().pubkey();
+    let counterparty_x_ata = setup.create_ata(&counterparty, &mint_x);
+    let arbiter = Keypair::new();
+    setup.svm.airdrop(&arbiter.pubkey(), 1_000_000_000).unwrap();
+    let arbitration_pda = arbitration_pda(&setup);
+
+    // 100_000 X paid out in two tranches for an off-chain delivery
+    let deadline_ts = setup.unix_timestamp() + 86_400;
+    initialize_arbitrated_order(
+        &mut setup,
+        &counterparty,
+        &arbiter.pubkey(),
+        deadline_ts,
+        &[60_000, 40_000],
+    );
+
+    let ix = approve_milestone_ix(&setup, &arbiter.pubkey(), &counterparty);
+    assert!(setup.send(&[ix], &arbiter));
+
+    assert_eq!(setup.token_balance(&counterparty_x_ata), 60_000);
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 40_000);
+    let arbitration_account = setup.svm.get_account(&arbitration_pda).unwrap();
+    let arbitration = Arbitration::try_from_slice(&arbitration_account.data).unwrap();
+    assert_eq!(arbitration.released, 1);
+    assert_eq!(arbitration.milestones, vec![60_000, 40_000]);
+
+    // the maker can't pull the rest back while the arbiter is in charge
+    let close_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: vec![7], // variant 7 for CloseOrder
+    };
+    assert!(!setup.send(&[close_ix], &maker_kp));
+
+    let ix = refund_ix(&setup, &maker);
+    assert!(!setup.send(std::slice::from_ref(&ix), &maker_kp));
+
+    // an unresponsive arbiter defaults to a refund
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+    setup.set_unix_timestamp(deadline_ts);
+    assert!(setup.send(&[ix], &maker_kp));
+
+    assert_eq!(
+        setup.token_balance(&setup.maker_token_ata),
+        maker_balance + 40_000
+    );
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 0);
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Filled);
+    assert!(setup
+        .svm
+        .get_account(&arbitration_pda)
+        .is_none_or(|account| account.lamports == 0));
+}
+
+#[test]
+fn test_arbitrated_order_refunded_by_anyone_after_deadline() {
+    let mut setup = TestSetup::new();
+    let counterparty = Keypair::new().pubkey();
+    let arbiter = Keypair::new().pubkey();
+    let deadline_ts = setup.unix_timestamp() + 86_400;
+    initialize_arbitrated_order(&mut setup, &counterparty, &arbiter, deadline_ts, &[]);
+    let arbitration_pda = arbitration_pda(&setup);
+    let arbitration_rent = setup.svm.get_account(&arbitration_pda).unwrap().lamports;
+
+    let stranger = Keypair::new();
+    setup
+        .svm
+        .airdrop(&stranger.pubkey(), 1_000_000_000)
+        .unwrap();
+    let ix = refund_ix(&setup, &stranger.pubkey());
+    // only the arbiter can rule before the deadline
+    assert!(!setup.send(std::slice::from_ref(&ix), &stranger));
+
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+    let maker_lamports = setup.svm.get_balance(&setup.payer.pubkey()).unwrap();
+    setup.set_unix_timestamp(deadline_ts);
+    assert!(setup.send(&[ix], &stranger));
+
+    assert_eq!(
+        setup.token_balance(&setup.maker_token_ata),
+        maker_balance + 100_000
+    );
+    assert_eq!(
+        setup.svm.get_balance(&setup.payer.pubkey()).unwrap(),
+        maker_lamports + arbitration_rent
+    );
+    assert!(setup
+        .svm
+        .get_account(&arbitration_pda)
+        .is_none_or(|account| account.lamports == 0));
+}
+
+#[test]
+fn test_last_milestone_closes_arbitration() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_x);
+
+    let counterparty = Keypair::new().pubkey();
+    let counterparty_x_ata = setup.create_ata(&counterparty, &mint_x);
+    let arbiter = Keypair::new();
+    setup.svm.airdrop(&arbiter.pubkey(), 1_000_000_000).unwrap();
+    let deadline_ts = setup.unix_timestamp() + 86_400;
+    initialize_arbitrated_order(
+        &mut setup,
+        &counterparty,
+        &arbiter.pubkey(),
+        deadline_ts,
+        &[],
+    );
+    let arbitration_pda = arbitration_pda(&setup);
+    let arbitration_rent = setup.svm.get_account(&arbitration_pda).unwrap().lamports;
+    let maker_lamports = setup.svm.get_balance(&maker).unwrap();
+
+    let ix = approve_milestone_ix(&setup, &arbiter.pubkey(), &counterparty);
+    assert!(setup.send(&[ix], &arbiter));
+
+    assert_eq!(setup.token_balance(&counterparty_x_ata), 100_000);
+    assert_eq!(
+        setup.svm.get_balance(&maker).unwrap(),
+        maker_lamports + arbitration_rent
+    );
+    assert!(setup
+        .svm
+        .get_account(&arbitration_pda)
+        .is_none_or(|account| account.lamports == 0));
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Filled);
+}
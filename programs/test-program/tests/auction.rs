@@ -0,0 +1,198 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::{
+    math::fee::split_token_fee,
+    state::{OrderStatus, SwapOrder},
+};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+fn place_bid_tx(
+    setup: &TestSetup,
+    bidder: &Keypair,
+    previous_bidder: &Pubkey,
+    amount: u64,
+) -> Transaction {
+    let (bid_pda, _) =
+        Pubkey::find_program_address(&[b"bid", setup.order_pda.as_ref()], &PROGRAM_KEY);
+
+    let mut ix_data = vec![9]; // variant 9 for PlaceBid
+    ix_data.extend_from_slice(&amount.to_le_bytes());
+
+    let place_bid_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(bidder.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(bid_pda, false),
+            AccountMeta::new(
+                get_associated_token_address(&bid_pda, &setup.taker_mint.pubkey()),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(&bidder.pubkey(), &setup.taker_mint.pubkey()),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(previous_bidder, &setup.taker_mint.pubkey()),
+                false,
+            ),
+            AccountMeta::new_readonly(setup.taker_mint.pubkey(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: ix_data,
+    };
+
+    Transaction::new_signed_with_payer(
+        &[place_bid_ix],
+        Some(&bidder.pubkey()),
+        &[bidder],
+        setup.svm.latest_blockhash(),
+    )
+}
+
+fn settle_auction_tx(setup: &TestSetup, bidder: &Pubkey) -> Transaction {
+    let (bid_pda, _) =
+        Pubkey::find_program_address(&[b"bid", setup.order_pda.as_ref()], &PROGRAM_KEY);
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+
+    let settle_auction_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(bid_pda, false),
+            AccountMeta::new(
+                get_associated_token_address(&bid_pda, &setup.taker_mint.pubkey()),
+                false,
+            ),
+            AccountMeta::new(*bidder, false),
+            AccountMeta::new(
+                get_associated_token_address(&setup.payer.pubkey(), &setup.taker_mint.pubkey()),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(bidder, &setup.maker_mint.pubkey()),
+                false,
+            ),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(
+                get_associated_token_address(&treasury_pda, &setup.maker_mint.pubkey()),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(&treasury_pda, &setup.taker_mint.pubkey()),
+                false,
+            ),
+            AccountMeta::new_readonly(setup.maker_mint.pubkey(), false),
+            AccountMeta::new_readonly(setup.taker_mint.pubkey(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ],
+        data: vec![10], // variant 10 for SettleAuction
+    };
+
+    Transaction::new_signed_with_payer(
+        &[settle_auction_ix],
+        Some(&setup.payer.pubkey()),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    )
+}
+
+#[test]
+fn test_auction_outbid_and_settle() {
+    let mut setup = TestSetup::new();
+    let fee = 100u16;
+
+    let tx = setup.initialize_treasury(&setup.payer.pubkey(), fee);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let (bid_pda, _) =
+        Pubkey::find_program_address(&[b"bid", setup.order_pda.as_ref()], &PROGRAM_KEY);
+    let maker_mint = setup.maker_mint.pubkey();
+    let taker_mint = setup.taker_mint.pubkey();
+
+    let maker_taker_ata = setup.create_ata(&setup.payer.pubkey(), &taker_mint);
+    let treasury_maker_ata = setup.create_ata(&treasury_pda, &maker_mint);
+    let treasury_taker_ata = setup.create_ata(&treasury_pda, &taker_mint);
+    setup.create_ata(&bid_pda, &taker_mint);
+
+    let first_bidder = Keypair::new();
+    let second_bidder = Keypair::new();
+    for bidder in [&first_bidder, &second_bidder] {
+        setup.svm.airdrop(&bidder.pubkey(), 1_000_000_000).unwrap();
+        setup.svm = mint_to_ata(
+            setup.svm,
+            &setup.payer,
+            &setup.taker_mint,
+            1_000_000,
+            &bidder.pubkey(),
+        )
+        .unwrap();
+    }
+    let winner_maker_ata = setup.create_ata(&second_bidder.pubkey(), &maker_mint);
+
+    let maker_amount = 100_000u64;
+    let reserve_price = 150_000u64;
+    let end_ts = setup.unix_timestamp() + 3_600;
+    let tx = setup.initialize_auction(maker_amount, reserve_price, end_ts);
+    setup.svm.send_transaction(tx).unwrap();
+
+    // Below the reserve
+    let tx = place_bid_tx(&setup, &first_bidder, &first_bidder.pubkey(), 100_000);
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    let tx = place_bid_tx(&setup, &first_bidder, &first_bidder.pubkey(), reserve_price);
+    setup.svm.send_transaction(tx).unwrap();
+
+    // Not above the current bid
+    let tx = place_bid_tx(
+        &setup,
+        &second_bidder,
+        &first_bidder.pubkey(),
+        reserve_price,
+    );
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    let winning_bid = 200_000u64;
+    let tx = place_bid_tx(&setup, &second_bidder, &first_bidder.pubkey(), winning_bid);
+    setup.svm.send_transaction(tx).unwrap();
+
+    // The outbid bidder was refunded
+    let first_bidder_ata = get_associated_token_address(&first_bidder.pubkey(), &taker_mint);
+    assert_eq!(setup.token_balance(&first_bidder_ata), 1_000_000);
+
+    let tx = settle_auction_tx(&setup, &second_bidder.pubkey());
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    setup.set_unix_timestamp(end_ts);
+
+    let tx = settle_auction_tx(&setup, &second_bidder.pubkey());
+    setup.svm.send_transaction(tx).unwrap();
+
+    let (maker_after_fee, maker_fee) = split_token_fee(maker_amount, fee).unwrap();
+    let (bid_after_fee, bid_fee) = split_token_fee(winning_bid, fee).unwrap();
+
+    assert_eq!(setup.token_balance(&maker_taker_ata), bid_after_fee);
+    assert_eq!(setup.token_balance(&winner_maker_ata), maker_after_fee);
+    assert_eq!(setup.token_balance(&treasury_maker_ata), maker_fee);
+    assert_eq!(setup.token_balance(&treasury_taker_ata), bid_fee);
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 0);
+
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Filled);
+    assert_eq!(order.taker, second_bidder.pubkey());
+    assert_eq!(order.taker_amount, winning_bid);
+}
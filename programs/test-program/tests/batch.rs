@@ -0,0 +1,153 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::Signer,
+    system_program,
+    transaction::Transaction,
+};
+use splerg_p2p::{
+    instruction::{OrderAmounts, SwapInstruction},
+    state::SwapOrder,
+};
+use test_program::{utils::TestSetup, PROGRAM_KEY};
+
+fn send_batch(setup: &mut TestSetup, instruction: SwapInstruction, accounts: Vec<AccountMeta>) {
+    let mut ix_data = vec![];
+    instruction.serialize(&mut ix_data).unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: PROGRAM_KEY,
+            accounts,
+            data: ix_data,
+        }],
+        Some(&setup.payer.pubkey()),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+}
+
+#[test]
+fn test_batch_ladder_create_reprice_cancel() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let maker_mint = setup.maker_mint.pubkey();
+    let taker_mint = setup.taker_mint.pubkey();
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+
+    let base_id = Pubkey::new_unique();
+    let levels: Vec<OrderAmounts> = (0..10u64)
+        .map(|i| OrderAmounts {
+            maker_amount: 10_000,
+            taker_amount: 20_000 + i * 1_000,
+        })
+        .collect();
+
+    let mut order_accounts = vec![];
+    let mut orders = vec![];
+    for index in 0..levels.len() as u8 {
+        let (order, _) = Pubkey::find_program_address(
+            &[
+                b"order",
+                SwapOrder::batch_id(&base_id, index).as_ref(),
+                maker.as_ref(),
+                maker_mint.as_ref(),
+                taker_mint.as_ref(),
+            ],
+            &PROGRAM_KEY,
+        );
+        let order_ata = setup.create_ata(&order, &maker_mint);
+        order_accounts.extend([
+            AccountMeta::new(order, false),
+            AccountMeta::new(order_ata, false),
+        ]);
+        orders.push((order, order_ata));
+    }
+
+    // The whole ladder goes out in a single transaction
+    let mut accounts = vec![
+        AccountMeta::new(maker, true),
+        AccountMeta::new(setup.maker_token_ata, false),
+        AccountMeta::new_readonly(maker_mint, false),
+        AccountMeta::new_readonly(taker_mint, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    accounts.extend(order_accounts.iter().cloned());
+    send_batch(
+        &mut setup,
+        SwapInstruction::BatchInitializeOrders {
+            base_id: base_id.to_bytes(),
+            levels: levels.clone(),
+        },
+        accounts,
+    );
+
+    assert_eq!(
+        setup.token_balance(&setup.maker_token_ata),
+        maker_balance - 100_000
+    );
+    for ((order, order_ata), level) in orders.iter().zip(&levels) {
+        let order_account = setup.svm.get_account(order).unwrap();
+        let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+        assert_eq!(order.maker_amount, level.maker_amount);
+        assert_eq!(order.taker_amount, level.taker_amount);
+        assert_eq!(setup.token_balance(order_ata), level.maker_amount);
+    }
+
+    // Shift the first three levels: one grows, one shrinks, one only reprices
+    let repriced = vec![
+        OrderAmounts {
+            maker_amount: 15_000,
+            taker_amount: 30_000,
+        },
+        OrderAmounts {
+            maker_amount: 5_000,
+            taker_amount: 11_000,
+        },
+        OrderAmounts {
+            maker_amount: 10_000,
+            taker_amount: 25_000,
+        },
+    ];
+    let mut accounts = vec![
+        AccountMeta::new_readonly(maker, true),
+        AccountMeta::new(setup.maker_token_ata, false),
+        AccountMeta::new_readonly(maker_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    accounts.extend(order_accounts[..6].iter().cloned());
+    send_batch(
+        &mut setup,
+        SwapInstruction::BatchChangeOrderAmounts {
+            levels: repriced.clone(),
+        },
+        accounts,
+    );
+
+    for ((order, order_ata), level) in orders.iter().zip(&repriced) {
+        let order_account = setup.svm.get_account(order).unwrap();
+        let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+        assert_eq!(order.maker_amount, level.maker_amount);
+        assert_eq!(order.taker_amount, level.taker_amount);
+        assert_eq!(setup.token_balance(order_ata), level.maker_amount);
+    }
+
+    // Pull the whole ladder
+    let mut accounts = vec![
+        AccountMeta::new(maker, true),
+        AccountMeta::new(setup.maker_token_ata, false),
+        AccountMeta::new_readonly(maker_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    accounts.extend(order_accounts.iter().cloned());
+    send_batch(&mut setup, SwapInstruction::BatchCancelOrders, accounts);
+
+    assert_eq!(setup.token_balance(&setup.maker_token_ata), maker_balance);
+    for (order, order_ata) in &orders {
+        assert!(setup.svm.get_account(order).is_none());
+        assert_eq!(setup.token_balance(order_ata), 0);
+    }
+}
@@ -0,0 +1,162 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::state::{OrderStatus, SwapOrder};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+#[test]
+fn test_chained_order_relists_proceeds() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_x);
+    setup.create_ata(&treasury_pda, &mint_y);
+
+    // sell 100_000 X for 200_000 Y, then offer the Y back for 110_000 X
+    let tx = setup.initialize_order(100_000, 200_000);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let end_ts = setup.unix_timestamp() + 3_600;
+    let mut ix_data = vec![33]; // variant 33 for SetFollowUpOrder
+    ix_data.extend_from_slice(&110_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&end_ts.to_le_bytes());
+    let follow_up_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: ix_data,
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[follow_up_ix],
+        Some(&maker),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    let follow_up_id = SwapOrder::follow_up_id(&setup.order_pda);
+    let (follow_up_pda, _) = Pubkey::find_program_address(
+        &[
+            b"order",
+            follow_up_id.as_ref(),
+            maker.as_ref(),
+            mint_y.as_ref(),
+            mint_x.as_ref(),
+        ],
+        &PROGRAM_KEY,
+    );
+    let follow_up_escrow = setup.create_ata(&follow_up_pda, &mint_y);
+
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        1_000_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let taker_x_ata = setup.create_ata(&taker.pubkey(), &mint_x);
+
+    // the taker fills without the maker, the Y lands in the follow-up escrow
+    let fill_ix = TestSetup::complete_swap_ix(
+        &taker.pubkey(),
+        &setup.order_pda,
+        &follow_up_escrow,
+        &mint_x,
+        &mint_y,
+        vec![
+            AccountMeta::new(follow_up_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[fill_ix],
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&taker_x_ata), 100_000);
+    assert_eq!(setup.token_balance(&follow_up_escrow), 200_000);
+
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Filled);
+
+    let follow_up_account = setup.svm.get_account(&follow_up_pda).unwrap();
+    assert_eq!(follow_up_account.owner, PROGRAM_KEY);
+    let follow_up = SwapOrder::try_from_slice(&follow_up_account.data).unwrap();
+    assert_eq!(follow_up.maker, maker);
+    assert_eq!(follow_up.maker_token_mint, mint_y);
+    assert_eq!(follow_up.taker_token_mint, mint_x);
+    assert_eq!(follow_up.maker_amount, 200_000);
+    assert_eq!(follow_up.taker_amount, 110_000);
+    assert_eq!(follow_up.end_ts, end_ts);
+    assert_eq!(follow_up.status, OrderStatus::Open);
+
+    // the re-listed order fills like any other until it expires
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.maker_mint,
+        10_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let relist_fill_ix = TestSetup::complete_swap_ix(
+        &taker.pubkey(),
+        &follow_up_pda,
+        &setup.maker_token_ata,
+        &mint_y,
+        &mint_x,
+        vec![],
+    );
+    let maker_x_balance = setup.token_balance(&setup.maker_token_ata);
+
+    setup.set_unix_timestamp(end_ts);
+    let tx = Transaction::new_signed_with_payer(
+        std::slice::from_ref(&relist_fill_ix),
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    setup.set_unix_timestamp(end_ts - 1);
+    setup.svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[relist_fill_ix],
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(
+        setup.token_balance(&setup.maker_token_ata),
+        maker_x_balance + 110_000
+    );
+    assert_eq!(
+        setup.token_balance(&get_associated_token_address(&taker.pubkey(), &mint_y)),
+        1_000_000
+    );
+}
@@ -0,0 +1,279 @@
+use solana_sdk::{
+    clock::Clock,
+    hash::hashv,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::Transaction,
+};
+use splerg_p2p::state::ClaimCommitment;
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+fn claim_commitment_pda(setup: &TestSetup, taker: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"claim_commitment",
+            setup.order_pda.as_ref(),
+            taker.as_ref(),
+        ],
+        &PROGRAM_KEY,
+    )
+    .0
+}
+
+fn set_claim_code_ix(setup: &TestSetup, authority: &Pubkey, secret: &[u8; 32]) -> Instruction {
+    let mut ix_data = vec![49]; // variant 49 for SetClaimCode
+    ix_data.extend_from_slice(&hashv(&[secret]).to_bytes());
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: ix_data,
+    }
+}
+
+fn commit_ix(setup: &TestSetup, taker: &Pubkey, commitment: [u8; 32]) -> Instruction {
+    let mut ix_data = vec![50]; // variant 50 for CommitClaimCode
+    ix_data.extend_from_slice(&commitment);
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new_readonly(setup.order_pda, false),
+            AccountMeta::new(claim_commitment_pda(setup, taker), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: ix_data,
+    }
+}
+
+fn reveal_ix(setup: &TestSetup, taker: &Pubkey, secret: [u8; 32]) -> Instruction {
+    let mut ix_data = vec![51]; // variant 51 for RevealClaimCode
+    ix_data.extend_from_slice(&secret);
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(claim_commitment_pda(setup, taker), false),
+        ],
+        data: ix_data,
+    }
+}
+
+fn withdraw_ix(setup: &TestSetup, taker: &Pubkey, commitment_owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new_readonly(setup.order_pda, false),
+            AccountMeta::new(claim_commitment_pda(setup, commitment_owner), false),
+        ],
+        data: vec![71], // variant 71 for WithdrawClaimCommitment
+    }
+}
+
+fn next_slot(setup: &mut TestSetup) {
+    let slot = setup.svm.get_sysvar::<Clock>().slot;
+    setup.svm.warp_to_slot(slot + 1);
+}
+
+#[test]
+fn test_private_order_filled_with_claim_code() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_x);
+    setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+
+    let tx = setup.initialize_order(100_000, 180_000);
+    setup.svm.send_transaction(tx).unwrap();
+    let secret = [42; 32];
+    let maker_kp = setup.payer.insecure_clone();
+    let ix = set_claim_code_ix(&setup, &maker, &secret);
+    assert!(setup.send(&[ix], &maker_kp));
+
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        180_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let taker_x_ata = setup.create_ata(&taker.pubkey(), &mint_x);
+
+    let complete_swap_ix = setup.complete_order_ix(&taker.pubkey(), vec![]);
+    let send_fill = |setup: &mut TestSetup| {
+        setup.svm.expire_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&complete_swap_ix),
+            Some(&taker.pubkey()),
+            &[&taker],
+            setup.svm.latest_blockhash(),
+        );
+        setup.svm.send_transaction(tx).is_ok()
+    };
+
+    // nobody can fill before claiming the order with the secret
+    assert!(!send_fill(&mut setup));
+
+    let commit_ix = commit_ix(
+        &setup,
+        &taker.pubkey(),
+        ClaimCommitment::commitment(&secret, &taker.pubkey()),
+    );
+    let reveal_ix = reveal_ix(&setup, &taker.pubkey(), secret);
+
+    // a commitment can't be revealed in its own slot
+    let tx = Transaction::new_signed_with_payer(
+        &[commit_ix.clone(), reveal_ix.clone()],
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[commit_ix],
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    next_slot(&mut setup);
+    let tx = Transaction::new_signed_with_payer(
+        &[reveal_ix, complete_swap_ix.clone()],
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&taker_x_ata), 100_000);
+    assert_eq!(setup.token_balance(&maker_y_ata), 180_000);
+    assert!(setup
+        .svm
+        .get_account(&claim_commitment_pda(&setup, &taker.pubkey()))
+        .is_none());
+}
+
+#[test]
+fn test_claim_code_rejects_wrong_secret_and_copied_commitment() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let maker_kp = setup.payer.insecure_clone();
+    let tx = setup.initialize_order(100_000, 180_000);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let secret = [42; 32];
+    let taker = Keypair::new();
+    let attacker = Keypair::new();
+    for user in [&taker, &attacker] {
+        setup.svm.airdrop(&user.pubkey(), 1_000_000_000).unwrap();
+    }
+
+    // only the maker sets the claim code
+    let ix = set_claim_code_ix(&setup, &attacker.pubkey(), &[7; 32]);
+    assert!(!setup.send(&[ix], &attacker));
+    let ix = set_claim_code_ix(&setup, &maker, &secret);
+    assert!(setup.send(&[ix], &maker_kp));
+
+    // a commitment copied from the taker's transaction is bound to the taker's key
+    let taker_commitment = ClaimCommitment::commitment(&secret, &taker.pubkey());
+    let ix = commit_ix(&setup, &attacker.pubkey(), taker_commitment);
+    assert!(setup.send(&[ix], &attacker));
+    let ix = commit_ix(&setup, &taker.pubkey(), taker_commitment);
+    assert!(setup.send(&[ix], &taker));
+    next_slot(&mut setup);
+
+    let ix = reveal_ix(&setup, &attacker.pubkey(), secret);
+    assert!(!setup.send(&[ix], &attacker));
+    // nor does a guess at the secret get through
+    let ix = reveal_ix(&setup, &taker.pubkey(), [41; 32]);
+    assert!(!setup.send(&[ix], &taker));
+
+    let ix = reveal_ix(&setup, &taker.pubkey(), secret);
+    assert!(setup.send(&[ix], &taker));
+    assert_eq!(setup.order_state(&setup.order_pda).taker, taker.pubkey());
+
+    // the code is spent once revealed
+    let late = Keypair::new();
+    setup.svm.airdrop(&late.pubkey(), 1_000_000_000).unwrap();
+    let ix = commit_ix(
+        &setup,
+        &late.pubkey(),
+        ClaimCommitment::commitment(&secret, &late.pubkey()),
+    );
+    assert!(!setup.send(&[ix], &late));
+}
+
+#[test]
+fn test_claim_commitment_withdrawn_before_reveal_and_after_close() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let maker_kp = setup.payer.insecure_clone();
+    let tx = setup.initialize_order(100_000, 180_000);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let secret = [42; 32];
+    let ix = set_claim_code_ix(&setup, &maker, &secret);
+    assert!(setup.send(&[ix], &maker_kp));
+
+    let taker = Keypair::new();
+    let stranger = Keypair::new();
+    for user in [&taker, &stranger] {
+        setup.svm.airdrop(&user.pubkey(), 1_000_000_000).unwrap();
+    }
+    let commitment_pda = claim_commitment_pda(&setup, &taker.pubkey());
+
+    // a mistyped commitment can be pulled back and made again
+    let ix = commit_ix(&setup, &taker.pubkey(), [7; 32]);
+    assert!(setup.send(&[ix], &taker));
+    let taker_lamports = setup.svm.get_account(&taker.pubkey()).unwrap().lamports;
+
+    // only the committer withdraws
+    let ix = withdraw_ix(&setup, &stranger.pubkey(), &taker.pubkey());
+    assert!(!setup.send(&[ix], &stranger));
+
+    let ix = withdraw_ix(&setup, &taker.pubkey(), &taker.pubkey());
+    assert!(setup.send(&[ix], &taker));
+    assert!(setup.svm.get_account(&commitment_pda).is_none());
+    // the rent comes back to the committer, net of the transaction fee
+    assert!(setup.svm.get_account(&taker.pubkey()).unwrap().lamports > taker_lamports);
+
+    let ix = commit_ix(
+        &setup,
+        &taker.pubkey(),
+        ClaimCommitment::commitment(&secret, &taker.pubkey()),
+    );
+    assert!(setup.send(&[ix], &taker));
+
+    // the maker closing the order doesn't strand the commitment's rent
+    let close_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: vec![7], // variant 7 for CloseOrder
+    };
+    assert!(setup.send(&[close_ix], &maker_kp));
+
+    let ix = withdraw_ix(&setup, &taker.pubkey(), &taker.pubkey());
+    assert!(setup.send(&[ix], &taker));
+    assert!(setup.svm.get_account(&commitment_pda).is_none());
+}
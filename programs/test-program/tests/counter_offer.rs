@@ -0,0 +1,225 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::{
+    math::fee::split_token_fee,
+    state::{OrderStatus, SwapOrder},
+};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+fn counter_offer_pda(setup: &TestSetup, taker: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"counter_offer", setup.order_pda.as_ref(), taker.as_ref()],
+        &PROGRAM_KEY,
+    )
+    .0
+}
+
+fn funded_taker(setup: &mut TestSetup) -> Keypair {
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        1_000_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+
+    let taker_mint = setup.taker_mint.pubkey();
+    setup.create_ata(&counter_offer_pda(setup, &taker.pubkey()), &taker_mint);
+
+    taker
+}
+
+fn propose_tx(
+    setup: &TestSetup,
+    taker: &Keypair,
+    maker_amount: u64,
+    taker_amount: u64,
+    expires_ts: i64,
+) -> Transaction {
+    let counter_offer = counter_offer_pda(setup, &taker.pubkey());
+    let taker_mint = setup.taker_mint.pubkey();
+
+    let mut ix_data = vec![16]; // variant 16 for ProposeCounterOffer
+    ix_data.extend_from_slice(&maker_amount.to_le_bytes());
+    ix_data.extend_from_slice(&taker_amount.to_le_bytes());
+    ix_data.extend_from_slice(&expires_ts.to_le_bytes());
+
+    let propose_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(taker.pubkey(), true),
+            AccountMeta::new_readonly(setup.order_pda, false),
+            AccountMeta::new(counter_offer, false),
+            AccountMeta::new(
+                get_associated_token_address(&counter_offer, &taker_mint),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(&taker.pubkey(), &taker_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(taker_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: ix_data,
+    };
+
+    Transaction::new_signed_with_payer(
+        &[propose_ix],
+        Some(&taker.pubkey()),
+        &[taker],
+        setup.svm.latest_blockhash(),
+    )
+}
+
+#[test]
+fn test_accept_counter_offer() {
+    let mut setup = TestSetup::new();
+    let fee = 100u16;
+
+    let tx = setup.initialize_treasury(&setup.payer.pubkey(), fee);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let maker_mint = setup.maker_mint.pubkey();
+    let taker_mint = setup.taker_mint.pubkey();
+
+    let maker_taker_ata = setup.create_ata(&setup.payer.pubkey(), &taker_mint);
+    let treasury_maker_ata = setup.create_ata(&treasury_pda, &maker_mint);
+    let treasury_taker_ata = setup.create_ata(&treasury_pda, &taker_mint);
+
+    let taker = funded_taker(&mut setup);
+    let taker_maker_ata = setup.create_ata(&taker.pubkey(), &maker_mint);
+
+    let tx = setup.initialize_order(100_000, 200_000);
+    setup.svm.send_transaction(tx).unwrap();
+    let maker_balance_before = setup.token_balance(&setup.maker_token_ata);
+
+    // Half the inventory at a lower price
+    let offer_maker_amount = 50_000u64;
+    let offer_taker_amount = 90_000u64;
+    let expires_ts = setup.unix_timestamp() + 600;
+    let tx = propose_tx(
+        &setup,
+        &taker,
+        offer_maker_amount,
+        offer_taker_amount,
+        expires_ts,
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    let counter_offer = counter_offer_pda(&setup, &taker.pubkey());
+    let accept_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(setup.payer.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(counter_offer, false),
+            AccountMeta::new(
+                get_associated_token_address(&counter_offer, &taker_mint),
+                false,
+            ),
+            AccountMeta::new(taker.pubkey(), false),
+            AccountMeta::new(maker_taker_ata, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new(taker_maker_ata, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(treasury_maker_ata, false),
+            AccountMeta::new(treasury_taker_ata, false),
+            AccountMeta::new_readonly(maker_mint, false),
+            AccountMeta::new_readonly(taker_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ],
+        data: vec![17], // variant 17 for AcceptCounterOffer
+    };
+    let maker = setup.payer.insecure_clone();
+    let order_pda = setup.order_pda;
+    // the maker can't sell around someone's reservation
+    assert!(!setup.send_with_order_state(
+        &order_pda,
+        |order| order.reserved_by = Pubkey::new_unique(),
+        std::slice::from_ref(&accept_ix),
+        &maker,
+    ));
+    assert!(setup.send(&[accept_ix], &maker));
+
+    let (maker_after_fee, maker_fee) = split_token_fee(offer_maker_amount, fee).unwrap();
+    let (taker_after_fee, taker_fee) = split_token_fee(offer_taker_amount, fee).unwrap();
+
+    assert_eq!(setup.token_balance(&maker_taker_ata), taker_after_fee);
+    assert_eq!(setup.token_balance(&taker_maker_ata), maker_after_fee);
+    assert_eq!(setup.token_balance(&treasury_maker_ata), maker_fee);
+    assert_eq!(setup.token_balance(&treasury_taker_ata), taker_fee);
+    assert_eq!(
+        setup.token_balance(&setup.maker_token_ata),
+        maker_balance_before + 50_000
+    );
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 0);
+    assert!(setup.svm.get_account(&counter_offer).is_none());
+
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Filled);
+    assert_eq!(order.taker, taker.pubkey());
+    assert_eq!(order.taker_amount, offer_taker_amount);
+}
+
+#[test]
+fn test_withdraw_counter_offer() {
+    let mut setup = TestSetup::new();
+    let taker_mint = setup.taker_mint.pubkey();
+
+    let taker = funded_taker(&mut setup);
+    let taker_ata = get_associated_token_address(&taker.pubkey(), &taker_mint);
+
+    let tx = setup.initialize_order(100_000, 200_000);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let expires_ts = setup.unix_timestamp() + 600;
+    let tx = propose_tx(&setup, &taker, 100_000, 150_000, expires_ts);
+    setup.svm.send_transaction(tx).unwrap();
+    assert_eq!(setup.token_balance(&taker_ata), 850_000);
+
+    let counter_offer = counter_offer_pda(&setup, &taker.pubkey());
+    let withdraw_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(taker.pubkey(), true),
+            AccountMeta::new_readonly(setup.order_pda, false),
+            AccountMeta::new(counter_offer, false),
+            AccountMeta::new(
+                get_associated_token_address(&counter_offer, &taker_mint),
+                false,
+            ),
+            AccountMeta::new(taker_ata, false),
+            AccountMeta::new_readonly(taker_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![18], // variant 18 for WithdrawCounterOffer
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[withdraw_ix],
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&taker_ata), 1_000_000);
+    assert!(setup.svm.get_account(&counter_offer).is_none());
+}
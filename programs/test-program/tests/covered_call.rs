@@ -0,0 +1,182 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+/// Writes a call over 100_000 X struck at 180_000 Y for a 5_000 Y premium
+fn initialize_covered_call(setup: &mut TestSetup, expiry_ts: i64) {
+    let mut ix_data = vec![54]; // variant 54 for InitializeCoveredCall
+    ix_data.extend_from_slice(&100_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&180_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&5_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&expiry_ts.to_le_bytes());
+    let tx = setup.create_order_tx(ix_data);
+    setup.svm.send_transaction(tx).unwrap();
+}
+
+fn buy_option_ix(
+    setup: &TestSetup,
+    buyer: &Pubkey,
+    maker_amount: u64,
+    strike_amount: u64,
+    premium: u64,
+) -> Instruction {
+    let mint_y = setup.taker_mint.pubkey();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let mut ix_data = vec![55]; // variant 55 for BuyOption
+    ix_data.extend_from_slice(&maker_amount.to_le_bytes());
+    ix_data.extend_from_slice(&strike_amount.to_le_bytes());
+    ix_data.extend_from_slice(&premium.to_le_bytes());
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(*buyer, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(get_associated_token_address(buyer, &mint_y), false),
+            AccountMeta::new(
+                get_associated_token_address(&setup.payer.pubkey(), &mint_y),
+                false,
+            ),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(get_associated_token_address(&treasury_pda, &mint_y), false),
+            AccountMeta::new_readonly(mint_y, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: ix_data,
+    }
+}
+
+fn new_buyer(setup: &mut TestSetup) -> Keypair {
+    let buyer = Keypair::new();
+    setup.svm.airdrop(&buyer.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        200_000,
+        &buyer.pubkey(),
+    )
+    .unwrap();
+    let mint_x = setup.maker_mint.pubkey();
+    setup.create_ata(&buyer.pubkey(), &mint_x);
+    buyer
+}
+
+#[test]
+fn test_covered_call_bought_transferred_and_exercised() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 100);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_x);
+    let treasury_y_ata = setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+
+    let expiry_ts = setup.unix_timestamp() + 86_400;
+    initialize_covered_call(&mut setup, expiry_ts);
+    let buyer = new_buyer(&mut setup);
+    let holder = new_buyer(&mut setup);
+
+    // the strike isn't open to anyone before the option is sold
+    let ix = setup.complete_order_ix(&buyer.pubkey(), vec![]);
+    assert!(!setup.send(&[ix], &buyer));
+
+    // the maker raises the strike while the buy is in flight
+    let mut ix_data = vec![4]; // variant 4 for ChangeOrderAmounts
+    ix_data.extend_from_slice(&100_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&190_000u64.to_le_bytes());
+    let change_amounts_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new(mint_x, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: ix_data,
+    };
+    let maker_kp = setup.payer.insecure_clone();
+    assert!(setup.send(&[change_amounts_ix], &maker_kp));
+
+    let ix = buy_option_ix(&setup, &buyer.pubkey(), 100_000, 180_000, 5_000);
+    assert!(!setup.send(&[ix], &buyer));
+    let ix = buy_option_ix(&setup, &buyer.pubkey(), 100_000, 190_000, 5_000);
+    assert!(setup.send(&[ix], &buyer));
+    assert_eq!(setup.token_balance(&maker_y_ata), 4_950);
+    assert_eq!(setup.token_balance(&treasury_y_ata), 50);
+
+    let transfer_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(buyer.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new_readonly(holder.pubkey(), false),
+        ],
+        data: vec![56], // variant 56 for TransferOption
+    };
+    assert!(setup.send(&[transfer_ix], &buyer));
+
+    let ix = setup.complete_order_ix(&buyer.pubkey(), vec![]);
+    assert!(!setup.send(&[ix], &buyer));
+    let ix = setup.complete_order_ix(&holder.pubkey(), vec![]);
+    assert!(setup.send(&[ix], &holder));
+
+    let holder_x_ata = get_associated_token_address(&holder.pubkey(), &mint_x);
+    assert_eq!(setup.token_balance(&holder_x_ata), 99_000);
+    assert_eq!(setup.token_balance(&maker_y_ata), 4_950 + 188_100);
+}
+
+#[test]
+fn test_expired_covered_call_reclaimed() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+
+    let expiry_ts = setup.unix_timestamp() + 86_400;
+    initialize_covered_call(&mut setup, expiry_ts);
+    let buyer = new_buyer(&mut setup);
+    let ix = buy_option_ix(&setup, &buyer.pubkey(), 100_000, 180_000, 5_000);
+    assert!(setup.send(&[ix], &buyer));
+
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+    let reclaim_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![57], // variant 57 for ReclaimCoveredCall
+    };
+    let payer = setup.payer.insecure_clone();
+    // the holder's right runs until expiry
+    assert!(!setup.send(std::slice::from_ref(&reclaim_ix), &payer));
+
+    setup.set_unix_timestamp(expiry_ts);
+    assert!(setup.send(&[reclaim_ix], &payer));
+    assert_eq!(
+        setup.token_balance(&setup.maker_token_ata),
+        maker_balance + 100_000
+    );
+    assert_eq!(setup.token_balance(&maker_y_ata), 5_000);
+}
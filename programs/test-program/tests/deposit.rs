@@ -0,0 +1,264 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::state::{OrderStatus, SwapOrder};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+/// Opens an order for 100_000 X against 200_000 Y with a deposit window for `taker`.
+/// Returns the order's taker mint escrow.
+fn open_deposit_window(setup: &mut TestSetup, taker: &Keypair, deposit_end_ts: i64) -> Pubkey {
+    let maker = setup.payer.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_order(100_000, 200_000);
+    setup.svm.send_transaction(tx).unwrap();
+
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        1_000_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let order_pda = setup.order_pda;
+    let order_taker_ata = setup.create_ata(&order_pda, &mint_y);
+
+    let mut change_taker_data = vec![5]; // variant 5 for ChangeTaker
+    change_taker_data.extend_from_slice(&taker.pubkey().to_bytes());
+    let change_taker_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new_readonly(taker.pubkey(), false),
+        ],
+        data: change_taker_data,
+    };
+    let mut ix_data = vec![36]; // variant 36 for OpenDepositWindow
+    ix_data.extend_from_slice(&deposit_end_ts.to_le_bytes());
+    let open_window_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: ix_data,
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[change_taker_ix, open_window_ix],
+        Some(&maker),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    order_taker_ata
+}
+
+fn deposit_ix(
+    setup: &TestSetup,
+    taker: &Pubkey,
+    order_taker_ata: &Pubkey,
+    maker_amount: u64,
+    taker_amount: u64,
+) -> Instruction {
+    let mint_y = setup.taker_mint.pubkey();
+    let mut ix_data = vec![37]; // variant 37 for DepositTakerTokens
+    ix_data.extend_from_slice(&maker_amount.to_le_bytes());
+    ix_data.extend_from_slice(&taker_amount.to_le_bytes());
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(*taker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(*order_taker_ata, false),
+            AccountMeta::new(get_associated_token_address(taker, &mint_y), false),
+            AccountMeta::new_readonly(mint_y, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: ix_data,
+    }
+}
+
+/// Opens the deposit window and has the taker deposit. Returns the order's taker mint
+/// escrow.
+fn setup_taker_deposit(setup: &mut TestSetup, taker: &Keypair, deposit_end_ts: i64) -> Pubkey {
+    let order_taker_ata = open_deposit_window(setup, taker, deposit_end_ts);
+    let ix = deposit_ix(setup, &taker.pubkey(), &order_taker_ata, 100_000, 200_000);
+    assert!(setup.send(&[ix], taker));
+
+    order_taker_ata
+}
+
+#[test]
+fn test_keeper_settles_both_deposits() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let treasury_x_ata = setup.create_ata(&treasury_pda, &mint_x);
+    let treasury_y_ata = setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+
+    let taker = Keypair::new();
+    let deposit_end_ts = setup.unix_timestamp() + 3_600;
+    let order_taker_ata = setup_taker_deposit(&mut setup, &taker, deposit_end_ts);
+    let taker_x_ata = setup.create_ata(&taker.pubkey(), &mint_x);
+    assert_eq!(setup.token_balance(&order_taker_ata), 200_000);
+
+    // the maker can't pull its side while the taker's deposit waits
+    let close_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: vec![7], // variant 7 for CloseOrder
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&maker),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    let keeper = Keypair::new();
+    setup.svm.airdrop(&keeper.pubkey(), 1_000_000_000).unwrap();
+    let settle_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(keeper.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(order_taker_ata, false),
+            AccountMeta::new(maker_y_ata, false),
+            AccountMeta::new(taker_x_ata, false),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(treasury_x_ata, false),
+            AccountMeta::new(treasury_y_ata, false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(mint_y, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ],
+        data: vec![38], // variant 38 for SettleDeposits
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[settle_ix],
+        Some(&keeper.pubkey()),
+        &[&keeper],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&maker_y_ata), 200_000);
+    assert_eq!(setup.token_balance(&taker_x_ata), 100_000);
+    assert_eq!(setup.token_balance(&order_taker_ata), 0);
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 0);
+
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Filled);
+    assert!(!order.taker_deposited);
+}
+
+#[test]
+fn test_taker_withdraws_deposit_after_window() {
+    let mut setup = TestSetup::new();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let taker = Keypair::new();
+    let deposit_end_ts = setup.unix_timestamp() + 3_600;
+    let order_taker_ata = setup_taker_deposit(&mut setup, &taker, deposit_end_ts);
+    let taker_y_ata = get_associated_token_address(&taker.pubkey(), &mint_y);
+    assert_eq!(setup.token_balance(&taker_y_ata), 800_000);
+
+    let withdraw_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(taker.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(order_taker_ata, false),
+            AccountMeta::new(taker_y_ata, false),
+            AccountMeta::new_readonly(mint_y, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![39], // variant 39 for WithdrawTakerDeposit
+    };
+
+    // locked in until the window closes
+    let tx = Transaction::new_signed_with_payer(
+        std::slice::from_ref(&withdraw_ix),
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    setup.set_unix_timestamp(deposit_end_ts);
+    setup.svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[withdraw_ix],
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&taker_y_ata), 1_000_000);
+    assert_eq!(setup.token_balance(&order_taker_ata), 0);
+
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Open);
+    assert!(!order.taker_deposited);
+}
+
+#[test]
+fn test_deposit_rejected_after_reprice() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let taker = Keypair::new();
+    let deposit_end_ts = setup.unix_timestamp() + 3_600;
+    let order_taker_ata = open_deposit_window(&mut setup, &taker, deposit_end_ts);
+
+    // the maker raises the price while the deposit is in flight
+    let mut ix_data = vec![4]; // variant 4 for ChangeOrderAmounts
+    ix_data.extend_from_slice(&100_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&250_000u64.to_le_bytes());
+    let change_amounts_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new(setup.maker_mint.pubkey(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: ix_data,
+    };
+    let maker_kp = setup.payer.insecure_clone();
+    assert!(setup.send(&[change_amounts_ix], &maker_kp));
+
+    let ix = deposit_ix(&setup, &taker.pubkey(), &order_taker_ata, 100_000, 200_000);
+    assert!(!setup.send(&[ix], &taker));
+    assert_eq!(setup.token_balance(&order_taker_ata), 0);
+
+    let ix = deposit_ix(&setup, &taker.pubkey(), &order_taker_ata, 100_000, 250_000);
+    assert!(setup.send(&[ix], &taker));
+    assert_eq!(setup.token_balance(&order_taker_ata), 250_000);
+}
@@ -0,0 +1,146 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::state::OrderGroup;
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+#[test]
+fn test_order_group_fill_voids_other_members() {
+    let mut setup = TestSetup::new();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&setup.payer.pubkey(), 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_x);
+    setup.create_ata(&treasury_pda, &mint_y);
+
+    let maker = Keypair::new();
+    setup.svm.airdrop(&maker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.maker_mint,
+        1_000_000,
+        &maker.pubkey(),
+    )
+    .unwrap();
+    let maker_x_ata = get_associated_token_address(&maker.pubkey(), &mint_x);
+    let maker_y_ata = setup.create_ata(&maker.pubkey(), &mint_y);
+
+    // the same X offered at two price levels, only one may fill
+    let order_a = setup.initialize_order_for(&maker, &mint_x, &mint_y, 100_000, 200_000);
+    let order_b = setup.initialize_order_for(&maker, &mint_x, &mint_y, 100_000, 250_000);
+    assert_eq!(setup.token_balance(&maker_x_ata), 800_000);
+
+    let group_id = Keypair::new().pubkey();
+    let (group_pda, _) = Pubkey::find_program_address(
+        &[b"order_group", maker.pubkey().as_ref(), group_id.as_ref()],
+        &PROGRAM_KEY,
+    );
+    let mut ix_data = vec![31]; // variant 31 for InitializeOrderGroup
+    ix_data.extend_from_slice(group_id.as_ref());
+    let group_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker.pubkey(), true),
+            AccountMeta::new(group_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(order_a, false),
+            AccountMeta::new(order_b, false),
+        ],
+        data: ix_data,
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[group_ix],
+        Some(&maker.pubkey()),
+        &[&maker],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        1_000_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let taker_x_ata = setup.create_ata(&taker.pubkey(), &mint_x);
+
+    let fill_a_ix = TestSetup::complete_swap_ix(
+        &taker.pubkey(),
+        &order_a,
+        &maker_y_ata,
+        &mint_x,
+        &mint_y,
+        vec![AccountMeta::new(group_pda, false)],
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[fill_a_ix],
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+    assert_eq!(setup.token_balance(&taker_x_ata), 100_000);
+    assert_eq!(setup.token_balance(&maker_y_ata), 200_000);
+
+    let group_account = setup.svm.get_account(&group_pda).unwrap();
+    let group = OrderGroup::try_from_slice(&group_account.data).unwrap();
+    assert_eq!(group.filled_order, order_a);
+    assert_eq!(group.members, 2);
+
+    // the other level can no longer be filled
+    let fill_b_ix = TestSetup::complete_swap_ix(
+        &taker.pubkey(),
+        &order_b,
+        &maker_y_ata,
+        &mint_x,
+        &mint_y,
+        vec![AccountMeta::new(group_pda, false)],
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[fill_b_ix],
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    // anyone can send the voided escrow back to the maker
+    let refund_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(group_pda, false),
+            AccountMeta::new(order_b, false),
+            AccountMeta::new(get_associated_token_address(&order_b, &mint_x), false),
+            AccountMeta::new(maker.pubkey(), false),
+            AccountMeta::new(maker_x_ata, false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![32], // variant 32 for RefundGroupOrder
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[refund_ix],
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&maker_x_ata), 900_000);
+    assert!(setup.svm.get_account(&order_b).is_none());
+}
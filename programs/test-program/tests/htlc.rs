@@ -0,0 +1,153 @@
+use solana_sdk::{
+    hash::hashv,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use test_program::{utils::TestSetup, PROGRAM_KEY};
+
+const PREIMAGE: [u8; 32] = [7; 32];
+
+fn initialize_htlc_order(setup: &mut TestSetup, counterparty: &Pubkey, timelock_ts: i64) {
+    let mut ix_data = vec![46]; // variant 46 for InitializeHtlcOrder
+    ix_data.extend_from_slice(&100_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&1u64.to_le_bytes());
+    ix_data.extend_from_slice(&hashv(&[&PREIMAGE]).to_bytes());
+    ix_data.extend_from_slice(&timelock_ts.to_le_bytes());
+    let initialize_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(setup.payer.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new_readonly(setup.order_id, false),
+            AccountMeta::new_readonly(setup.maker_mint.pubkey(), false),
+            AccountMeta::new_readonly(setup.taker_mint.pubkey(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(*counterparty, false),
+        ],
+        data: ix_data,
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[initialize_ix],
+        Some(&setup.payer.pubkey()),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+}
+
+#[test]
+fn test_htlc_claimed_with_preimage() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let treasury_x_ata = setup.create_ata(&treasury_pda, &mint_x);
+
+    let counterparty = Keypair::new().pubkey();
+    let counterparty_x_ata = setup.create_ata(&counterparty, &mint_x);
+    let timelock_ts = setup.unix_timestamp() + 3_600;
+    initialize_htlc_order(&mut setup, &counterparty, timelock_ts);
+
+    // anyone can relay the preimage, the escrow still goes to the counterparty
+    let relayer = Keypair::new();
+    setup.svm.airdrop(&relayer.pubkey(), 1_000_000_000).unwrap();
+    let claim_ix = |preimage: [u8; 32]| {
+        let mut ix_data = vec![47]; // variant 47 for ClaimHtlc
+        ix_data.extend_from_slice(&preimage);
+        Instruction {
+            program_id: PROGRAM_KEY,
+            accounts: vec![
+                AccountMeta::new_readonly(relayer.pubkey(), true),
+                AccountMeta::new(setup.order_pda, false),
+                AccountMeta::new(setup.order_maker_token_ata, false),
+                AccountMeta::new(counterparty_x_ata, false),
+                AccountMeta::new_readonly(treasury_pda, false),
+                AccountMeta::new(treasury_x_ata, false),
+                AccountMeta::new_readonly(mint_x, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
+            data: ix_data,
+        }
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[claim_ix([8; 32])],
+        Some(&relayer.pubkey()),
+        &[&relayer],
+        setup.svm.latest_blockhash(),
+    );
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[claim_ix(PREIMAGE)],
+        Some(&relayer.pubkey()),
+        &[&relayer],
+        setup.svm.latest_blockhash(),
+    );
+    let meta = setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&counterparty_x_ata), 100_000);
+    assert!(meta
+        .logs
+        .iter()
+        .any(|log| log.starts_with("Program data: ")));
+}
+
+#[test]
+fn test_htlc_reclaimed_after_timelock() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+
+    let counterparty = Keypair::new().pubkey();
+    let timelock_ts = setup.unix_timestamp() + 3_600;
+    initialize_htlc_order(&mut setup, &counterparty, timelock_ts);
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+
+    let reclaim_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![48], // variant 48 for ReclaimHtlc
+    };
+    let tx = Transaction::new_signed_with_payer(
+        std::slice::from_ref(&reclaim_ix),
+        Some(&maker),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    setup.set_unix_timestamp(timelock_ts);
+    setup.svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[reclaim_ix],
+        Some(&maker),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(
+        setup.token_balance(&setup.maker_token_ata),
+        maker_balance + 100_000
+    );
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 0);
+}
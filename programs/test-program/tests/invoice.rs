@@ -0,0 +1,237 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+fn one_sided_order_tx(setup: &TestSetup, ix_data: Vec<u8>, taker: &Pubkey) -> Transaction {
+    let initialize_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(setup.payer.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new_readonly(setup.order_id, false),
+            AccountMeta::new_readonly(setup.maker_mint.pubkey(), false),
+            AccountMeta::new_readonly(setup.taker_mint.pubkey(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(*taker, false),
+        ],
+        data: ix_data,
+    };
+    Transaction::new_signed_with_payer(
+        &[initialize_ix],
+        Some(&setup.payer.pubkey()),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    )
+}
+
+#[test]
+fn test_invoice_paid_by_named_taker() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let treasury_y_ata = setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+
+    let payer = Keypair::new();
+    setup.svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        200_000,
+        &payer.pubkey(),
+    )
+    .unwrap();
+    let payer_y_ata = get_associated_token_address(&payer.pubkey(), &mint_y);
+
+    let mut ix_data = vec![58]; // variant 58 for InitializeInvoice
+    ix_data.extend_from_slice(&0u64.to_le_bytes());
+    let tx = one_sided_order_tx(&setup, ix_data, &payer.pubkey());
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    let mut ix_data = vec![58];
+    ix_data.extend_from_slice(&150_000u64.to_le_bytes());
+    let tx = one_sided_order_tx(&setup, ix_data, &payer.pubkey());
+    setup.svm.send_transaction(tx).unwrap();
+    // nothing is escrowed for an invoice
+    assert_eq!(setup.token_balance(&setup.maker_token_ata), maker_balance);
+
+    let pay_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(payer.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(payer_y_ata, false),
+            AccountMeta::new(maker_y_ata, false),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(treasury_y_ata, false),
+            AccountMeta::new_readonly(mint_y, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![59], // variant 59 for PayInvoice
+    };
+    let tx = Transaction::new_signed_with_payer(
+        std::slice::from_ref(&pay_ix),
+        Some(&payer.pubkey()),
+        &[&payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&maker_y_ata), 150_000);
+    assert_eq!(setup.token_balance(&payer_y_ata), 50_000);
+
+    // paid invoices can't be paid twice
+    setup.svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[pay_ix],
+        Some(&payer.pubkey()),
+        &[&payer],
+        setup.svm.latest_blockhash(),
+    );
+    assert!(setup.svm.send_transaction(tx).is_err());
+}
+
+#[test]
+fn test_drop_claimed_by_recipient() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let treasury_x_ata = setup.create_ata(&treasury_pda, &mint_x);
+
+    let recipient = Keypair::new();
+    setup
+        .svm
+        .airdrop(&recipient.pubkey(), 1_000_000_000)
+        .unwrap();
+    let recipient_x_ata = setup.create_ata(&recipient.pubkey(), &mint_x);
+
+    let mut ix_data = vec![60]; // variant 60 for InitializeDrop
+    ix_data.extend_from_slice(&100_000u64.to_le_bytes());
+    let tx = one_sided_order_tx(&setup, ix_data, &recipient.pubkey());
+    setup.svm.send_transaction(tx).unwrap();
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 100_000);
+
+    let claim_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(recipient.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(recipient_x_ata, false),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(treasury_x_ata, false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![61], // variant 61 for ClaimDrop
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[claim_ix],
+        Some(&recipient.pubkey()),
+        &[&recipient],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&recipient_x_ata), 100_000);
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 0);
+}
+
+#[test]
+fn test_unclaimed_drop_reclaimed_by_maker() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let treasury_x_ata = setup.create_ata(&treasury_pda, &mint_x);
+
+    let recipient = Keypair::new();
+    setup
+        .svm
+        .airdrop(&recipient.pubkey(), 1_000_000_000)
+        .unwrap();
+    let recipient_x_ata = setup.create_ata(&recipient.pubkey(), &mint_x);
+
+    let mut ix_data = vec![60]; // variant 60 for InitializeDrop
+    ix_data.extend_from_slice(&100_000u64.to_le_bytes());
+    let tx = one_sided_order_tx(&setup, ix_data, &recipient.pubkey());
+    setup.svm.send_transaction(tx).unwrap();
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+
+    let reclaim_ix = |authority: &Pubkey| Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![70], // variant 70 for ReclaimDrop
+    };
+    let recipient_reclaim_ix = reclaim_ix(&recipient.pubkey());
+    let maker_reclaim_ix = reclaim_ix(&maker);
+    assert!(!setup.send(&[recipient_reclaim_ix], &recipient));
+
+    // closing would strand the escrow
+    let close_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: vec![7], // variant 7 for CloseOrder
+    };
+    let maker_kp = setup.payer.insecure_clone();
+    assert!(!setup.send(&[close_ix], &maker_kp));
+
+    assert!(setup.send(std::slice::from_ref(&maker_reclaim_ix), &maker_kp));
+    assert_eq!(
+        setup.token_balance(&setup.maker_token_ata),
+        maker_balance + 100_000
+    );
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 0);
+
+    // nothing left to claim or reclaim
+    let claim_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(recipient.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(recipient_x_ata, false),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(treasury_x_ata, false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![61], // variant 61 for ClaimDrop
+    };
+    assert!(!setup.send(&[claim_ix], &recipient));
+    assert!(!setup.send(&[maker_reclaim_ix], &maker_kp));
+}
@@ -0,0 +1,143 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::{
+    math::fee::{calculate_token_fee, split_token_fee},
+    state::{OrderStatus, SwapOrder},
+};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+#[test]
+fn test_match_orders_with_partial_fill() {
+    let mut setup = TestSetup::new();
+    let fee = 100u16;
+    let cranker_share_bps = 5_000u16;
+
+    let tx = setup.initialize_treasury(&setup.payer.pubkey(), fee);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let mut ix_data = vec![19]; // variant 19 for UpdateCrankerShare
+    ix_data.extend_from_slice(&cranker_share_bps.to_le_bytes());
+    let update_share_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(setup.payer.pubkey(), true),
+            AccountMeta::new(treasury_pda, false),
+        ],
+        data: ix_data,
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[update_share_ix],
+        Some(&setup.payer.pubkey()),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+    let treasury_x_ata = setup.create_ata(&treasury_pda, &mint_x);
+    let treasury_y_ata = setup.create_ata(&treasury_pda, &mint_y);
+    let maker_a_ata = setup.create_ata(&setup.payer.pubkey(), &mint_y);
+
+    let maker_b = Keypair::new();
+    setup.svm.airdrop(&maker_b.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        1_000_000,
+        &maker_b.pubkey(),
+    )
+    .unwrap();
+    let maker_b_ata = setup.create_ata(&maker_b.pubkey(), &mint_x);
+
+    let cranker = Keypair::new();
+    setup.svm.airdrop(&cranker.pubkey(), 1_000_000_000).unwrap();
+    let cranker_ata = setup.create_ata(&cranker.pubkey(), &mint_y);
+
+    // A asks 2 Y per X, B pays 5 Y per X but only wants 60% of A's X
+    let tx = setup.initialize_order(100_000, 200_000);
+    setup.svm.send_transaction(tx).unwrap();
+    let order_b = setup.initialize_order_for(&maker_b, &mint_y, &mint_x, 300_000, 60_000);
+
+    let match_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(cranker.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(order_b, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(get_associated_token_address(&order_b, &mint_y), false),
+            AccountMeta::new(maker_a_ata, false),
+            AccountMeta::new(maker_b_ata, false),
+            AccountMeta::new(cranker_ata, false),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(treasury_x_ata, false),
+            AccountMeta::new(treasury_y_ata, false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(mint_y, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ],
+        data: vec![20], // variant 20 for MatchOrders
+    };
+    // Recurring orders only refill through CompleteSwap
+    let order_pda = setup.order_pda;
+    assert!(!setup.send_with_order_state(
+        &order_pda,
+        |order| order.funding_account = Pubkey::new_unique(),
+        std::slice::from_ref(&match_ix),
+        &cranker,
+    ));
+
+    // Chained orders re-list their proceeds through CompleteSwap
+    assert!(!setup.send_with_order_state(
+        &order_pda,
+        |order| order.follow_up_taker_mint = Pubkey::new_unique(),
+        std::slice::from_ref(&match_ix),
+        &cranker,
+    ));
+
+    // Vested fills go through the schedule CompleteSwap sets up
+    assert!(!setup.send_with_order_state(
+        &order_pda,
+        |order| order.vesting_duration = 86_400,
+        std::slice::from_ref(&match_ix),
+        &cranker,
+    ));
+
+    assert!(setup.send(&[match_ix], &cranker));
+
+    let surplus = 300_000 - 120_000;
+    let cranker_tip = calculate_token_fee(surplus, cranker_share_bps).unwrap() as u64;
+    let (x_after_fee, x_fee) = split_token_fee(60_000, fee).unwrap();
+    let (y_after_fee, y_fee) = split_token_fee(300_000 - cranker_tip, fee).unwrap();
+
+    assert_eq!(setup.token_balance(&maker_b_ata), x_after_fee);
+    assert_eq!(setup.token_balance(&maker_a_ata), y_after_fee);
+    assert_eq!(setup.token_balance(&cranker_ata), cranker_tip);
+    assert_eq!(setup.token_balance(&treasury_x_ata), x_fee);
+    assert_eq!(setup.token_balance(&treasury_y_ata), y_fee);
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 40_000);
+
+    // A keeps the unmatched remainder at its original price
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order_a = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order_a.status, OrderStatus::Open);
+    assert_eq!(order_a.maker_amount, 40_000);
+    assert_eq!(order_a.taker_amount, 80_000);
+
+    let order_account = setup.svm.get_account(&order_b).unwrap();
+    let order_b = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order_b.status, OrderStatus::Filled);
+    assert_eq!(order_b.maker_amount, 0);
+    assert_eq!(order_b.taker_amount, 0);
+}
@@ -0,0 +1,212 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+use splerg_p2p::state::{
+    LegacySwapOrder, LegacyTreasury, OrderKind, OrderStatus, SwapOrder, Treasury,
+};
+use test_program::{utils::TestSetup, PROGRAM_KEY};
+
+fn migrate_order_ix(payer: &Pubkey, order: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*order, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: vec![66], // variant 66 for MigrateOrder
+    }
+}
+
+#[test]
+fn test_legacy_order_migrated_to_current_layout() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let maker_kp = setup.payer.insecure_clone();
+    let taker = Keypair::new().pubkey();
+
+    // an order left behind by the original program
+    let (_, bump) = Pubkey::find_program_address(
+        &[
+            b"order",
+            setup.order_id.as_ref(),
+            maker.as_ref(),
+            setup.maker_mint.pubkey().as_ref(),
+            setup.taker_mint.pubkey().as_ref(),
+        ],
+        &PROGRAM_KEY,
+    );
+    let legacy = LegacySwapOrder {
+        maker,
+        taker,
+        id: setup.order_id,
+        maker_token_mint: setup.maker_mint.pubkey(),
+        taker_token_mint: setup.taker_mint.pubkey(),
+        maker_amount: 100_000,
+        taker_amount: 200_000,
+        bump,
+    };
+    let data = borsh::to_vec(&legacy).unwrap();
+    assert_eq!(data.len(), LegacySwapOrder::LEN);
+    setup
+        .svm
+        .set_account(
+            setup.order_pda,
+            Account {
+                lamports: setup.svm.minimum_balance_for_rent_exemption(data.len()),
+                data,
+                owner: PROGRAM_KEY,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    let close_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: vec![7], // variant 7 for CloseOrder
+    };
+    assert!(!setup.send(std::slice::from_ref(&close_ix), &maker_kp));
+
+    // anyone can pay to migrate
+    let payer = Keypair::new();
+    setup.svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let ix = migrate_order_ix(&payer.pubkey(), &setup.order_pda);
+    assert!(setup.send(std::slice::from_ref(&ix), &payer));
+
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    assert_eq!(order_account.data.len(), SwapOrder::LEN);
+    assert_eq!(
+        order_account.lamports,
+        setup.svm.minimum_balance_for_rent_exemption(SwapOrder::LEN)
+    );
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.version, SwapOrder::VERSION);
+    assert_eq!(order.kind, OrderKind::Fixed);
+    assert_eq!(order.status, OrderStatus::Open);
+    assert_eq!(order.taker, taker);
+    assert_eq!(order.maker_amount, 100_000);
+    assert_eq!(order.taker_amount, 200_000);
+
+    // a current order can't be migrated again
+    let ix = migrate_order_ix(&payer.pubkey(), &setup.order_pda);
+    assert!(!setup.send(std::slice::from_ref(&ix), &payer));
+
+    assert!(setup.send(std::slice::from_ref(&close_ix), &maker_kp));
+}
+
+#[test]
+fn test_migrate_rejects_foreign_account() {
+    let mut setup = TestSetup::new();
+    let payer = setup.payer.insecure_clone();
+
+    // right length, but not at the order's PDA
+    let legacy = LegacySwapOrder {
+        maker: payer.pubkey(),
+        taker: Pubkey::default(),
+        id: setup.order_id,
+        maker_token_mint: setup.maker_mint.pubkey(),
+        taker_token_mint: setup.taker_mint.pubkey(),
+        maker_amount: 1,
+        taker_amount: 1,
+        bump: 255,
+    };
+    let mut data = Vec::new();
+    legacy.serialize(&mut data).unwrap();
+    let impostor = Keypair::new().pubkey();
+    setup
+        .svm
+        .set_account(
+            impostor,
+            Account {
+                lamports: setup.svm.minimum_balance_for_rent_exemption(data.len()),
+                data,
+                owner: PROGRAM_KEY,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    let ix = migrate_order_ix(&payer.pubkey(), &impostor);
+    assert!(!setup.send(std::slice::from_ref(&ix), &payer));
+}
+
+#[test]
+fn test_legacy_treasury_migrated_to_current_layout() {
+    let mut setup = TestSetup::new();
+    let authority = setup.payer.insecure_clone();
+    let (treasury_pda, bump) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let legacy = LegacyTreasury {
+        authority: authority.pubkey(),
+        fee: 100,
+        bump,
+    };
+    let data = borsh::to_vec(&legacy).unwrap();
+    assert_eq!(data.len(), LegacyTreasury::LEN);
+    setup
+        .svm
+        .set_account(
+            treasury_pda,
+            Account {
+                lamports: setup.svm.minimum_balance_for_rent_exemption(data.len()),
+                data,
+                owner: PROGRAM_KEY,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    let mut ix_data = vec![19]; // variant 19 for UpdateCrankerShare
+    ix_data.extend_from_slice(&2_500u16.to_le_bytes());
+    let update_share_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(authority.pubkey(), true),
+            AccountMeta::new(treasury_pda, false),
+        ],
+        data: ix_data,
+    };
+    assert!(!setup.send(std::slice::from_ref(&update_share_ix), &authority));
+
+    let payer = Keypair::new();
+    setup.svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+    let migrate_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(treasury_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: vec![68], // variant 68 for MigrateTreasury
+    };
+    assert!(setup.send(std::slice::from_ref(&migrate_ix), &payer));
+
+    let treasury_account = setup.svm.get_account(&treasury_pda).unwrap();
+    assert_eq!(treasury_account.data.len(), Treasury::LEN);
+    assert_eq!(
+        treasury_account.lamports,
+        setup.svm.minimum_balance_for_rent_exemption(Treasury::LEN)
+    );
+    let treasury = Treasury::try_from_slice(&treasury_account.data).unwrap();
+    assert_eq!(treasury, Treasury::new(authority.pubkey(), 100, bump));
+
+    // a current treasury can't be migrated again
+    assert!(!setup.send(std::slice::from_ref(&migrate_ix), &payer));
+
+    assert!(setup.send(std::slice::from_ref(&update_share_ix), &authority));
+    let treasury_account = setup.svm.get_account(&treasury_pda).unwrap();
+    let treasury = Treasury::try_from_slice(&treasury_account.data).unwrap();
+    assert_eq!(treasury.cranker_share_bps, 2_500);
+}
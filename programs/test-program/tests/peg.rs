@@ -0,0 +1,115 @@
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::oracle::{pack_price, OraclePrice, PYTH_PROGRAM_ID};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+#[test]
+fn test_pegged_order_fills_at_oracle_price() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_x);
+    setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+
+    // stand-in price account quoting 2.5 Y per X
+    let now = setup.unix_timestamp();
+    let data = pack_price(&OraclePrice {
+        price: 250_000_000,
+        conf: 100_000,
+        expo: -8,
+        publish_time: now,
+    });
+    let oracle = Keypair::new().pubkey();
+    // the same quote from an account the maker could write to
+    let fake_oracle = Keypair::new().pubkey();
+    for (account, owner) in [(oracle, PYTH_PROGRAM_ID), (fake_oracle, maker)] {
+        setup
+            .svm
+            .set_account(
+                account,
+                Account {
+                    lamports: setup.svm.minimum_balance_for_rent_exemption(data.len()),
+                    data: data.clone(),
+                    owner,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+    }
+
+    // the stale ratio is ignored once pegged at a 1% premium
+    let tx = setup.initialize_order(100_000, 1);
+    setup.svm.send_transaction(tx).unwrap();
+    let order_pda = setup.order_pda;
+    let peg_ix = |oracle: Pubkey| {
+        let mut ix_data = vec![43]; // variant 43 for SetOraclePeg
+        ix_data.extend_from_slice(&100i16.to_le_bytes());
+        ix_data.extend_from_slice(&60i64.to_le_bytes());
+        ix_data.extend_from_slice(&50u16.to_le_bytes());
+        Instruction {
+            program_id: PROGRAM_KEY,
+            accounts: vec![
+                AccountMeta::new_readonly(maker, true),
+                AccountMeta::new(order_pda, false),
+                AccountMeta::new_readonly(oracle, false),
+            ],
+            data: ix_data,
+        }
+    };
+    let maker_kp = setup.payer.insecure_clone();
+    let ix = peg_ix(fake_oracle);
+    assert!(!setup.send(&[ix], &maker_kp));
+    let ix = peg_ix(oracle);
+    assert!(setup.send(&[ix], &maker_kp));
+
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        1_000_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let taker_x_ata = setup.create_ata(&taker.pubkey(), &mint_x);
+    let taker_y_ata = get_associated_token_address(&taker.pubkey(), &mint_y);
+
+    let complete_swap_ix = setup.complete_order_ix(
+        &taker.pubkey(),
+        vec![AccountMeta::new_readonly(oracle, false)],
+    );
+    let pegged_swap_ix = |max_taker_amount: u64| {
+        let mut ix = complete_swap_ix.clone();
+        ix.data = vec![69]; // variant 69 for CompletePeggedSwap
+        ix.data.extend_from_slice(&max_taker_amount.to_le_bytes());
+        ix
+    };
+
+    // a price older than a minute is refused
+    setup.set_unix_timestamp(now + 61);
+    assert!(!setup.send(&[pegged_swap_ix(252_500)], &taker));
+
+    setup.set_unix_timestamp(now + 60);
+    // the taker has to cap the oracle price
+    assert!(!setup.send(std::slice::from_ref(&complete_swap_ix), &taker));
+    assert!(!setup.send(&[pegged_swap_ix(252_499)], &taker));
+    assert!(setup.send(&[pegged_swap_ix(252_500)], &taker));
+
+    assert_eq!(setup.token_balance(&taker_x_ata), 100_000);
+    assert_eq!(setup.token_balance(&maker_y_ata), 252_500);
+    assert_eq!(setup.token_balance(&taker_y_ata), 1_000_000 - 252_500);
+}
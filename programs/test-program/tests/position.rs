@@ -0,0 +1,267 @@
+use borsh::BorshDeserialize;
+use solana_program::program_pack::Pack;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account,
+};
+use splerg_p2p::state::SwapOrder;
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+struct Accounts {
+    position_mint: Pubkey,
+}
+
+fn position_ata(owner: &Pubkey, position_mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, position_mint, &spl_token_2022::id())
+}
+
+/// Tokenized order selling 100_000 X for 180_000 Y
+fn setup_order(setup: &mut TestSetup) -> Accounts {
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_x);
+    setup.create_ata(&treasury_pda, &mint_y);
+
+    let (position_mint, _) =
+        Pubkey::find_program_address(&[b"position", setup.order_pda.as_ref()], &PROGRAM_KEY);
+
+    let mut ix_data = vec![3]; // variant 3 for InitializeOrder
+    ix_data.extend_from_slice(&100_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&180_000u64.to_le_bytes());
+    let initialize_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new_readonly(setup.order_id, false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(mint_y, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(position_mint, false),
+            AccountMeta::new(position_ata(&maker, &position_mint), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+        data: ix_data,
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[initialize_ix],
+        Some(&maker),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    Accounts { position_mint }
+}
+
+fn new_user(setup: &mut TestSetup) -> Keypair {
+    let user = Keypair::new();
+    setup.svm.airdrop(&user.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        200_000,
+        &user.pubkey(),
+    )
+    .unwrap();
+    let mint_x = setup.maker_mint.pubkey();
+    setup.create_ata(&user.pubkey(), &mint_x);
+    user
+}
+
+fn transfer_position_ixs(
+    setup: &TestSetup,
+    accounts: &Accounts,
+    from: &Pubkey,
+    to: &Pubkey,
+) -> Vec<Instruction> {
+    vec![
+        create_associated_token_account(
+            &setup.payer.pubkey(),
+            to,
+            &accounts.position_mint,
+            &spl_token_2022::id(),
+        ),
+        spl_token_2022::instruction::transfer_checked(
+            &spl_token_2022::id(),
+            &position_ata(from, &accounts.position_mint),
+            &accounts.position_mint,
+            &position_ata(to, &accounts.position_mint),
+            from,
+            &[],
+            1,
+            0,
+        )
+        .unwrap(),
+    ]
+}
+
+fn change_amounts_ix(
+    setup: &TestSetup,
+    accounts: &Accounts,
+    authority: &Pubkey,
+    maker_amount: u64,
+    taker_amount: u64,
+) -> Instruction {
+    let mut ix_data = vec![4]; // variant 4 for ChangeOrderAmounts
+    ix_data.extend_from_slice(&maker_amount.to_le_bytes());
+    ix_data.extend_from_slice(&taker_amount.to_le_bytes());
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(
+                get_associated_token_address(authority, &setup.maker_mint.pubkey()),
+                false,
+            ),
+            AccountMeta::new(setup.maker_mint.pubkey(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(position_ata(authority, &accounts.position_mint), false),
+        ],
+        data: ix_data,
+    }
+}
+
+fn close_order_ix(setup: &TestSetup, accounts: &Accounts, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new_readonly(position_ata(authority, &accounts.position_mint), false),
+        ],
+        data: vec![7], // variant 7 for CloseOrder
+    }
+}
+
+fn complete_swap_ix(
+    setup: &TestSetup,
+    accounts: &Accounts,
+    taker: &Pubkey,
+    holder: &Pubkey,
+) -> Instruction {
+    let mint_y = setup.taker_mint.pubkey();
+    TestSetup::complete_swap_ix(
+        taker,
+        &setup.order_pda,
+        &get_associated_token_address(holder, &mint_y),
+        &setup.maker_mint.pubkey(),
+        &mint_y,
+        vec![AccountMeta::new_readonly(
+            position_ata(holder, &accounts.position_mint),
+            false,
+        )],
+    )
+}
+
+#[test]
+fn test_position_holder_reprices_and_receives_proceeds() {
+    let mut setup = TestSetup::new();
+    let accounts = setup_order(&mut setup);
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.position_mint, accounts.position_mint);
+    let maker_position = position_ata(&maker, &accounts.position_mint);
+    let position_account = setup.svm.get_account(&maker_position).unwrap();
+    let position = spl_token_2022::state::Account::unpack(&position_account.data).unwrap();
+    assert_eq!(position.amount, 1);
+
+    let buyer = new_user(&mut setup);
+    let taker = new_user(&mut setup);
+    let maker_kp = setup.payer.insecure_clone();
+    let ixs = transfer_position_ixs(&setup, &accounts, &maker, &buyer.pubkey());
+    assert!(setup.send(&ixs, &maker_kp));
+
+    // the maker sold the position, and its rights with it
+    let ix = change_amounts_ix(&setup, &accounts, &maker, 60_000, 120_000);
+    assert!(!setup.send(std::slice::from_ref(&ix), &maker_kp));
+
+    let buyer_x_ata = get_associated_token_address(&buyer.pubkey(), &mint_x);
+    let ix = change_amounts_ix(&setup, &accounts, &buyer.pubkey(), 60_000, 120_000);
+    assert!(setup.send(std::slice::from_ref(&ix), &buyer));
+    assert_eq!(setup.token_balance(&buyer_x_ata), 40_000);
+
+    // proceeds can't be routed to the original maker
+    let mint_y = setup.taker_mint.pubkey();
+    setup.create_ata(&maker, &mint_y);
+    let ix = complete_swap_ix(&setup, &accounts, &taker.pubkey(), &maker);
+    assert!(!setup.send(std::slice::from_ref(&ix), &taker));
+
+    // a forfeited reservation bond would go to the original maker
+    let order_pda = setup.order_pda;
+    let order_y_ata = setup.create_ata(&order_pda, &mint_y);
+    let mut ix_data = vec![52]; // variant 52 for ReserveOrder
+    ix_data.extend_from_slice(&10u64.to_le_bytes());
+    let reserve_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(taker.pubkey(), true),
+            AccountMeta::new(order_pda, false),
+            AccountMeta::new(
+                get_associated_token_address(&taker.pubkey(), &mint_y),
+                false,
+            ),
+            AccountMeta::new(order_y_ata, false),
+            AccountMeta::new_readonly(mint_y, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: ix_data,
+    };
+    assert!(!setup.send(&[reserve_ix], &taker));
+
+    let buyer_y_ata = get_associated_token_address(&buyer.pubkey(), &setup.taker_mint.pubkey());
+    let buyer_y_balance = setup.token_balance(&buyer_y_ata);
+    let ix = complete_swap_ix(&setup, &accounts, &taker.pubkey(), &buyer.pubkey());
+    assert!(setup.send(std::slice::from_ref(&ix), &taker));
+    assert_eq!(setup.token_balance(&buyer_y_ata), buyer_y_balance + 120_000);
+}
+
+#[test]
+fn test_position_holder_closes_order() {
+    let mut setup = TestSetup::new();
+    let accounts = setup_order(&mut setup);
+    let maker = setup.payer.pubkey();
+    let maker_kp = setup.payer.insecure_clone();
+
+    // the position token account has to come along
+    let ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: vec![7], // variant 7 for CloseOrder
+    };
+    assert!(!setup.send(std::slice::from_ref(&ix), &maker_kp));
+
+    let ix = close_order_ix(&setup, &accounts, &maker);
+    assert!(setup.send(std::slice::from_ref(&ix), &maker_kp));
+    assert!(setup
+        .svm
+        .get_account(&setup.order_pda)
+        .is_none_or(|account| account.lamports == 0));
+}
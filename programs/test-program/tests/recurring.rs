@@ -0,0 +1,145 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::state::{OrderStatus, SwapOrder};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+fn order_state(setup: &TestSetup) -> SwapOrder {
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    SwapOrder::try_from_slice(&order_account.data).unwrap()
+}
+
+#[test]
+fn test_recurring_order_refills_after_fill() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let maker_mint = setup.maker_mint.pubkey();
+    let taker_mint = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let treasury_maker_ata = setup.create_ata(&treasury_pda, &maker_mint);
+    let treasury_taker_ata = setup.create_ata(&treasury_pda, &taker_mint);
+    let maker_taker_ata = setup.create_ata(&maker, &taker_mint);
+
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        1_000_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let taker_ata = get_associated_token_address(&taker.pubkey(), &taker_mint);
+    let taker_maker_ata = setup.create_ata(&taker.pubkey(), &maker_mint);
+
+    // One refill, funded from the same account the order was escrowed from
+    let maker_amount = 100_000u64;
+    let mut ix_data = vec![29]; // variant 29 for InitializeRecurringOrder
+    ix_data.extend_from_slice(&maker_amount.to_le_bytes());
+    ix_data.extend_from_slice(&200_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&1u64.to_le_bytes());
+    ix_data.extend_from_slice(&1_000_000u64.to_le_bytes());
+
+    let recurring_order_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new_readonly(setup.order_id, false),
+            AccountMeta::new_readonly(maker_mint, false),
+            AccountMeta::new_readonly(taker_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(setup.maker_token_ata, false),
+        ],
+        data: ix_data,
+    };
+    let approve_ix = spl_token::instruction::approve(
+        &spl_token::id(),
+        &setup.maker_token_ata,
+        &setup.order_pda,
+        &maker,
+        &[],
+        maker_amount,
+    )
+    .unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[recurring_order_ix, approve_ix],
+        Some(&maker),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+
+    let complete_swap_tx = |setup: &TestSetup| {
+        let complete_swap_ix = Instruction {
+            program_id: PROGRAM_KEY,
+            accounts: vec![
+                AccountMeta::new(taker.pubkey(), true),
+                AccountMeta::new(setup.order_pda, false),
+                AccountMeta::new(maker_taker_ata, false),
+                AccountMeta::new(taker_ata, false),
+                AccountMeta::new(taker_maker_ata, false),
+                AccountMeta::new(setup.order_maker_token_ata, false),
+                AccountMeta::new(treasury_pda, false),
+                AccountMeta::new(treasury_maker_ata, false),
+                AccountMeta::new(treasury_taker_ata, false),
+                AccountMeta::new(maker_mint, false),
+                AccountMeta::new(taker_mint, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(spl_token_2022::id(), false),
+                AccountMeta::new(setup.maker_token_ata, false),
+            ],
+            data: vec![6], // variant 6 for CompleteSwap
+        };
+        Transaction::new_signed_with_payer(
+            &[complete_swap_ix],
+            Some(&taker.pubkey()),
+            &[&taker],
+            setup.svm.latest_blockhash(),
+        )
+    };
+
+    // First fill re-escrows the order from the funding account
+    let tx = complete_swap_tx(&setup);
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&taker_maker_ata), maker_amount);
+    assert_eq!(
+        setup.token_balance(&setup.order_maker_token_ata),
+        maker_amount
+    );
+    assert_eq!(
+        setup.token_balance(&setup.maker_token_ata),
+        maker_balance - maker_amount
+    );
+    let order = order_state(&setup);
+    assert_eq!(order.status, OrderStatus::Open);
+    assert_eq!(order.refills_remaining, 0);
+    assert_eq!(order.refill_cap, 1_000_000 - maker_amount);
+
+    // Second fill exhausts the refills
+    setup.svm.expire_blockhash();
+    let tx = complete_swap_tx(&setup);
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&taker_maker_ata), 2 * maker_amount);
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 0);
+    assert_eq!(setup.token_balance(&maker_taker_ata), 400_000);
+    assert_eq!(order_state(&setup).status, OrderStatus::Filled);
+}
@@ -0,0 +1,309 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::state::{MakerAccount, OrderStatus, SwapOrder};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+fn registered_order_tx(
+    setup: &mut TestSetup,
+    maker_account: &Pubkey,
+    nonce: u64,
+    maker_amount: u64,
+    taker_amount: u64,
+) -> (Transaction, Pubkey) {
+    let maker = setup.payer.pubkey();
+    let maker_mint = setup.maker_mint.pubkey();
+    let taker_mint = setup.taker_mint.pubkey();
+    let order_id = MakerAccount::order_id(&maker, nonce);
+
+    let (order, _) = Pubkey::find_program_address(
+        &[
+            b"order",
+            order_id.as_ref(),
+            maker.as_ref(),
+            maker_mint.as_ref(),
+            taker_mint.as_ref(),
+        ],
+        &PROGRAM_KEY,
+    );
+    let order_ata = setup.create_ata(&order, &maker_mint);
+
+    let mut ix_data = vec![27]; // variant 27 for InitializeRegisteredOrder
+    ix_data.extend_from_slice(&maker_amount.to_le_bytes());
+    ix_data.extend_from_slice(&taker_amount.to_le_bytes());
+
+    let registered_order_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(order, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new(order_ata, false),
+            AccountMeta::new_readonly(order_id, false),
+            AccountMeta::new_readonly(maker_mint, false),
+            AccountMeta::new_readonly(taker_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(*maker_account, false),
+        ],
+        data: ix_data,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[registered_order_ix],
+        Some(&maker),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    (tx, order)
+}
+
+fn initialize_maker_account(setup: &mut TestSetup) -> Pubkey {
+    let maker = setup.payer.pubkey();
+    let (maker_account, _) =
+        Pubkey::find_program_address(&[b"maker", maker.as_ref()], &PROGRAM_KEY);
+    let init_maker_account_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(maker_account, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: vec![26], // variant 26 for InitializeMakerAccount
+    };
+    let payer = setup.payer.insecure_clone();
+    assert!(setup.send(&[init_maker_account_ix], &payer));
+    maker_account
+}
+
+fn cancel_all_ix(setup: &TestSetup, maker_account: &Pubkey, orders: &[Pubkey]) -> Instruction {
+    let maker = setup.payer.pubkey();
+    let maker_mint = setup.maker_mint.pubkey();
+    let mut accounts = vec![
+        AccountMeta::new(maker, true),
+        AccountMeta::new(*maker_account, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_token_2022::id(), false),
+    ];
+    for order in orders {
+        accounts.extend([
+            AccountMeta::new(*order, false),
+            AccountMeta::new(get_associated_token_address(order, &maker_mint), false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new_readonly(maker_mint, false),
+        ]);
+    }
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts,
+        data: vec![28], // variant 28 for CancelAllOrders
+    }
+}
+
+fn maker_account_state(setup: &TestSetup, maker_account: &Pubkey) -> MakerAccount {
+    let account = setup.svm.get_account(maker_account).unwrap();
+    MakerAccount::try_from_slice(&account.data).unwrap()
+}
+
+#[test]
+fn test_registered_orders_and_cancel_all() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+
+    let maker_account = initialize_maker_account(&mut setup);
+
+    // Orders must use the next nonce
+    let (tx, _) = registered_order_tx(&mut setup, &maker_account, 1, 10_000, 20_000);
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    let mut orders = vec![];
+    for nonce in 0..3 {
+        let (tx, order) = registered_order_tx(&mut setup, &maker_account, nonce, 10_000, 20_000);
+        setup.svm.send_transaction(tx).unwrap();
+        orders.push(order);
+    }
+
+    let registry = maker_account_state(&setup, &maker_account);
+    assert_eq!(registry.nonce, 3);
+    assert_eq!(registry.open_orders, 3);
+    assert_eq!(registry.escrowed_amount, 30_000);
+
+    let order_account = setup.svm.get_account(&orders[1]).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.id, MakerAccount::order_id(&maker, 1));
+
+    let ix = cancel_all_ix(&setup, &maker_account, &orders);
+    let payer = setup.payer.insecure_clone();
+    assert!(setup.send(&[ix], &payer));
+
+    assert_eq!(setup.token_balance(&setup.maker_token_ata), maker_balance);
+    for order in &orders {
+        assert!(setup.svm.get_account(order).is_none());
+    }
+
+    let registry = maker_account_state(&setup, &maker_account);
+    assert_eq!(registry.nonce, 3);
+    assert_eq!(registry.open_orders, 0);
+    assert_eq!(registry.escrowed_amount, 0);
+}
+
+#[test]
+fn test_registry_counters_follow_fills_and_closes() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let maker_kp = setup.payer.insecure_clone();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let treasury_x_ata = setup.create_ata(&treasury_pda, &mint_x);
+    let treasury_y_ata = setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+
+    let maker_account = initialize_maker_account(&mut setup);
+    let mut orders = vec![];
+    for nonce in 0..3 {
+        let (tx, order) = registered_order_tx(&mut setup, &maker_account, nonce, 10_000, 20_000);
+        setup.svm.send_transaction(tx).unwrap();
+        orders.push(order);
+    }
+    let registry_meta = AccountMeta::new(maker_account, false);
+
+    // repricing moves the escrow
+    let mut ix_data = vec![4]; // variant 4 for ChangeOrderAmounts
+    ix_data.extend_from_slice(&15_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&30_000u64.to_le_bytes());
+    let mut change_amounts_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(orders[0], false),
+            AccountMeta::new(get_associated_token_address(&orders[0], &mint_x), false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new(mint_x, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: ix_data,
+    };
+    assert!(!setup.send(std::slice::from_ref(&change_amounts_ix), &maker_kp));
+    change_amounts_ix.accounts.push(registry_meta.clone());
+    assert!(setup.send(&[change_amounts_ix], &maker_kp));
+    let registry = maker_account_state(&setup, &maker_account);
+    assert_eq!(registry.open_orders, 3);
+    assert_eq!(registry.escrowed_amount, 35_000);
+
+    // a partial fill leaves the order open with less escrowed
+    let maker_b = Keypair::new();
+    setup.svm.airdrop(&maker_b.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        10_000,
+        &maker_b.pubkey(),
+    )
+    .unwrap();
+    let maker_b_ata = setup.create_ata(&maker_b.pubkey(), &mint_x);
+    let order_b = setup.initialize_order_for(&maker_b, &mint_y, &mint_x, 10_000, 5_000);
+    let cranker = Keypair::new();
+    setup.svm.airdrop(&cranker.pubkey(), 1_000_000_000).unwrap();
+    let cranker_ata = setup.create_ata(&cranker.pubkey(), &mint_y);
+    let mut match_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(cranker.pubkey(), true),
+            AccountMeta::new(orders[1], false),
+            AccountMeta::new(order_b, false),
+            AccountMeta::new(get_associated_token_address(&orders[1], &mint_x), false),
+            AccountMeta::new(get_associated_token_address(&order_b, &mint_y), false),
+            AccountMeta::new(maker_y_ata, false),
+            AccountMeta::new(maker_b_ata, false),
+            AccountMeta::new(cranker_ata, false),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(treasury_x_ata, false),
+            AccountMeta::new(treasury_y_ata, false),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(mint_y, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ],
+        data: vec![20], // variant 20 for MatchOrders
+    };
+    assert!(!setup.send(std::slice::from_ref(&match_ix), &cranker));
+    match_ix.accounts.push(registry_meta.clone());
+    assert!(setup.send(&[match_ix], &cranker));
+    let order_account = setup.svm.get_account(&orders[1]).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Open);
+    assert_eq!(order.maker_amount, 5_000);
+    let registry = maker_account_state(&setup, &maker_account);
+    assert_eq!(registry.open_orders, 3);
+    assert_eq!(registry.escrowed_amount, 30_000);
+
+    // a full fill takes the order off the counters
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        30_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    setup.create_ata(&taker.pubkey(), &mint_x);
+    let ix = TestSetup::complete_swap_ix(
+        &taker.pubkey(),
+        &orders[0],
+        &maker_y_ata,
+        &mint_x,
+        &mint_y,
+        vec![],
+    );
+    assert!(!setup.send(&[ix], &taker));
+    let ix = TestSetup::complete_swap_ix(
+        &taker.pubkey(),
+        &orders[0],
+        &maker_y_ata,
+        &mint_x,
+        &mint_y,
+        vec![registry_meta.clone()],
+    );
+    assert!(setup.send(&[ix], &taker));
+    let registry = maker_account_state(&setup, &maker_account);
+    assert_eq!(registry.open_orders, 2);
+    assert_eq!(registry.escrowed_amount, 15_000);
+
+    // closing the filled order leaves them as they are
+    let close_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(orders[0], false),
+            registry_meta,
+        ],
+        data: vec![7], // variant 7 for CloseOrder
+    };
+    assert!(setup.send(&[close_ix], &maker_kp));
+    let registry = maker_account_state(&setup, &maker_account);
+    assert_eq!(registry.open_orders, 2);
+    assert_eq!(registry.escrowed_amount, 15_000);
+
+    let ix = cancel_all_ix(&setup, &maker_account, &orders[1..]);
+    assert!(setup.send(&[ix], &maker_kp));
+    let registry = maker_account_state(&setup, &maker_account);
+    assert_eq!(registry.open_orders, 0);
+    assert_eq!(registry.escrowed_amount, 0);
+}
@@ -0,0 +1,154 @@
+use solana_sdk::{
+    clock::Clock,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+struct Accounts {
+    maker_y_ata: Pubkey,
+    order_y_ata: Pubkey,
+}
+
+/// Order selling 100_000 X for 180_000 Y, reservable against a 1_800 Y bond
+fn setup_order(setup: &mut TestSetup) -> Accounts {
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_x);
+    setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+
+    let tx = setup.initialize_order(100_000, 180_000);
+    setup.svm.send_transaction(tx).unwrap();
+    let order_pda = setup.order_pda;
+    let order_y_ata = setup.create_ata(&order_pda, &mint_y);
+
+    Accounts {
+        maker_y_ata,
+        order_y_ata,
+    }
+}
+
+fn new_taker(setup: &mut TestSetup) -> Keypair {
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        200_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let mint_x = setup.maker_mint.pubkey();
+    setup.create_ata(&taker.pubkey(), &mint_x);
+    taker
+}
+
+fn reserve_ix(setup: &TestSetup, accounts: &Accounts, taker: &Pubkey, slots: u64) -> Instruction {
+    let mut ix_data = vec![52]; // variant 52 for ReserveOrder
+    ix_data.extend_from_slice(&slots.to_le_bytes());
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(*taker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(
+                get_associated_token_address(taker, &setup.taker_mint.pubkey()),
+                false,
+            ),
+            AccountMeta::new(accounts.order_y_ata, false),
+            AccountMeta::new_readonly(setup.taker_mint.pubkey(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: ix_data,
+    }
+}
+
+fn complete_swap_ix(
+    setup: &TestSetup,
+    accounts: &Accounts,
+    taker: &Pubkey,
+    reserved: bool,
+) -> Instruction {
+    let trailing_accounts = if reserved {
+        vec![AccountMeta::new(accounts.order_y_ata, false)]
+    } else {
+        vec![]
+    };
+    setup.complete_order_ix(taker, trailing_accounts)
+}
+
+fn warp_slots(setup: &mut TestSetup, slots: u64) {
+    let slot = setup.svm.get_sysvar::<Clock>().slot;
+    setup.svm.warp_to_slot(slot + slots);
+}
+
+#[test]
+fn test_reserved_order_filled_by_holder() {
+    let mut setup = TestSetup::new();
+    let accounts = setup_order(&mut setup);
+    let holder = new_taker(&mut setup);
+    let racer = new_taker(&mut setup);
+    let holder_y_ata = get_associated_token_address(&holder.pubkey(), &setup.taker_mint.pubkey());
+
+    let ix = reserve_ix(&setup, &accounts, &holder.pubkey(), 10);
+    assert!(setup.send(&[ix], &holder));
+    assert_eq!(setup.token_balance(&holder_y_ata), 200_000 - 1_800);
+    assert_eq!(setup.token_balance(&accounts.order_y_ata), 1_800);
+
+    // nobody else can take or re-reserve the order during the window
+    let ix = reserve_ix(&setup, &accounts, &racer.pubkey(), 10);
+    assert!(!setup.send(&[ix], &racer));
+    let ix = complete_swap_ix(&setup, &accounts, &racer.pubkey(), true);
+    assert!(!setup.send(&[ix], &racer));
+
+    warp_slots(&mut setup, 5);
+    let ix = complete_swap_ix(&setup, &accounts, &holder.pubkey(), true);
+    assert!(setup.send(&[ix], &holder));
+
+    assert_eq!(setup.token_balance(&holder_y_ata), 200_000 - 180_000);
+    assert_eq!(setup.token_balance(&accounts.maker_y_ata), 180_000);
+    assert_eq!(setup.token_balance(&accounts.order_y_ata), 0);
+}
+
+#[test]
+fn test_lapsed_reservation_forfeits_bond() {
+    let mut setup = TestSetup::new();
+    let accounts = setup_order(&mut setup);
+    let holder = new_taker(&mut setup);
+    let racer = new_taker(&mut setup);
+
+    let ix = reserve_ix(&setup, &accounts, &holder.pubkey(), 10);
+    assert!(setup.send(&[ix], &holder));
+
+    warp_slots(&mut setup, 10);
+    let ix = complete_swap_ix(&setup, &accounts, &holder.pubkey(), true);
+    assert!(!setup.send(&[ix], &holder));
+
+    let forfeit_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(accounts.order_y_ata, false),
+            AccountMeta::new(accounts.maker_y_ata, false),
+            AccountMeta::new_readonly(setup.taker_mint.pubkey(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![53], // variant 53 for ForfeitReservation
+    };
+    let ix = complete_swap_ix(&setup, &accounts, &racer.pubkey(), false);
+    assert!(setup.send(&[forfeit_ix, ix], &racer));
+
+    // the bond plus the fill
+    assert_eq!(setup.token_balance(&accounts.maker_y_ata), 1_800 + 180_000);
+    assert_eq!(setup.token_balance(&accounts.order_y_ata), 0);
+}
@@ -0,0 +1,156 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::{
+    math::fee::split_token_fee,
+    state::{OrderStatus, SwapOrder},
+};
+use test_program::{
+    mints::{mint_to_ata, setup_mint},
+    utils::TestSetup,
+    PROGRAM_KEY,
+};
+
+#[test]
+fn test_settle_three_order_ring() {
+    let mut setup = TestSetup::new();
+    let fee = 100u16;
+
+    let tx = setup.initialize_treasury(&setup.payer.pubkey(), fee);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+
+    let mint_z_setup = setup_mint(std::mem::take(&mut setup.svm), &setup.payer, 9).unwrap();
+    setup.svm = mint_z_setup.svm;
+
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+    let mint_z = mint_z_setup.mint.pubkey();
+
+    let maker_b = Keypair::new();
+    let maker_c = Keypair::new();
+    let submitter = Keypair::new();
+    for (maker, mint) in [
+        (&maker_b, &setup.taker_mint),
+        (&maker_c, &mint_z_setup.mint),
+    ] {
+        setup.svm.airdrop(&maker.pubkey(), 1_000_000_000).unwrap();
+        setup.svm = mint_to_ata(
+            std::mem::take(&mut setup.svm),
+            &setup.payer,
+            mint,
+            1_000_000,
+            &maker.pubkey(),
+        )
+        .unwrap();
+    }
+    setup
+        .svm
+        .airdrop(&submitter.pubkey(), 1_000_000_000)
+        .unwrap();
+
+    // A: X -> Y, B: Y -> Z, C: Z -> X, no pair crosses on its own
+    let tx = setup.initialize_order(100_000, 200_000);
+    setup.svm.send_transaction(tx).unwrap();
+    let order_a = setup.order_pda;
+    let order_b = setup.initialize_order_for(&maker_b, &mint_y, &mint_z, 210_000, 50_000);
+    let order_c = setup.initialize_order_for(&maker_c, &mint_z, &mint_x, 60_000, 100_000);
+
+    let payer = setup.payer.pubkey();
+    // (order, maker mint, maker paid from this escrow)
+    let ring = [
+        (order_a, mint_x, maker_c.pubkey()),
+        (order_b, mint_y, payer),
+        (order_c, mint_z, maker_b.pubkey()),
+    ];
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(submitter.pubkey(), true),
+        AccountMeta::new_readonly(treasury_pda, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_token_2022::id(), false),
+    ];
+    for (order, mint, receiver) in ring {
+        accounts.extend([
+            AccountMeta::new(order, false),
+            AccountMeta::new(get_associated_token_address(&order, &mint), false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(setup.create_ata(&receiver, &mint), false),
+            AccountMeta::new(setup.create_ata(&treasury_pda, &mint), false),
+            AccountMeta::new(setup.create_ata(&submitter.pubkey(), &mint), false),
+        ]);
+    }
+
+    let settle_ring_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts,
+        data: vec![21], // variant 21 for SettleRing
+    };
+
+    // Recurring orders only refill through CompleteSwap
+    assert!(!setup.send_with_order_state(
+        &order_c,
+        |order| order.funding_account = Pubkey::new_unique(),
+        std::slice::from_ref(&settle_ring_ix),
+        &submitter,
+    ));
+
+    // Chained orders re-list their proceeds through CompleteSwap
+    assert!(!setup.send_with_order_state(
+        &order_c,
+        |order| order.follow_up_taker_mint = Pubkey::new_unique(),
+        std::slice::from_ref(&settle_ring_ix),
+        &submitter,
+    ));
+
+    // Vested fills go through the schedule CompleteSwap sets up
+    assert!(!setup.send_with_order_state(
+        &order_c,
+        |order| order.vesting_duration = 86_400,
+        std::slice::from_ref(&settle_ring_ix),
+        &submitter,
+    ));
+
+    // Registry counters only follow fills that pass the registry
+    assert!(!setup.send_with_order_state(
+        &order_c,
+        |order| order.registry = Pubkey::new_unique(),
+        std::slice::from_ref(&settle_ring_ix),
+        &submitter,
+    ));
+
+    assert!(setup.send(&[settle_ring_ix], &submitter));
+
+    // (mint, receiver, amount the receiver asked for, surplus left for the submitter)
+    let expected = [
+        (mint_x, maker_c.pubkey(), 100_000, 0),
+        (mint_y, payer, 200_000, 10_000),
+        (mint_z, maker_b.pubkey(), 50_000, 10_000),
+    ];
+    for (mint, receiver, amount, surplus) in expected {
+        let (after_fee, fee) = split_token_fee(amount, fee).unwrap();
+        assert_eq!(
+            setup.token_balance(&get_associated_token_address(&receiver, &mint)),
+            after_fee
+        );
+        assert_eq!(
+            setup.token_balance(&get_associated_token_address(&treasury_pda, &mint)),
+            fee
+        );
+        assert_eq!(
+            setup.token_balance(&get_associated_token_address(&submitter.pubkey(), &mint)),
+            surplus
+        );
+    }
+
+    for order in [order_a, order_b, order_c] {
+        let order_account = setup.svm.get_account(&order).unwrap();
+        let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+        assert_eq!(order.status, OrderStatus::Filled);
+    }
+}
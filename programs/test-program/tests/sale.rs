@@ -0,0 +1,218 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+struct Sale {
+    end_ts: i64,
+    treasury_pda: Pubkey,
+    treasury_y_ata: Pubkey,
+    maker_y_ata: Pubkey,
+    order_y_ata: Pubkey,
+}
+
+/// Sells 100_000 X at 2 Y each, 40_000 per wallet, needing 50_000 sold to go through
+fn initialize_sale(setup: &mut TestSetup) -> Sale {
+    let maker = setup.payer.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let treasury_y_ata = setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+
+    let end_ts = setup.unix_timestamp() + 3_600;
+    let mut ix_data = vec![62]; // variant 62 for InitializeSale
+    ix_data.extend_from_slice(&100_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&200_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&end_ts.to_le_bytes());
+    ix_data.extend_from_slice(&40_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&50_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&0u64.to_le_bytes());
+    let tx = setup.create_order_tx(ix_data);
+    setup.svm.send_transaction(tx).unwrap();
+    let order_pda = setup.order_pda;
+    let order_y_ata = setup.create_ata(&order_pda, &mint_y);
+
+    Sale {
+        end_ts,
+        treasury_pda,
+        treasury_y_ata,
+        maker_y_ata,
+        order_y_ata,
+    }
+}
+
+fn new_buyer(setup: &mut TestSetup) -> Keypair {
+    let buyer = Keypair::new();
+    setup.svm.airdrop(&buyer.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        100_000,
+        &buyer.pubkey(),
+    )
+    .unwrap();
+    let mint_x = setup.maker_mint.pubkey();
+    setup.create_ata(&buyer.pubkey(), &mint_x);
+    buyer
+}
+
+fn fill_record_pda(setup: &TestSetup, buyer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"fill_record", setup.order_pda.as_ref(), buyer.as_ref()],
+        &PROGRAM_KEY,
+    )
+    .0
+}
+
+fn buy(setup: &mut TestSetup, sale: &Sale, buyer: &Keypair, amount: u64) -> bool {
+    let mut ix_data = vec![63]; // variant 63 for BuyFromSale
+    ix_data.extend_from_slice(&amount.to_le_bytes());
+    let ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(buyer.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(fill_record_pda(setup, &buyer.pubkey()), false),
+            AccountMeta::new(
+                get_associated_token_address(&buyer.pubkey(), &setup.taker_mint.pubkey()),
+                false,
+            ),
+            AccountMeta::new(sale.order_y_ata, false),
+            AccountMeta::new_readonly(setup.taker_mint.pubkey(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: ix_data,
+    };
+    setup.send(&[ix], buyer)
+}
+
+fn claim(setup: &mut TestSetup, sale: &Sale, buyer: &Keypair) -> bool {
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+    let ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(buyer.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(fill_record_pda(setup, &buyer.pubkey()), false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(
+                get_associated_token_address(&buyer.pubkey(), &mint_x),
+                false,
+            ),
+            AccountMeta::new(sale.order_y_ata, false),
+            AccountMeta::new(
+                get_associated_token_address(&buyer.pubkey(), &mint_y),
+                false,
+            ),
+            AccountMeta::new_readonly(mint_x, false),
+            AccountMeta::new_readonly(mint_y, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ],
+        data: vec![64], // variant 64 for ClaimSale
+    };
+    setup.send(&[ix], buyer)
+}
+
+fn finalize(setup: &mut TestSetup, sale: &Sale) -> bool {
+    let maker = setup.payer.pubkey();
+    let ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new(sale.order_y_ata, false),
+            AccountMeta::new(sale.maker_y_ata, false),
+            AccountMeta::new_readonly(sale.treasury_pda, false),
+            AccountMeta::new(sale.treasury_y_ata, false),
+            AccountMeta::new_readonly(setup.maker_mint.pubkey(), false),
+            AccountMeta::new_readonly(setup.taker_mint.pubkey(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ],
+        data: vec![65], // variant 65 for FinalizeSale
+    };
+    setup.svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&maker),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).is_ok()
+}
+
+#[test]
+fn test_sale_reaching_soft_cap_settles() {
+    let mut setup = TestSetup::new();
+    let sale = initialize_sale(&mut setup);
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+    let alice = new_buyer(&mut setup);
+    let bob = new_buyer(&mut setup);
+
+    assert!(buy(&mut setup, &sale, &alice, 30_000));
+    // the wallet cap counts every purchase
+    assert!(!buy(&mut setup, &sale, &alice, 10_001));
+    assert!(buy(&mut setup, &sale, &alice, 10_000));
+    assert!(buy(&mut setup, &sale, &bob, 20_000));
+    assert_eq!(setup.token_balance(&sale.order_y_ata), 120_000);
+
+    assert!(!claim(&mut setup, &sale, &alice));
+    assert!(!finalize(&mut setup, &sale));
+
+    setup.set_unix_timestamp(sale.end_ts);
+    assert!(!buy(&mut setup, &sale, &bob, 1));
+    assert!(finalize(&mut setup, &sale));
+    assert!(claim(&mut setup, &sale, &alice));
+    assert!(claim(&mut setup, &sale, &bob));
+
+    let mint_x = setup.maker_mint.pubkey();
+    let alice_x_ata = get_associated_token_address(&alice.pubkey(), &mint_x);
+    let bob_x_ata = get_associated_token_address(&bob.pubkey(), &mint_x);
+    assert_eq!(setup.token_balance(&alice_x_ata), 40_000);
+    assert_eq!(setup.token_balance(&bob_x_ata), 20_000);
+    assert_eq!(setup.token_balance(&sale.maker_y_ata), 120_000);
+    assert_eq!(
+        setup.token_balance(&setup.maker_token_ata),
+        maker_balance + 40_000
+    );
+    assert!(setup
+        .svm
+        .get_account(&fill_record_pda(&setup, &alice.pubkey()))
+        .is_none());
+}
+
+#[test]
+fn test_sale_missing_soft_cap_refunds() {
+    let mut setup = TestSetup::new();
+    let sale = initialize_sale(&mut setup);
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+    let alice = new_buyer(&mut setup);
+
+    assert!(buy(&mut setup, &sale, &alice, 30_000));
+
+    setup.set_unix_timestamp(sale.end_ts);
+    assert!(finalize(&mut setup, &sale));
+    assert!(claim(&mut setup, &sale, &alice));
+
+    let alice_y_ata = get_associated_token_address(&alice.pubkey(), &setup.taker_mint.pubkey());
+    assert_eq!(setup.token_balance(&alice_y_ata), 100_000);
+    assert_eq!(setup.token_balance(&sale.maker_y_ata), 0);
+    assert_eq!(
+        setup.token_balance(&setup.maker_token_ata),
+        maker_balance + 100_000
+    );
+}
@@ -0,0 +1,89 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+fn set_order_start(setup: &mut TestSetup, start_ts: i64) -> bool {
+    let mut ix_data = vec![45]; // variant 45 for SetOrderStart
+    ix_data.extend_from_slice(&start_ts.to_le_bytes());
+    let ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(setup.payer.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: ix_data,
+    };
+    setup.svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&setup.payer.pubkey()),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).is_ok()
+}
+
+#[test]
+fn test_scheduled_order_fills_after_start() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_x);
+    setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+
+    let tx = setup.initialize_order(100_000, 180_000);
+    setup.svm.send_transaction(tx).unwrap();
+    let now = setup.unix_timestamp();
+    assert!(!set_order_start(&mut setup, now));
+    assert!(set_order_start(&mut setup, now + 100));
+    // pushed back before the sale begins
+    assert!(set_order_start(&mut setup, now + 200));
+
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        180_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let taker_x_ata = setup.create_ata(&taker.pubkey(), &mint_x);
+
+    let complete_swap_ix = setup.complete_order_ix(&taker.pubkey(), vec![]);
+    let send_fill = |setup: &mut TestSetup| {
+        setup.svm.expire_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&complete_swap_ix),
+            Some(&taker.pubkey()),
+            &[&taker],
+            setup.svm.latest_blockhash(),
+        );
+        setup.svm.send_transaction(tx).is_ok()
+    };
+
+    assert!(!send_fill(&mut setup));
+
+    setup.set_unix_timestamp(now + 199);
+    assert!(!send_fill(&mut setup));
+
+    setup.set_unix_timestamp(now + 200);
+    // the schedule is fixed once the sale has begun
+    assert!(!set_order_start(&mut setup, now + 300));
+    assert!(send_fill(&mut setup));
+
+    assert_eq!(setup.token_balance(&taker_x_ata), 100_000);
+    assert_eq!(setup.token_balance(&maker_y_ata), 180_000);
+}
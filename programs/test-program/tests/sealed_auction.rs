@@ -0,0 +1,373 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::{
+    math::fee::{calculate_token_fee, split_token_fee},
+    state::{OrderStatus, SealedBid, SwapOrder},
+};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+fn sealed_bid_pda(setup: &TestSetup, bidder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"sealed_bid", setup.order_pda.as_ref(), bidder.as_ref()],
+        &PROGRAM_KEY,
+    )
+    .0
+}
+
+fn commit_bid_tx(
+    setup: &TestSetup,
+    bidder: &Keypair,
+    commitment: [u8; 32],
+    deposit: u64,
+) -> Transaction {
+    let sealed_bid = sealed_bid_pda(setup, &bidder.pubkey());
+    let taker_mint = setup.taker_mint.pubkey();
+
+    let mut ix_data = vec![12]; // variant 12 for CommitBid
+    ix_data.extend_from_slice(&commitment);
+    ix_data.extend_from_slice(&deposit.to_le_bytes());
+
+    let commit_bid_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(bidder.pubkey(), true),
+            AccountMeta::new_readonly(setup.order_pda, false),
+            AccountMeta::new(sealed_bid, false),
+            AccountMeta::new(
+                get_associated_token_address(&sealed_bid, &taker_mint),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(&bidder.pubkey(), &taker_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(taker_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: ix_data,
+    };
+
+    Transaction::new_signed_with_payer(
+        &[commit_bid_ix],
+        Some(&bidder.pubkey()),
+        &[bidder],
+        setup.svm.latest_blockhash(),
+    )
+}
+
+fn reveal_bid_tx(setup: &TestSetup, bidder: &Keypair, amount: u64, salt: [u8; 32]) -> Transaction {
+    let mut ix_data = vec![13]; // variant 13 for RevealBid
+    ix_data.extend_from_slice(&amount.to_le_bytes());
+    ix_data.extend_from_slice(&salt);
+
+    let reveal_bid_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(bidder.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(sealed_bid_pda(setup, &bidder.pubkey()), false),
+        ],
+        data: ix_data,
+    };
+
+    Transaction::new_signed_with_payer(
+        &[reveal_bid_ix],
+        Some(&bidder.pubkey()),
+        &[bidder],
+        setup.svm.latest_blockhash(),
+    )
+}
+
+fn refund_sealed_bid_ix(
+    setup: &TestSetup,
+    bidder: &Pubkey,
+    maker_taker_ata: &Pubkey,
+) -> Instruction {
+    let taker_mint = setup.taker_mint.pubkey();
+    let sealed_bid = sealed_bid_pda(setup, bidder);
+    Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(*bidder, false),
+            AccountMeta::new_readonly(setup.order_pda, false),
+            AccountMeta::new(sealed_bid, false),
+            AccountMeta::new(
+                get_associated_token_address(&sealed_bid, &taker_mint),
+                false,
+            ),
+            AccountMeta::new(get_associated_token_address(bidder, &taker_mint), false),
+            AccountMeta::new(*maker_taker_ata, false),
+            AccountMeta::new_readonly(taker_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![15], // variant 15 for RefundSealedBid
+    }
+}
+
+#[test]
+fn test_sealed_auction_reveal_settle_and_refund() {
+    let mut setup = TestSetup::new();
+    let fee = 100u16;
+    let penalty_bps = 1_000u16;
+
+    let tx = setup.initialize_treasury(&setup.payer.pubkey(), fee);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let maker_mint = setup.maker_mint.pubkey();
+    let taker_mint = setup.taker_mint.pubkey();
+
+    let maker_taker_ata = setup.create_ata(&setup.payer.pubkey(), &taker_mint);
+    let treasury_maker_ata = setup.create_ata(&treasury_pda, &maker_mint);
+    let treasury_taker_ata = setup.create_ata(&treasury_pda, &taker_mint);
+
+    let winner = Keypair::new();
+    let no_show = Keypair::new();
+    let late = Keypair::new();
+    for bidder in [&winner, &no_show, &late] {
+        setup.svm.airdrop(&bidder.pubkey(), 1_000_000_000).unwrap();
+        setup.svm = mint_to_ata(
+            setup.svm,
+            &setup.payer,
+            &setup.taker_mint,
+            1_000_000,
+            &bidder.pubkey(),
+        )
+        .unwrap();
+        setup.create_ata(&sealed_bid_pda(&setup, &bidder.pubkey()), &taker_mint);
+    }
+    let winner_maker_ata = setup.create_ata(&winner.pubkey(), &maker_mint);
+    let winner_taker_ata = get_associated_token_address(&winner.pubkey(), &taker_mint);
+    let no_show_taker_ata = get_associated_token_address(&no_show.pubkey(), &taker_mint);
+    let late_taker_ata = get_associated_token_address(&late.pubkey(), &taker_mint);
+
+    let maker_amount = 100_000u64;
+    let reserve_price = 150_000u64;
+    let commit_end_ts = setup.unix_timestamp() + 3_600;
+    let reveal_end_ts = commit_end_ts + 3_600;
+    let tx = setup.initialize_sealed_auction(
+        maker_amount,
+        reserve_price,
+        commit_end_ts,
+        reveal_end_ts,
+        penalty_bps,
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    let winning_bid = 200_000u64;
+    let winner_salt = [7u8; 32];
+    let winner_deposit = 250_000u64;
+    let commitment = SealedBid::commitment(winning_bid, &winner_salt, &winner.pubkey());
+    let tx = commit_bid_tx(&setup, &winner, commitment, winner_deposit);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let no_show_deposit = 300_000u64;
+    let commitment = SealedBid::commitment(290_000, &[9u8; 32], &no_show.pubkey());
+    let tx = commit_bid_tx(&setup, &no_show, commitment, no_show_deposit);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let late_deposit = 200_000u64;
+    let commitment = SealedBid::commitment(210_000, &[3u8; 32], &late.pubkey());
+    let tx = commit_bid_tx(&setup, &late, commitment, late_deposit);
+    setup.svm.send_transaction(tx).unwrap();
+
+    // The maker can't call the auction off while bids are sealed
+    let close_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(setup.payer.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: vec![7], // variant 7 for CloseOrder
+    };
+    let payer = setup.payer.insecure_clone();
+    assert!(!setup.send(std::slice::from_ref(&close_ix), &payer));
+
+    // Reveals only open after the commit phase
+    let tx = reveal_bid_tx(&setup, &winner, winning_bid, winner_salt);
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    setup.set_unix_timestamp(commit_end_ts);
+
+    // Wrong amount doesn't match the commitment
+    let tx = reveal_bid_tx(&setup, &winner, winning_bid + 1, winner_salt);
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    let tx = reveal_bid_tx(&setup, &winner, winning_bid, winner_salt);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.taker, winner.pubkey());
+    assert_eq!(order.taker_amount, winning_bid);
+
+    // or while they're being revealed
+    assert!(!setup.send(std::slice::from_ref(&close_ix), &payer));
+
+    setup.set_unix_timestamp(reveal_end_ts);
+
+    // A bidder who never revealed gets the deposit back less the penalty
+    let ix = refund_sealed_bid_ix(&setup, &no_show.pubkey(), &maker_taker_ata);
+    assert!(setup.send(&[ix], &payer));
+    let penalty = calculate_token_fee(no_show_deposit.into(), penalty_bps).unwrap() as u64;
+    assert_eq!(setup.token_balance(&no_show_taker_ata), 1_000_000 - penalty);
+    assert_eq!(setup.token_balance(&maker_taker_ata), penalty);
+    let no_show_bid = sealed_bid_pda(&setup, &no_show.pubkey());
+    assert!(setup.svm.get_account(&no_show_bid).is_none());
+
+    let winner_bid = sealed_bid_pda(&setup, &winner.pubkey());
+    let settle_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(winner_bid, false),
+            AccountMeta::new(
+                get_associated_token_address(&winner_bid, &taker_mint),
+                false,
+            ),
+            AccountMeta::new(winner.pubkey(), false),
+            AccountMeta::new(maker_taker_ata, false),
+            AccountMeta::new(winner_taker_ata, false),
+            AccountMeta::new(winner_maker_ata, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(treasury_maker_ata, false),
+            AccountMeta::new(treasury_taker_ata, false),
+            AccountMeta::new_readonly(maker_mint, false),
+            AccountMeta::new_readonly(taker_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ],
+        data: vec![14], // variant 14 for SettleSealedAuction
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[settle_ix],
+        Some(&setup.payer.pubkey()),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    let (maker_after_fee, maker_fee) = split_token_fee(maker_amount, fee).unwrap();
+    let (bid_after_fee, bid_fee) = split_token_fee(winning_bid, fee).unwrap();
+
+    assert_eq!(
+        setup.token_balance(&maker_taker_ata),
+        penalty + bid_after_fee
+    );
+    assert_eq!(setup.token_balance(&winner_maker_ata), maker_after_fee);
+    assert_eq!(setup.token_balance(&treasury_maker_ata), maker_fee);
+    assert_eq!(setup.token_balance(&treasury_taker_ata), bid_fee);
+    assert_eq!(
+        setup.token_balance(&winner_taker_ata),
+        1_000_000 - winning_bid
+    );
+
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Filled);
+
+    // Settling first doesn't get a non-revealer out of the penalty
+    let ix = refund_sealed_bid_ix(&setup, &late.pubkey(), &maker_taker_ata);
+    assert!(setup.send(&[ix], &payer));
+    let late_penalty = calculate_token_fee(late_deposit.into(), penalty_bps).unwrap() as u64;
+    assert_eq!(
+        setup.token_balance(&late_taker_ata),
+        1_000_000 - late_penalty
+    );
+    assert_eq!(
+        setup.token_balance(&maker_taker_ata),
+        penalty + late_penalty + bid_after_fee
+    );
+}
+
+#[test]
+fn test_sealed_auction_without_winning_bid_reclaimed() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let maker_kp = setup.payer.insecure_clone();
+    let taker_mint = setup.taker_mint.pubkey();
+    let maker_taker_ata = setup.create_ata(&maker, &taker_mint);
+
+    let bidder = Keypair::new();
+    setup.svm.airdrop(&bidder.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        setup.svm,
+        &setup.payer,
+        &setup.taker_mint,
+        1_000_000,
+        &bidder.pubkey(),
+    )
+    .unwrap();
+    setup.create_ata(&sealed_bid_pda(&setup, &bidder.pubkey()), &taker_mint);
+
+    let maker_balance = setup.token_balance(&setup.maker_token_ata);
+    let commit_end_ts = setup.unix_timestamp() + 3_600;
+    let reveal_end_ts = commit_end_ts + 3_600;
+    let tx = setup.initialize_sealed_auction(100_000, 150_000, commit_end_ts, reveal_end_ts, 1_000);
+    setup.svm.send_transaction(tx).unwrap();
+
+    // the only bid is under the reserve
+    let salt = [5u8; 32];
+    let commitment = SealedBid::commitment(120_000, &salt, &bidder.pubkey());
+    let tx = commit_bid_tx(&setup, &bidder, commitment, 150_000);
+    setup.svm.send_transaction(tx).unwrap();
+    setup.set_unix_timestamp(commit_end_ts);
+    let tx = reveal_bid_tx(&setup, &bidder, 120_000, salt);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let reclaim_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(setup.maker_token_ata, false),
+            AccountMeta::new_readonly(setup.maker_mint.pubkey(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![67], // variant 67 for ReclaimSealedAuction
+    };
+    // later reveals could still meet the reserve
+    assert!(!setup.send(std::slice::from_ref(&reclaim_ix), &maker_kp));
+
+    setup.set_unix_timestamp(reveal_end_ts);
+    let close_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: vec![7], // variant 7 for CloseOrder
+    };
+    // closing would strand the lot
+    assert!(!setup.send(std::slice::from_ref(&close_ix), &maker_kp));
+
+    assert!(setup.send(std::slice::from_ref(&reclaim_ix), &maker_kp));
+    assert_eq!(setup.token_balance(&setup.maker_token_ata), maker_balance);
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 0);
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Filled);
+    assert_eq!(order.maker_amount, 0);
+    assert!(!setup.send(std::slice::from_ref(&reclaim_ix), &maker_kp));
+
+    // the bidder gets the whole deposit back
+    let ix = refund_sealed_bid_ix(&setup, &bidder.pubkey(), &maker_taker_ata);
+    assert!(setup.send(&[ix], &maker_kp));
+    let bidder_taker_ata = get_associated_token_address(&bidder.pubkey(), &taker_mint);
+    assert_eq!(setup.token_balance(&bidder_taker_ata), 1_000_000);
+    assert_eq!(setup.token_balance(&maker_taker_ata), 0);
+
+    assert!(setup.send(std::slice::from_ref(&close_ix), &maker_kp));
+}
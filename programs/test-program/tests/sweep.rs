@@ -0,0 +1,159 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::{
+    math::fee::split_token_fee,
+    state::{OrderStatus, SwapOrder},
+};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+#[test]
+fn test_sweep_orders_up_to_max_spend() {
+    let mut setup = TestSetup::new();
+    let fee = 100u16;
+
+    let tx = setup.initialize_treasury(&setup.payer.pubkey(), fee);
+    setup.svm.send_transaction(tx).unwrap();
+
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let maker_mint = setup.maker_mint.pubkey();
+    let taker_mint = setup.taker_mint.pubkey();
+    let treasury_maker_ata = setup.create_ata(&treasury_pda, &maker_mint);
+    let treasury_taker_ata = setup.create_ata(&treasury_pda, &taker_mint);
+
+    let maker_b = Keypair::new();
+    let taker = Keypair::new();
+    for (owner, mint) in [(&maker_b, &setup.maker_mint), (&taker, &setup.taker_mint)] {
+        setup.svm.airdrop(&owner.pubkey(), 1_000_000_000).unwrap();
+        setup.svm = mint_to_ata(
+            std::mem::take(&mut setup.svm),
+            &setup.payer,
+            mint,
+            1_000_000,
+            &owner.pubkey(),
+        )
+        .unwrap();
+    }
+    let taker_ata = get_associated_token_address(&taker.pubkey(), &taker_mint);
+    let taker_maker_ata = setup.create_ata(&taker.pubkey(), &maker_mint);
+
+    // Best price first: 2 Y per X, then 3 Y per X
+    let tx = setup.initialize_order(100_000, 200_000);
+    setup.svm.send_transaction(tx).unwrap();
+    let order_a = setup.order_pda;
+    let order_b = setup.initialize_order_for(&maker_b, &maker_mint, &taker_mint, 100_000, 300_000);
+
+    let mut order_accounts = vec![];
+    for (order, maker) in [(order_a, setup.payer.pubkey()), (order_b, maker_b.pubkey())] {
+        order_accounts.extend([
+            AccountMeta::new(order, false),
+            AccountMeta::new(get_associated_token_address(&order, &maker_mint), false),
+            AccountMeta::new(setup.create_ata(&maker, &taker_mint), false),
+        ]);
+    }
+
+    let sweep_ix = |max_taker_amount: u64, min_maker_amount: u64| {
+        let mut ix_data = vec![22]; // variant 22 for SweepOrders
+        ix_data.extend_from_slice(&max_taker_amount.to_le_bytes());
+        ix_data.extend_from_slice(&min_maker_amount.to_le_bytes());
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(taker.pubkey(), true),
+            AccountMeta::new(taker_ata, false),
+            AccountMeta::new(taker_maker_ata, false),
+            AccountMeta::new_readonly(treasury_pda, false),
+            AccountMeta::new(treasury_maker_ata, false),
+            AccountMeta::new(treasury_taker_ata, false),
+            AccountMeta::new_readonly(maker_mint, false),
+            AccountMeta::new_readonly(taker_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ];
+        accounts.extend(order_accounts.iter().cloned());
+
+        Instruction {
+            program_id: PROGRAM_KEY,
+            accounts,
+            data: ix_data,
+        }
+    };
+
+    // 350k Y fills A and half of B, 150k X before fees
+    let (maker_after_fee, maker_fee) = split_token_fee(150_000, fee).unwrap();
+
+    // Asking for more X than the budget buys fails the whole sweep
+    let ix = sweep_ix(350_000, maker_after_fee + 1);
+    assert!(!setup.send(&[ix], &taker));
+
+    // Recurring orders only refill through CompleteSwap
+    let ix = sweep_ix(350_000, maker_after_fee);
+    assert!(!setup.send_with_order_state(
+        &order_b,
+        |order| order.funding_account = Pubkey::new_unique(),
+        std::slice::from_ref(&ix),
+        &taker,
+    ));
+
+    // Chained orders re-list their proceeds through CompleteSwap
+    assert!(!setup.send_with_order_state(
+        &order_b,
+        |order| order.follow_up_taker_mint = Pubkey::new_unique(),
+        std::slice::from_ref(&ix),
+        &taker,
+    ));
+
+    // Vested fills go through the schedule CompleteSwap sets up
+    assert!(!setup.send_with_order_state(
+        &order_b,
+        |order| order.vesting_duration = 86_400,
+        std::slice::from_ref(&ix),
+        &taker,
+    ));
+
+    // Registry counters only follow fills that pass the registry
+    assert!(!setup.send_with_order_state(
+        &order_b,
+        |order| order.registry = Pubkey::new_unique(),
+        std::slice::from_ref(&ix),
+        &taker,
+    ));
+
+    assert!(setup.send(&[ix], &taker));
+
+    let (a_after_fee, a_fee) = split_token_fee(200_000, fee).unwrap();
+    let (b_after_fee, b_fee) = split_token_fee(150_000, fee).unwrap();
+
+    assert_eq!(setup.token_balance(&taker_ata), 1_000_000 - 350_000);
+    assert_eq!(setup.token_balance(&taker_maker_ata), maker_after_fee);
+    assert_eq!(setup.token_balance(&treasury_maker_ata), maker_fee);
+    assert_eq!(setup.token_balance(&treasury_taker_ata), a_fee + b_fee);
+    assert_eq!(
+        setup.token_balance(&get_associated_token_address(
+            &setup.payer.pubkey(),
+            &taker_mint
+        )),
+        a_after_fee
+    );
+    assert_eq!(
+        setup.token_balance(&get_associated_token_address(
+            &maker_b.pubkey(),
+            &taker_mint
+        )),
+        b_after_fee
+    );
+
+    let order_account = setup.svm.get_account(&order_a).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Filled);
+
+    let order_account = setup.svm.get_account(&order_b).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Open);
+    assert_eq!(order.maker_amount, 50_000);
+    assert_eq!(order.taker_amount, 150_000);
+}
@@ -0,0 +1,121 @@
+use borsh::BorshSerialize;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use splerg_p2p::{
+    oracle::{pack_price, OraclePrice, PYTH_PROGRAM_ID},
+    state::TriggerCondition,
+};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+fn set_price(setup: &mut TestSetup, oracle: Pubkey, price: i64, publish_time: i64) {
+    let data = pack_price(&OraclePrice {
+        price,
+        conf: 10_000,
+        expo: -8,
+        publish_time,
+    });
+    setup
+        .svm
+        .set_account(
+            oracle,
+            Account {
+                lamports: setup.svm.minimum_balance_for_rent_exemption(data.len()),
+                data,
+                owner: PYTH_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_stop_order_fills_once_price_drops() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &mint_x);
+    setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+
+    let now = setup.unix_timestamp();
+    let oracle = Pubkey::new_unique();
+    set_price(&mut setup, oracle, 250_000_000, now);
+
+    // sell X once it trades at or below 2.0
+    let tx = setup.initialize_order(100_000, 180_000);
+    setup.svm.send_transaction(tx).unwrap();
+    let mut ix_data = vec![44]; // variant 44 for SetOrderTrigger
+    TriggerCondition::Below.serialize(&mut ix_data).unwrap();
+    ix_data.extend_from_slice(&200_000_000i64.to_le_bytes());
+    ix_data.extend_from_slice(&60i64.to_le_bytes());
+    ix_data.extend_from_slice(&50u16.to_le_bytes());
+    let trigger_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new_readonly(oracle, false),
+        ],
+        data: ix_data,
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[trigger_ix],
+        Some(&maker),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        180_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let taker_x_ata = setup.create_ata(&taker.pubkey(), &mint_x);
+
+    let complete_swap_ix = setup.complete_order_ix(
+        &taker.pubkey(),
+        vec![AccountMeta::new_readonly(oracle, false)],
+    );
+    let send_fill = |setup: &mut TestSetup| {
+        setup.svm.expire_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            std::slice::from_ref(&complete_swap_ix),
+            Some(&taker.pubkey()),
+            &[&taker],
+            setup.svm.latest_blockhash(),
+        );
+        setup.svm.send_transaction(tx).is_ok()
+    };
+
+    // price above the threshold
+    assert!(!send_fill(&mut setup));
+
+    // below the threshold but published too long ago
+    set_price(&mut setup, oracle, 190_000_000, now);
+    setup.set_unix_timestamp(now + 61);
+    assert!(!send_fill(&mut setup));
+
+    set_price(&mut setup, oracle, 190_000_000, now + 61);
+    assert!(send_fill(&mut setup));
+
+    assert_eq!(setup.token_balance(&taker_x_ata), 100_000);
+    assert_eq!(setup.token_balance(&maker_y_ata), 180_000);
+}
@@ -0,0 +1,133 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::state::{OrderStatus, SwapOrder};
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+fn complete_swap_tx(setup: &TestSetup, taker: &Keypair) -> Transaction {
+    let maker_mint = setup.maker_mint.pubkey();
+    let taker_mint = setup.taker_mint.pubkey();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+
+    let complete_swap_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(taker.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(
+                get_associated_token_address(&setup.payer.pubkey(), &taker_mint),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(&taker.pubkey(), &taker_mint),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(&taker.pubkey(), &maker_mint),
+                false,
+            ),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(treasury_pda, false),
+            AccountMeta::new(
+                get_associated_token_address(&treasury_pda, &maker_mint),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(&treasury_pda, &taker_mint),
+                false,
+            ),
+            AccountMeta::new(maker_mint, false),
+            AccountMeta::new(taker_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+        ],
+        data: vec![6], // variant 6 for CompleteSwap
+    };
+    Transaction::new_signed_with_payer(
+        &[complete_swap_ix],
+        Some(&taker.pubkey()),
+        &[taker],
+        setup.svm.latest_blockhash(),
+    )
+}
+
+#[test]
+fn test_twap_order_caps_fills_at_unlocked_slices() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let maker_mint = setup.maker_mint.pubkey();
+    let taker_mint = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    setup.create_ata(&treasury_pda, &maker_mint);
+    setup.create_ata(&treasury_pda, &taker_mint);
+    let maker_taker_ata = setup.create_ata(&maker, &taker_mint);
+
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        1_000_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let taker_maker_ata = setup.create_ata(&taker.pubkey(), &maker_mint);
+
+    // 240_000 X for 480_000 Y, released in 24 hourly slices
+    let start_ts = setup.unix_timestamp();
+    let mut ix_data = vec![30]; // variant 30 for InitializeTwapOrder
+    ix_data.extend_from_slice(&240_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&480_000u64.to_le_bytes());
+    ix_data.extend_from_slice(&3_600i64.to_le_bytes());
+    ix_data.extend_from_slice(&10_000u64.to_le_bytes());
+    let tx = setup.create_order_tx(ix_data);
+    setup.svm.send_transaction(tx).unwrap();
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 240_000);
+
+    // only the first slice is fillable at the start
+    let tx = complete_swap_tx(&setup, &taker);
+    setup.svm.send_transaction(tx).unwrap();
+    assert_eq!(setup.token_balance(&taker_maker_ata), 10_000);
+    assert_eq!(setup.token_balance(&maker_taker_ata), 20_000);
+
+    setup.svm.expire_blockhash();
+    let tx = complete_swap_tx(&setup, &taker);
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    // two more hours release two more slices
+    setup.set_unix_timestamp(start_ts + 2 * 3_600);
+    setup.svm.expire_blockhash();
+    let tx = complete_swap_tx(&setup, &taker);
+    setup.svm.send_transaction(tx).unwrap();
+    assert_eq!(setup.token_balance(&taker_maker_ata), 30_000);
+    assert_eq!(setup.token_balance(&maker_taker_ata), 60_000);
+
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Open);
+    assert_eq!(order.maker_amount, 210_000);
+    assert_eq!(order.taker_amount, 420_000);
+    assert_eq!(order.twap_filled, 30_000);
+
+    // after the schedule ends the rest fills at once
+    setup.set_unix_timestamp(start_ts + 24 * 3_600);
+    setup.svm.expire_blockhash();
+    let tx = complete_swap_tx(&setup, &taker);
+    setup.svm.send_transaction(tx).unwrap();
+    assert_eq!(setup.token_balance(&taker_maker_ata), 240_000);
+    assert_eq!(setup.token_balance(&setup.order_maker_token_ata), 0);
+
+    let order_account = setup.svm.get_account(&setup.order_pda).unwrap();
+    let order = SwapOrder::try_from_slice(&order_account.data).unwrap();
+    assert_eq!(order.status, OrderStatus::Filled);
+}
@@ -0,0 +1,153 @@
+use borsh::BorshDeserialize;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use splerg_p2p::state::Vesting;
+use test_program::{mints::mint_to_ata, utils::TestSetup, PROGRAM_KEY};
+
+fn claim_vested_tx(
+    setup: &TestSetup,
+    taker: &Keypair,
+    vesting_pda: &Pubkey,
+    vesting_ata: &Pubkey,
+) -> Transaction {
+    let mint = setup.maker_mint.pubkey();
+    let claim_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(taker.pubkey(), true),
+            AccountMeta::new(*vesting_pda, false),
+            AccountMeta::new(*vesting_ata, false),
+            AccountMeta::new(get_associated_token_address(&taker.pubkey(), &mint), false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: vec![35], // variant 35 for ClaimVested
+    };
+    Transaction::new_signed_with_payer(
+        &[claim_ix],
+        Some(&taker.pubkey()),
+        &[taker],
+        setup.svm.latest_blockhash(),
+    )
+}
+
+#[test]
+fn test_vesting_order_releases_tokens_over_time() {
+    let mut setup = TestSetup::new();
+    let maker = setup.payer.pubkey();
+    let mint_x = setup.maker_mint.pubkey();
+    let mint_y = setup.taker_mint.pubkey();
+
+    let tx = setup.initialize_treasury(&maker, 0);
+    setup.svm.send_transaction(tx).unwrap();
+    let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], &PROGRAM_KEY);
+    let treasury_x_ata = setup.create_ata(&treasury_pda, &mint_x);
+    let treasury_y_ata = setup.create_ata(&treasury_pda, &mint_y);
+    let maker_y_ata = setup.create_ata(&maker, &mint_y);
+
+    let tx = setup.initialize_order(100_000, 200_000);
+    setup.svm.send_transaction(tx).unwrap();
+
+    // 100s cliff, fully vested 1_000s after the fill
+    let mut ix_data = vec![34]; // variant 34 for SetVestingSchedule
+    ix_data.extend_from_slice(&100i64.to_le_bytes());
+    ix_data.extend_from_slice(&1_000i64.to_le_bytes());
+    let schedule_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new_readonly(maker, true),
+            AccountMeta::new(setup.order_pda, false),
+        ],
+        data: ix_data,
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[schedule_ix],
+        Some(&maker),
+        &[&setup.payer],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    let taker = Keypair::new();
+    setup.svm.airdrop(&taker.pubkey(), 1_000_000_000).unwrap();
+    setup.svm = mint_to_ata(
+        std::mem::take(&mut setup.svm),
+        &setup.payer,
+        &setup.taker_mint,
+        1_000_000,
+        &taker.pubkey(),
+    )
+    .unwrap();
+    let taker_x_ata = setup.create_ata(&taker.pubkey(), &mint_x);
+
+    let (vesting_pda, _) =
+        Pubkey::find_program_address(&[b"vesting", setup.order_pda.as_ref()], &PROGRAM_KEY);
+    let vesting_ata = setup.create_ata(&vesting_pda, &mint_x);
+
+    let complete_swap_ix = Instruction {
+        program_id: PROGRAM_KEY,
+        accounts: vec![
+            AccountMeta::new(taker.pubkey(), true),
+            AccountMeta::new(setup.order_pda, false),
+            AccountMeta::new(maker_y_ata, false),
+            AccountMeta::new(
+                get_associated_token_address(&taker.pubkey(), &mint_y),
+                false,
+            ),
+            AccountMeta::new(vesting_ata, false),
+            AccountMeta::new(setup.order_maker_token_ata, false),
+            AccountMeta::new(treasury_pda, false),
+            AccountMeta::new(treasury_x_ata, false),
+            AccountMeta::new(treasury_y_ata, false),
+            AccountMeta::new(mint_x, false),
+            AccountMeta::new(mint_y, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+            AccountMeta::new(vesting_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: vec![6], // variant 6 for CompleteSwap
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[complete_swap_ix],
+        Some(&taker.pubkey()),
+        &[&taker],
+        setup.svm.latest_blockhash(),
+    );
+    setup.svm.send_transaction(tx).unwrap();
+
+    assert_eq!(setup.token_balance(&maker_y_ata), 200_000);
+    assert_eq!(setup.token_balance(&vesting_ata), 100_000);
+    assert_eq!(setup.token_balance(&taker_x_ata), 0);
+
+    let vesting_account = setup.svm.get_account(&vesting_pda).unwrap();
+    let vesting = Vesting::try_from_slice(&vesting_account.data).unwrap();
+    assert_eq!(vesting.beneficiary, taker.pubkey());
+    assert_eq!(vesting.total_amount, 100_000);
+    assert_eq!(vesting.cliff_ts, vesting.start_ts + 100);
+    assert_eq!(vesting.end_ts, vesting.start_ts + 1_000);
+
+    // nothing before the cliff
+    let tx = claim_vested_tx(&setup, &taker, &vesting_pda, &vesting_ata);
+    assert!(setup.svm.send_transaction(tx).is_err());
+
+    setup.set_unix_timestamp(vesting.start_ts + 500);
+    setup.svm.expire_blockhash();
+    let tx = claim_vested_tx(&setup, &taker, &vesting_pda, &vesting_ata);
+    setup.svm.send_transaction(tx).unwrap();
+    assert_eq!(setup.token_balance(&taker_x_ata), 50_000);
+
+    setup.set_unix_timestamp(vesting.end_ts);
+    setup.svm.expire_blockhash();
+    let tx = claim_vested_tx(&setup, &taker, &vesting_pda, &vesting_ata);
+    setup.svm.send_transaction(tx).unwrap();
+    assert_eq!(setup.token_balance(&taker_x_ata), 100_000);
+    assert!(setup.svm.get_account(&vesting_pda).is_none());
+}